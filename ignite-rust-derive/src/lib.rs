@@ -0,0 +1,344 @@
+//! Derive and attribute macros of the Ignite Rust thin client. Use them through the
+//! `ignite-rust` crate.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta};
+use syn::{FnArg, ItemTrait, Pat, ReturnType, TraitItem, TraitItemMethod};
+
+/// Derive `FromRow`, mapping columns of the SQL query result to the fields of the struct
+/// by name, case-insensitively. Column name can be set with `#[ignite(rename = "NAME")]`.
+#[proc_macro_derive(FromRow, attributes(ignite))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    from_row(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generate implementation of `FromRow`.
+fn from_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "FromRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "FromRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut values = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("Named field");
+
+        let column = match column_name(field)? {
+            Some(column) => column,
+            None => ident.to_string().trim_start_matches("r#").to_owned(),
+        };
+
+        values.push(quote! { #ident: row.take(#column)? });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ignite_rust::FromRow for #name #ty_generics #where_clause {
+            fn from_row(row: &mut ::ignite_rust::Row<'_>) -> ::ignite_rust::IgniteResult<Self> {
+                Ok(Self { #(#values),* })
+            }
+        }
+    })
+}
+
+/// Get column name set with the `ignite(rename = "...")` attribute.
+fn column_name(field: &syn::Field) -> syn::Result<Option<String>> {
+    let mut column = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("ignite"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "Expected #[ignite(rename = \"...\")]",
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                    match nv.lit {
+                        Lit::Str(s) => column = Some(s.value()),
+                        lit => return Err(Error::new(lit.span(), "Column name must be a string")),
+                    }
+                }
+                nested => return Err(Error::new(nested.span(), "Unknown ignite attribute")),
+            }
+        }
+    }
+
+    Ok(column)
+}
+
+/// Turn the trait into the proxy of the service, implemented for `ServiceProxy`.
+///
+/// Every method takes `&self` and returns `IgniteResult<T>`, where `T` implements
+/// `FromValue`. It may be declared `async`. Arguments have to be convertible into
+/// `IgniteValue`. The method is invoked by the lower camel case name, e.g.
+/// `get_total` invokes `getTotal`, unless it is set with `#[ignite(rename = "...")]`.
+/// Java types of the parameters are passed with `#[ignite(param_types("int", ...))]`.
+#[proc_macro_attribute]
+pub fn service_proxy(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemTrait);
+
+    if !attr.is_empty() {
+        let attr = TokenStream2::from(attr);
+        return Error::new(attr.span(), "service_proxy does not take arguments")
+            .into_compile_error()
+            .into();
+    }
+
+    proxy(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generate the trait and its implementation for `ServiceProxy`.
+fn proxy(mut input: ItemTrait) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() || !input.supertraits.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Service proxy trait can not have generics or supertraits",
+        ));
+    }
+
+    let mut methods = Vec::new();
+    let mut impls = Vec::new();
+
+    for item in &mut input.items {
+        let method = match item {
+            TraitItem::Method(method) => method,
+            item => {
+                return Err(Error::new(
+                    item.span(),
+                    "Service proxy trait can only have methods",
+                ))
+            }
+        };
+
+        let (signature, body) = proxy_method(method)?;
+
+        let attrs = method
+            .attrs
+            .iter()
+            .filter(|attr| !attr.path.is_ident("ignite"));
+
+        methods.push(quote! { #(#attrs)* #signature; });
+        impls.push(quote! { #signature #body });
+    }
+
+    let vis = &input.vis;
+    let attrs = &input.attrs;
+    let name = &input.ident;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis trait #name {
+            #(#methods)*
+        }
+
+        impl #name for ::ignite_rust::ServiceProxy {
+            #(#impls)*
+        }
+    })
+}
+
+/// Generate signature of the method, returning boxed future, and its body invoking the service.
+fn proxy_method(method: &TraitItemMethod) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let sig = &method.sig;
+
+    if method.default.is_some() {
+        return Err(Error::new(
+            method.span(),
+            "Service proxy method can not have a body",
+        ));
+    }
+
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new(
+            sig.generics.span(),
+            "Service proxy method can not have generics",
+        ));
+    }
+
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return Err(Error::new(
+                sig.span(),
+                "Service proxy method has to return IgniteResult",
+            ))
+        }
+    };
+
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(Error::new(
+                sig.span(),
+                "Service proxy method has to take &self",
+            ))
+        }
+    }
+
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+    for input in inputs {
+        let typed = match input {
+            FnArg::Typed(typed) => typed,
+            FnArg::Receiver(_) => unreachable!("Receiver can only be the first argument"),
+        };
+
+        match &*typed.pat {
+            Pat::Ident(pat) => args.push(pat.ident.clone()),
+            pat => {
+                return Err(Error::new(
+                    pat.span(),
+                    "Service proxy method arguments have to be identifiers",
+                ))
+            }
+        }
+        types.push(&typed.ty);
+    }
+
+    let (rename, param_types) = method_options(method)?;
+
+    let method_name = rename.unwrap_or_else(|| java_method_name(&sig.ident.to_string()));
+
+    let param_types = match param_types {
+        Some(param_types) => {
+            if param_types.len() != args.len() {
+                return Err(Error::new(
+                    sig.span(),
+                    "Number of parameter types has to match the number of arguments",
+                ));
+            }
+
+            quote! { ::std::option::Option::Some(&[#(#param_types),*]) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+
+    let ident = &sig.ident;
+
+    let signature = quote! {
+        fn #ident<'ignite>(&'ignite self, #(#args: #types),*)
+            -> ::std::pin::Pin<::std::boxed::Box<
+                dyn ::std::future::Future<Output = #output> + ::std::marker::Send + 'ignite
+            >>
+    };
+
+    let body = quote! {
+        {
+            let args = ::std::vec![#(::ignite_rust::IgniteValue::from(#args)),*];
+
+            ::std::boxed::Box::pin(async move {
+                ::ignite_rust::ServiceProxy::invoke(self, #method_name, &args, #param_types).await
+            })
+        }
+    };
+
+    Ok((signature, body))
+}
+
+/// Get method name and parameter types set with the `ignite(...)` attribute.
+fn method_options(method: &TraitItemMethod) -> syn::Result<(Option<String>, Option<Vec<String>>)> {
+    let mut rename = None;
+    let mut param_types = None;
+
+    for attr in method
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("ignite"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "Expected #[ignite(rename = \"...\", param_types(...))]",
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                    match nv.lit {
+                        Lit::Str(s) => rename = Some(s.value()),
+                        lit => return Err(Error::new(lit.span(), "Method name must be a string")),
+                    }
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("param_types") => {
+                    let mut types = Vec::new();
+                    for nested in list.nested {
+                        match nested {
+                            NestedMeta::Lit(Lit::Str(s)) => types.push(s.value()),
+                            nested => {
+                                return Err(Error::new(
+                                    nested.span(),
+                                    "Parameter type must be a string",
+                                ))
+                            }
+                        }
+                    }
+                    param_types = Some(types);
+                }
+                nested => return Err(Error::new(nested.span(), "Unknown ignite attribute")),
+            }
+        }
+    }
+
+    Ok((rename, param_types))
+}
+
+/// Convert snake case name of the Rust method to the lower camel case name of the Java one.
+fn java_method_name(name: &str) -> String {
+    let mut res = String::with_capacity(name.len());
+    let mut upper = false;
+
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' {
+            upper = !res.is_empty();
+        } else if upper {
+            res.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
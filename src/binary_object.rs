@@ -0,0 +1,583 @@
+use std::collections::HashMap;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{header, utils, write_full, IgniteHash, ProtocolType};
+use crate::protocol::{InStream, OutStream};
+use crate::IgniteError;
+
+/// Version of the binary object format.
+const PROTO_VER: i8 = 1;
+
+/// Length of the binary object header, including the type header.
+const HEADER_LEN: i32 = 24;
+
+/// Flag: the object is of the user type.
+const FLAG_USER_TYPE: i16 = 0x0001;
+
+/// Flag: the object has a schema in the footer.
+const FLAG_HAS_SCHEMA: i16 = 0x0002;
+
+/// Flag: the object has raw data after the fields, which offset ends the footer.
+const FLAG_HAS_RAW_DATA: i16 = 0x0004;
+
+/// Flag: field offsets in the schema are one byte long.
+const FLAG_OFFSET_ONE_BYTE: i16 = 0x0008;
+
+/// Flag: field offsets in the schema are two bytes long.
+const FLAG_OFFSET_TWO_BYTES: i16 = 0x0010;
+
+/// Flag: the schema contains offsets only, without field IDs.
+const FLAG_COMPACT_FOOTER: i16 = 0x0020;
+
+/// Basis of the FNV1 hash, used to calculate schema ID.
+const FNV1_OFFSET_BASIS: i32 = 0x811C_9DC5_u32 as i32;
+
+/// Prime of the FNV1 hash, used to calculate schema ID.
+const FNV1_PRIME: i32 = 0x0100_0193;
+
+/// Field of the binary object.
+#[derive(Debug, Clone, PartialEq)]
+struct BinaryField {
+    id: i32,
+    data: Vec<u8>,
+    hash: Option<i32>,
+}
+
+/// Object as it was read from the server.
+#[derive(Debug, Clone, PartialEq)]
+struct SerializedObject {
+    /// Bytes of the object, starting with the type header.
+    bytes: Vec<u8>,
+    /// Offsets of the fields in the order of the schema.
+    offsets: Vec<usize>,
+    /// Offset the field data ends at, which is where the raw data or the footer starts.
+    data_end: usize,
+}
+
+/// Object in the Ignite binary format, which can be used as a cache key or
+/// value without a corresponding Java class on the server.
+///
+/// Hash code of the object is calculated over its field data, the same way as
+/// the server does it, so binary keys are colocated correctly. If the cache has
+/// an affinity key field configured for the type, the hash code of the field
+/// value is used to find the partition instead.
+///
+/// Objects read from the server are written back as they are, until a field is
+/// set. Objects written with compact footer, which is the server default, only
+/// have field offsets in the footer, so their fields are only available by
+/// position with `field_data_at()`.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::BinaryObject;
+///
+/// let mut key = BinaryObject::new("PersonKey");
+/// key.set_field("id", &42i32);
+/// key.set_field("company", &"Acme".to_owned());
+///
+/// assert_eq!(key.field_ids(), vec![3355, 950_484_093]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryObject {
+    type_id: i32,
+    fields: Vec<BinaryField>,
+    serialized: Option<SerializedObject>,
+}
+
+impl BinaryObject {
+    /// Make new object of the type with the given name, without fields.
+    pub fn new(type_name: &str) -> Self {
+        Self::with_type_id(Self::type_id_of(type_name))
+    }
+
+    /// Make new object of the type with the given ID, without fields.
+    pub fn with_type_id(type_id: i32) -> Self {
+        Self {
+            type_id,
+            fields: Vec::new(),
+            serialized: None,
+        }
+    }
+
+    /// Get ID of the type with the given name, the same way the server does it.
+    pub fn type_id_of(type_name: &str) -> i32 {
+        utils::java_string_hash(&type_name.to_lowercase())
+    }
+
+    /// Get ID of the field with the given name, the same way the server does it.
+    pub fn field_id_of(field_name: &str) -> i32 {
+        utils::java_string_hash(&field_name.to_lowercase())
+    }
+
+    /// Get type ID of the object.
+    pub fn type_id(&self) -> i32 {
+        self.type_id
+    }
+
+    /// Get IDs of the fields, in the order they are written.
+    /// IDs of the fields of the object with compact footer are not known.
+    pub fn field_ids(&self) -> Vec<i32> {
+        self.fields.iter().map(|field| field.id).collect()
+    }
+
+    /// Check if the object was read with compact footer, so IDs of its fields are not known.
+    pub fn has_compact_footer(&self) -> bool {
+        match &self.serialized {
+            Some(obj) => InStream::new(&obj.bytes[2..]).read_i16() & FLAG_COMPACT_FOOTER != 0,
+            None => false,
+        }
+    }
+
+    /// Set value of the field, replacing the existing one.
+    ///
+    /// The object read from the server is written in full from now on, so its raw
+    /// data, and the fields of the object with compact footer, are dropped.
+    pub fn set_field<T>(&mut self, name: &str, value: &T)
+    where
+        T: ProtocolType + IgniteHash + ?Sized,
+    {
+        let id = Self::field_id_of(name);
+
+        let out = OutStream::new();
+        write_full(value, &out);
+
+        let field = BinaryField {
+            id,
+            data: out.into_memory().into_vec(),
+            hash: Some(value.ignite_hash()),
+        };
+
+        match self.fields.iter_mut().find(|field| field.id == id) {
+            Some(existing) => *existing = field,
+            None => self.fields.push(field),
+        }
+
+        self.serialized = None;
+    }
+
+    /// Get serialized value of the field with the given name, including the type header.
+    pub fn field_data(&self, name: &str) -> Option<&[u8]> {
+        let id = Self::field_id_of(name);
+
+        self.fields
+            .iter()
+            .find(|field| field.id == id)
+            .map(|field| field.data.as_slice())
+    }
+
+    /// Get serialized value of the field by its position in the schema,
+    /// including the type header.
+    pub fn field_data_at(&self, index: usize) -> Option<&[u8]> {
+        match &self.serialized {
+            Some(obj) => {
+                let begin = *obj.offsets.get(index)?;
+                let end = obj.offsets.get(index + 1).copied().unwrap_or(obj.data_end);
+
+                Some(&obj.bytes[begin..end])
+            }
+            None => self.fields.get(index).map(|field| field.data.as_slice()),
+        }
+    }
+
+    /// Get number of the fields.
+    pub fn field_count(&self) -> usize {
+        match &self.serialized {
+            Some(obj) => obj.offsets.len(),
+            None => self.fields.len(),
+        }
+    }
+
+    /// Read object, which type header is already read, failing instead of panicking
+    /// if the object can not be read.
+    pub(crate) fn read_checked(stream: &InStream) -> IgniteResult<BinaryObject> {
+        let malformed = || IgniteError::new("Binary object is malformed");
+
+        // Type header is already read.
+        if stream.remaining() < HEADER_LEN as usize - 1 {
+            return Err(malformed());
+        }
+
+        let hdr = InStream::new(stream.peek_bytes(HEADER_LEN as usize - 1));
+        let _ver = hdr.read_i8();
+        let flags = hdr.read_i16();
+        let type_id = hdr.read_i32();
+        let _hash = hdr.read_i32();
+        let len = hdr.read_i32();
+
+        if len < HEADER_LEN || len as usize - 1 > stream.remaining() {
+            return Err(malformed());
+        }
+
+        let mut bytes = Vec::with_capacity(len as usize);
+        bytes.push(header::OBJECT as u8);
+        bytes.extend_from_slice(stream.read_bytes(len as usize - 1));
+
+        let (schema, data_end) = read_schema(&bytes, flags).ok_or_else(malformed)?;
+
+        let mut obj = BinaryObject::with_type_id(type_id);
+
+        for (i, (id, begin)) in schema.iter().enumerate() {
+            let end = schema.get(i + 1).map_or(data_end, |(_, end)| *end);
+
+            if let Some(id) = id {
+                obj.fields.push(BinaryField {
+                    id: *id,
+                    data: bytes[*begin..end].to_vec(),
+                    hash: None,
+                });
+            }
+        }
+
+        obj.serialized = Some(SerializedObject {
+            offsets: schema.into_iter().map(|(_, offset)| offset).collect(),
+            data_end,
+            bytes,
+        });
+
+        Ok(obj)
+    }
+
+    /// Calculate ID of the schema, which is FNV1 hash over the field IDs.
+    fn schema_id(&self) -> i32 {
+        self.fields
+            .iter()
+            .fold(FNV1_OFFSET_BASIS, |schema_id, field| {
+                (0..4).fold(schema_id, |schema_id, byte| {
+                    (schema_id ^ ((field.id >> (byte * 8)) & 0xFF)).wrapping_mul(FNV1_PRIME)
+                })
+            })
+    }
+
+    /// Get length of the field data.
+    fn data_len(&self) -> i32 {
+        self.fields
+            .iter()
+            .map(|field| field.data.len() as i32)
+            .sum()
+    }
+}
+
+/// Schema of the object: field IDs, unless the footer is compact, and offsets.
+type Schema = Vec<(Option<i32>, usize)>;
+
+/// Read schema of the object, along with the offset the field data ends at.
+/// None is returned if the offsets are out of the object bounds.
+fn read_schema(bytes: &[u8], flags: i16) -> Option<(Schema, usize)> {
+    let header_len = HEADER_LEN as usize;
+    let len = bytes.len();
+    let has_raw_data = flags & FLAG_HAS_RAW_DATA != 0;
+
+    // Offset of the schema, or of the raw data if there is no schema.
+    let schema_offset = InStream::new(&bytes[20..]).read_i32() as usize;
+
+    if flags & FLAG_HAS_SCHEMA == 0 {
+        let data_end = if has_raw_data { schema_offset } else { len };
+
+        return if (header_len..=len).contains(&data_end) {
+            Some((Vec::new(), data_end))
+        } else {
+            None
+        };
+    }
+
+    let footer_end = if has_raw_data {
+        len.checked_sub(4)?
+    } else {
+        len
+    };
+    if !(header_len..=footer_end).contains(&schema_offset) {
+        return None;
+    }
+
+    let data_end = if has_raw_data {
+        InStream::new(&bytes[footer_end..]).read_i32() as usize
+    } else {
+        schema_offset
+    };
+    if !(header_len..=schema_offset).contains(&data_end) {
+        return None;
+    }
+
+    let compact = flags & FLAG_COMPACT_FOOTER != 0;
+    let offset_len = if flags & FLAG_OFFSET_ONE_BYTE != 0 {
+        1
+    } else if flags & FLAG_OFFSET_TWO_BYTES != 0 {
+        2
+    } else {
+        4
+    };
+    let entry_len = if compact { offset_len } else { 4 + offset_len };
+
+    let footer = &bytes[schema_offset..footer_end];
+    if !footer.len().is_multiple_of(entry_len) {
+        return None;
+    }
+
+    let stream = InStream::new(footer);
+    let mut schema: Schema = Vec::with_capacity(footer.len() / entry_len);
+
+    while stream.remaining() > 0 {
+        let id = if compact {
+            None
+        } else {
+            Some(stream.read_i32())
+        };
+
+        let offset = match offset_len {
+            1 => stream.read_i8() as u8 as usize,
+            2 => stream.read_i16() as u16 as usize,
+            _ => stream.read_i32() as usize,
+        };
+
+        let prev = schema.last().map_or(header_len, |(_, prev)| *prev);
+        if offset < prev || offset > data_end {
+            return None;
+        }
+
+        schema.push((id, offset));
+    }
+
+    Some((schema, data_end))
+}
+
+impl IgniteHash for BinaryObject {
+    /// Hash code of the object read from the server is the one the server calculated.
+    fn ignite_hash(&self) -> i32 {
+        match &self.serialized {
+            Some(obj) => InStream::new(&obj.bytes[8..]).read_i32(),
+            None => utils::java_bytes_hash(self.fields.iter().flat_map(|field| field.data.iter())),
+        }
+    }
+
+    fn affinity_hash(&self, key_fields: &HashMap<i32, i32>) -> i32 {
+        key_fields
+            .get(&self.type_id)
+            .and_then(|field_id| self.fields.iter().find(|field| field.id == *field_id))
+            .and_then(|field| field.hash)
+            .unwrap_or_else(|| self.ignite_hash())
+    }
+}
+
+impl ProtocolType for BinaryObject {
+    type Item = BinaryObject;
+    const HEADER: i8 = header::OBJECT;
+
+    /// Objects are written with the full schema, four bytes per field offset,
+    /// unless they are read from the server and not changed.
+    fn write_payload(&self, stream: &OutStream) {
+        if let Some(obj) = &self.serialized {
+            stream.write_bytes(&obj.bytes[1..]);
+            return;
+        }
+
+        let data_len = self.data_len();
+        let schema_len = 8 * self.fields.len() as i32;
+
+        let (flags, schema_id) = if self.fields.is_empty() {
+            (FLAG_USER_TYPE, 0)
+        } else {
+            (FLAG_USER_TYPE | FLAG_HAS_SCHEMA, self.schema_id())
+        };
+
+        stream.write_i8(PROTO_VER);
+        stream.write_i16(flags);
+        stream.write_i32(self.type_id);
+        stream.write_i32(self.ignite_hash());
+        stream.write_i32(HEADER_LEN + data_len + schema_len);
+        stream.write_i32(schema_id);
+        stream.write_i32(HEADER_LEN + data_len);
+
+        for field in &self.fields {
+            stream.write_bytes(&field.data);
+        }
+
+        let mut offset = HEADER_LEN;
+        for field in &self.fields {
+            stream.write_i32(field.id);
+            stream.write_i32(offset);
+
+            offset += field.data.len() as i32;
+        }
+    }
+
+    /// Panics if the object is malformed, see `try_read_payload()`.
+    fn read_payload(stream: &InStream) -> Self::Item {
+        Self::read_checked(stream).expect("Binary object can not be read")
+    }
+
+    fn try_read_payload(stream: &InStream) -> IgniteResult<Self::Item> {
+        Self::read_checked(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{read_full, try_read_full};
+
+    /// Make object with two fields.
+    fn make_key() -> BinaryObject {
+        let mut key = BinaryObject::new("PersonKey");
+        key.set_field("id", &42i32);
+        key.set_field("company", &"Acme".to_owned());
+        key
+    }
+
+    #[test]
+    fn binary_object_ids() {
+        // Reference values are produced by Java's BinaryBasicIdMapper.
+        assert_eq!(BinaryObject::type_id_of("PersonKey"), 853_198_570);
+        assert_eq!(BinaryObject::field_id_of("ID"), 3355);
+
+        let key = make_key();
+
+        assert_eq!(key.type_id(), 853_198_570);
+        assert_eq!(key.field_ids(), vec![3355, 950_484_093]);
+        assert_eq!(key.schema_id(), -892_856_669);
+    }
+
+    #[test]
+    fn binary_object_hash() {
+        let key = make_key();
+
+        // Reference value is produced by Java's Arrays.hashCode() over the field data.
+        assert_eq!(key.ignite_hash(), -151_027_673);
+
+        let mut key_fields = HashMap::new();
+        assert_eq!(key.affinity_hash(&key_fields), -151_027_673);
+
+        key_fields.insert(key.type_id(), BinaryObject::field_id_of("company"));
+        assert_eq!(key.affinity_hash(&key_fields), "Acme".ignite_hash());
+
+        key_fields.insert(key.type_id(), BinaryObject::field_id_of("unknown"));
+        assert_eq!(key.affinity_hash(&key_fields), -151_027_673);
+    }
+
+    #[test]
+    fn binary_object_set_field_replaces() {
+        let mut key = make_key();
+        key.set_field("id", &43i32);
+
+        assert_eq!(key.field_ids(), vec![3355, 950_484_093]);
+        assert_eq!(key.field_data("id"), Some(&[3u8, 43, 0, 0, 0][..]));
+        assert_eq!(key.field_data("name"), None);
+    }
+
+    #[test]
+    fn binary_object_round_trip() {
+        let key = make_key();
+
+        let out = OutStream::new();
+        write_full(&key, &out);
+        out.write_i32(7);
+
+        let mem = out.into_memory();
+
+        // Header, fields data and schema.
+        assert_eq!(mem.len(), 24 + 5 + 9 + 16 + 4);
+        assert_eq!(mem[0], header::OBJECT as u8);
+
+        let stream = InStream::new(&mem);
+        let read = read_full::<BinaryObject, BinaryObject>(&stream).unwrap();
+
+        assert_eq!(read.type_id(), key.type_id());
+        assert_eq!(read.field_ids(), key.field_ids());
+        assert_eq!(read.field_data("company"), key.field_data("company"));
+        assert_eq!(read.ignite_hash(), key.ignite_hash());
+        assert_eq!(stream.read_i32(), 7);
+    }
+
+    /// Write header of the object with the given flags, length and schema offset.
+    fn put_header(buf: &mut Vec<u8>, flags: i16, len: i32, schema_offset: i32) {
+        buf.push(header::OBJECT as u8);
+        buf.push(PROTO_VER as u8);
+        buf.extend_from_slice(&flags.to_le_bytes());
+        buf.extend_from_slice(&853_198_570i32.to_le_bytes());
+        buf.extend_from_slice(&77i32.to_le_bytes());
+        buf.extend_from_slice(&len.to_le_bytes());
+        buf.extend_from_slice(&0i32.to_le_bytes());
+        buf.extend_from_slice(&schema_offset.to_le_bytes());
+    }
+
+    /// Read object and the value following it, checking the object is written back as is.
+    fn read_and_write_back(mem: &[u8]) -> BinaryObject {
+        let mut buf = mem.to_vec();
+        buf.extend_from_slice(&7i32.to_le_bytes());
+
+        let stream = InStream::new(&buf);
+        let obj = try_read_full::<BinaryObject, BinaryObject>(&stream)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stream.read_i32(), 7);
+
+        let out = OutStream::new();
+        write_full(&obj, &out);
+        assert_eq!(&*out.into_memory(), mem);
+
+        obj
+    }
+
+    #[test]
+    fn binary_object_compact_footer() {
+        // Fields are int 42 and string "Acme", offsets are one byte long.
+        let flags = FLAG_USER_TYPE | FLAG_HAS_SCHEMA | FLAG_OFFSET_ONE_BYTE | FLAG_COMPACT_FOOTER;
+
+        let mut mem = Vec::new();
+        put_header(&mut mem, flags, 24 + 5 + 9 + 2, 24 + 5 + 9);
+        mem.extend_from_slice(&[3, 42, 0, 0, 0]);
+        mem.extend_from_slice(&[9, 4, 0, 0, 0, b'A', b'c', b'm', b'e']);
+        mem.extend_from_slice(&[24, 29]);
+
+        let obj = read_and_write_back(&mem);
+
+        assert!(obj.has_compact_footer());
+        assert_eq!(obj.type_id(), 853_198_570);
+        assert_eq!(obj.field_ids(), Vec::<i32>::new());
+        assert_eq!(obj.field_count(), 2);
+        assert_eq!(obj.field_data_at(0), Some(&[3u8, 42, 0, 0, 0][..]));
+        assert_eq!(obj.field_data_at(1), Some(&mem[29..38]));
+        assert_eq!(obj.field_data_at(2), None);
+        assert_eq!(obj.ignite_hash(), 77);
+    }
+
+    #[test]
+    fn binary_object_raw_data() {
+        // Field is int 42 followed by three bytes of raw data, offsets are four bytes long.
+        let flags = FLAG_USER_TYPE | FLAG_HAS_SCHEMA | FLAG_HAS_RAW_DATA;
+
+        let mut mem = Vec::new();
+        put_header(&mut mem, flags, 24 + 5 + 3 + 8 + 4, 24 + 5 + 3);
+        mem.extend_from_slice(&[3, 42, 0, 0, 0]);
+        mem.extend_from_slice(&[1, 2, 3]);
+        mem.extend_from_slice(&BinaryObject::field_id_of("id").to_le_bytes());
+        mem.extend_from_slice(&24i32.to_le_bytes());
+        mem.extend_from_slice(&29i32.to_le_bytes());
+
+        let mut obj = read_and_write_back(&mem);
+
+        assert!(!obj.has_compact_footer());
+        assert_eq!(obj.field_ids(), vec![BinaryObject::field_id_of("id")]);
+        assert_eq!(obj.field_data("id"), Some(&[3u8, 42, 0, 0, 0][..]));
+        assert_eq!(obj.field_data_at(0), obj.field_data("id"));
+
+        // Raw data is dropped once the object is changed.
+        obj.set_field("name", &"Acme".to_owned());
+        assert_eq!(obj.field_count(), 2);
+
+        let out = OutStream::new();
+        write_full(&obj, &out);
+        assert_eq!(out.into_memory().len(), 24 + 5 + 9 + 16);
+    }
+
+    #[test]
+    fn binary_object_malformed() {
+        // Schema offset points past the end of the object.
+        let mut mem = Vec::new();
+        put_header(&mut mem, FLAG_USER_TYPE | FLAG_HAS_SCHEMA, 24 + 5, 99);
+        mem.extend_from_slice(&[3, 42, 0, 0, 0]);
+
+        let stream = InStream::new(&mem);
+        assert!(try_read_full::<BinaryObject, BinaryObject>(&stream).is_err());
+    }
+}
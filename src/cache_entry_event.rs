@@ -0,0 +1,76 @@
+/// Type of the change of the cache entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheEntryEventType {
+    /// Entry is created.
+    Created = 0,
+    /// Value of the entry is replaced.
+    Updated = 1,
+    /// Entry is removed.
+    Removed = 2,
+    /// Entry is removed, as it has expired.
+    Expired = 3,
+}
+
+impl CacheEntryEventType {
+    /// Get type by the code sent by the server.
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            0 => Some(CacheEntryEventType::Created),
+            1 => Some(CacheEntryEventType::Updated),
+            2 => Some(CacheEntryEventType::Removed),
+            3 => Some(CacheEntryEventType::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// Change of the cache entry, reported by the continuous query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntryEvent<K, V> {
+    key: K,
+    old_value: Option<V>,
+    new_value: Option<V>,
+    event_type: CacheEntryEventType,
+}
+
+impl<K, V> CacheEntryEvent<K, V> {
+    /// Make new instance.
+    pub(crate) fn new(
+        key: K,
+        old_value: Option<V>,
+        new_value: Option<V>,
+        event_type: CacheEntryEventType,
+    ) -> Self {
+        Self {
+            key,
+            old_value,
+            new_value,
+            event_type,
+        }
+    }
+
+    /// Get key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Get value before the change. None if the entry is created.
+    pub fn old_value(&self) -> Option<&V> {
+        self.old_value.as_ref()
+    }
+
+    /// Get value after the change. None if the entry is removed.
+    pub fn new_value(&self) -> Option<&V> {
+        self.new_value.as_ref()
+    }
+
+    /// Get type of the change.
+    pub fn event_type(&self) -> CacheEntryEventType {
+        self.event_type
+    }
+
+    /// Split into key, old value and new value.
+    pub fn into_parts(self) -> (K, Option<V>, Option<V>) {
+        (self.key, self.old_value, self.new_value)
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::cluster_group::ClusterGroup;
+use crate::cluster_node::ClusterNode;
+use crate::ignite_error::IgniteResult;
+use crate::net::MessageRouter;
+use crate::protocol::message::{ClusterChangeStateReq, ClusterChangeWalStateReq};
+use crate::protocol::message::{ClusterGetStateReq, ClusterGetWalStateReq};
+
+/// State of the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterState {
+    /// Cluster is inactive. Caches can not be used.
+    Inactive = 0,
+    /// Cluster is active.
+    Active = 1,
+    /// Cluster is active, but the data can only be read.
+    ReadOnly = 2,
+}
+
+impl ClusterState {
+    /// Get state by the protocol code.
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            0 => Some(ClusterState::Inactive),
+            1 => Some(ClusterState::Active),
+            2 => Some(ClusterState::ReadOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Cluster the client is connected to.
+///
+/// Operations require protocol version 1.7.0 and fail with the Unsupported
+/// error kind if the node does not support them.
+#[derive(Debug, Clone)]
+pub struct ClientCluster {
+    router: Arc<MessageRouter>,
+}
+
+impl ClientCluster {
+    /// Make new instance.
+    pub(crate) fn new(router: Arc<MessageRouter>) -> Self {
+        Self { router }
+    }
+
+    /// Get current state of the cluster.
+    pub async fn state(&self) -> IgniteResult<ClusterState> {
+        self.router.send(&ClusterGetStateReq::new()).await
+    }
+
+    /// Change state of the cluster.
+    /// Deactivation should be used with care, as it makes all the caches unavailable.
+    pub async fn set_state(&self, state: ClusterState) -> IgniteResult<()> {
+        self.router.send(&ClusterChangeStateReq::new(state)).await
+    }
+
+    /// Disable write-ahead logging for the cache, e.g. to speed up the initial load.
+    /// Returns true if the state was changed, false if it was already disabled.
+    ///
+    /// Data loaded while WAL is disabled may be lost if the node crashes.
+    pub async fn disable_wal(&self, cache_name: &str) -> IgniteResult<bool> {
+        self.router
+            .send(&ClusterChangeWalStateReq::new(cache_name, false))
+            .await
+    }
+
+    /// Enable write-ahead logging for the cache.
+    /// Returns true if the state was changed, false if it was already enabled.
+    pub async fn enable_wal(&self, cache_name: &str) -> IgniteResult<bool> {
+        self.router
+            .send(&ClusterChangeWalStateReq::new(cache_name, true))
+            .await
+    }
+
+    /// Check if write-ahead logging is enabled for the cache.
+    pub async fn is_wal_enabled(&self, cache_name: &str) -> IgniteResult<bool> {
+        self.router
+            .send(&ClusterGetWalStateReq::new(cache_name))
+            .await
+    }
+
+    /// Get group of all the nodes of the cluster.
+    pub fn group(&self) -> ClusterGroup {
+        ClusterGroup::new(self.router.clone())
+    }
+
+    /// Get group of the nodes having the attribute of the given value.
+    pub fn for_attribute<N: Into<String>, V: Into<String>>(
+        &self,
+        name: N,
+        value: V,
+    ) -> ClusterGroup {
+        self.group().for_attribute(name, value)
+    }
+
+    /// Get group of the server nodes.
+    pub fn for_servers(&self) -> ClusterGroup {
+        self.group().for_servers()
+    }
+
+    /// Get group of the nodes with the given IDs.
+    pub fn for_node_ids<I: IntoIterator<Item = Uuid>>(&self, ids: I) -> ClusterGroup {
+        self.group().for_node_ids(ids)
+    }
+
+    /// Get group of the nodes matching the predicate.
+    pub fn for_predicate<F>(&self, predicate: F) -> ClusterGroup
+    where
+        F: Fn(&ClusterNode) -> bool + Send + Sync + 'static,
+    {
+        self.group().for_predicate(predicate)
+    }
+
+    /// Get group of the single node which joined the cluster first.
+    pub fn for_oldest(&self) -> ClusterGroup {
+        self.group().for_oldest()
+    }
+
+    /// Get group of the single node which joined the cluster last.
+    pub fn for_youngest(&self) -> ClusterGroup {
+        self.group().for_youngest()
+    }
+
+    /// Get all the nodes of the cluster, both servers and clients.
+    pub async fn nodes(&self) -> IgniteResult<Vec<ClusterNode>> {
+        self.group().nodes().await
+    }
+}
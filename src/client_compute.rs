@@ -0,0 +1,172 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::cluster_group::ClusterGroup;
+use crate::from_row::FromValue;
+use crate::ignite_error::{ErrorKind, IgniteError, IgniteResult};
+use crate::ignite_value::IgniteValue;
+use crate::net::{AsyncDataChannel, MessageRouter};
+use crate::paged_cursor::close_resource;
+use crate::protocol::message::{read_task_result, ComputeTaskExecuteReq};
+use crate::protocol::message::{FLAG_NO_FAILOVER, FLAG_NO_RESULT_CACHE};
+use crate::protocol::InStream;
+
+/// Compute tasks of the cluster the client is connected to.
+///
+/// Tasks are deployed on the server and executed by the name of their class.
+/// Execution requires protocol version 1.7.0 and the node supporting it, and
+/// fails with the Unsupported error kind otherwise.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// # use std::time::Duration;
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let words: i32 = client
+///     .compute()
+///     .with_cluster_group(&client.cluster().for_servers())
+///     .with_timeout(Duration::from_secs(10))
+///     .execute("org.apache.ignite.examples.WordCountTask", "to be or not to be")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientCompute {
+    router: Arc<MessageRouter>,
+    group: Option<ClusterGroup>,
+    timeout: Option<Duration>,
+    flags: i8,
+}
+
+impl ClientCompute {
+    /// Make new instance, executing tasks on all the nodes.
+    pub(crate) fn new(router: Arc<MessageRouter>) -> Self {
+        Self {
+            router,
+            group: None,
+            timeout: None,
+            flags: 0,
+        }
+    }
+
+    /// Get instance executing tasks on the nodes of the group.
+    pub fn with_cluster_group(&self, group: &ClusterGroup) -> Self {
+        Self {
+            group: Some(group.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Get instance executing tasks, which are cancelled by the server if they
+    /// are not finished within the timeout.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Get instance executing tasks, which jobs are not moved to the other
+    /// nodes if the node running them fails.
+    pub fn with_no_failover(&self) -> Self {
+        self.with_flag(FLAG_NO_FAILOVER)
+    }
+
+    /// Get instance executing tasks, which job results are not cached on the nodes.
+    pub fn with_no_result_cache(&self) -> Self {
+        self.with_flag(FLAG_NO_RESULT_CACHE)
+    }
+
+    /// Get timeout of the tasks, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Execute the task and wait for its result, converting it. Null argument,
+    /// e.g. `IgniteValue::Null`, is passed to the task if it takes none, and
+    /// `Option` is expected as the result if the task can return null.
+    ///
+    /// The task is cancelled on the server if the returned future is dropped
+    /// before the task is finished.
+    pub async fn execute<A, R>(&self, task_name: &str, arg: A) -> IgniteResult<R>
+    where
+        A: Into<IgniteValue>,
+        R: FromValue,
+    {
+        let arg = arg.into();
+
+        let node_ids = match &self.group {
+            Some(group) => {
+                let ids = group.node_ids().await?;
+                if ids.is_empty() {
+                    return Err(IgniteError::new(format!(
+                        "Can not execute task {}: cluster group is empty",
+                        task_name
+                    )));
+                }
+
+                Some(ids)
+            }
+            None => None,
+        };
+
+        let req = ComputeTaskExecuteReq::new(
+            node_ids.as_deref(),
+            self.flags,
+            self.timeout,
+            task_name,
+            &arg,
+        );
+
+        let (task_id, mut notifications, channel) = self.router.subscribe(&req, None).await?;
+
+        let mut task = RunningTask {
+            router: self.router.clone(),
+            channel,
+            task_id,
+            finished: false,
+        };
+
+        // Server releases the task once the result is sent, or the connection is lost.
+        let res = notifications.next().await;
+        task.finished = true;
+
+        match res {
+            Some(Ok(data)) => R::from_value(read_task_result(&InStream::new(&data))?),
+            Some(Err(err)) => Err(err),
+            None => Err(IgniteError::new_with_kind(
+                ErrorKind::Connection,
+                format!("Task {} was not finished: channel is closed", task_name),
+            )),
+        }
+    }
+
+    /// Make a copy of the instance with one more flag set.
+    fn with_flag(&self, flag: i8) -> Self {
+        Self {
+            flags: self.flags | flag,
+            ..self.clone()
+        }
+    }
+}
+
+/// Task executed on the server, which is cancelled once dropped unfinished.
+struct RunningTask {
+    router: Arc<MessageRouter>,
+    channel: Arc<AsyncDataChannel>,
+    task_id: i64,
+    finished: bool,
+}
+
+impl Drop for RunningTask {
+    fn drop(&mut self) {
+        self.channel.unsubscribe(self.task_id);
+
+        if !self.finished {
+            close_resource(&self.router, &self.channel, self.task_id);
+        }
+    }
+}
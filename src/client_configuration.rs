@@ -1,13 +1,79 @@
+use crate::credentials::{Credentials, CredentialsProvider};
+use crate::endpoint_discovery::EndpointDiscovery;
+use crate::ignite_error::ErrorKind;
 use crate::net::utils;
 use crate::net::EndPoint;
-use crate::IgniteResult;
-use std::convert::Into;
+use crate::resolver::{DnsResolver, Resolver};
+use crate::retry_policy::{RetryIdempotentPolicy, RetryPolicy};
+use crate::tls_configuration::TlsConfiguration;
+use crate::{IgniteError, IgniteResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default number of retries of the operation failed because of the connection loss.
+pub const DEFAULT_RETRY_LIMIT: u32 = 3;
+
+/// Default delay before the second reconnection attempt.
+pub const DEFAULT_RECONNECT_DELAY_INITIAL: Duration = Duration::from_millis(100);
+
+/// Default max delay between reconnection attempts.
+pub const DEFAULT_RECONNECT_DELAY_MAX: Duration = Duration::from_secs(5);
+
+/// Default timeout of establishing TCP connection, including TLS handshake.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default timeout of the protocol handshake.
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default max interval between heartbeats, sent over the idle connections.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default interval between resolutions of the endpoint host names.
+pub const DEFAULT_RESOLVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Policy of choosing a connection to send the request with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadBalancing {
+    /// Connections are used one after another.
+    RoundRobin,
+    /// Random connection is used for every request.
+    Random,
+    /// Connection with the least number of requests waiting for response is used.
+    LeastInFlight,
+}
 
 #[derive(Debug, Clone)]
 pub struct ClientConfiguration {
     end_points: Vec<EndPoint>,
-    user: String,
-    pass: String,
+    endpoint_discovery: Option<Arc<dyn EndpointDiscovery>>,
+    resolver: Arc<dyn Resolver>,
+    resolve_interval: Option<Duration>,
+    shuffle_addresses: bool,
+    cluster_discovery: bool,
+    credentials: Credentials,
+    credentials_provider: Option<Arc<dyn CredentialsProvider>>,
+    retry_limit: u32,
+    retry_policy: Arc<dyn RetryPolicy>,
+    reconnect_delay_initial: Duration,
+    reconnect_delay_max: Duration,
+    load_balancing: LoadBalancing,
+    connect_eagerly: bool,
+    partition_awareness: bool,
+    tls: Option<TlsConfiguration>,
+    user_attributes: HashMap<String, String>,
+    connect_timeout: Duration,
+    handshake_timeout: Duration,
+    request_timeout: Option<Duration>,
+    heartbeat_interval: Option<Duration>,
+    tcp_nodelay: bool,
+    tcp_keepalive: Option<Duration>,
+}
+
+impl Default for ClientConfiguration {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ClientConfiguration {
@@ -15,8 +81,28 @@ impl ClientConfiguration {
     pub fn new() -> ClientConfiguration {
         ClientConfiguration {
             end_points: Vec::new(),
-            user: String::new(),
-            pass: String::new(),
+            endpoint_discovery: None,
+            resolver: Arc::new(DnsResolver),
+            resolve_interval: Some(DEFAULT_RESOLVE_INTERVAL),
+            shuffle_addresses: false,
+            cluster_discovery: true,
+            credentials: Credentials::default(),
+            credentials_provider: None,
+            retry_limit: DEFAULT_RETRY_LIMIT,
+            retry_policy: Arc::new(RetryIdempotentPolicy),
+            reconnect_delay_initial: DEFAULT_RECONNECT_DELAY_INITIAL,
+            reconnect_delay_max: DEFAULT_RECONNECT_DELAY_MAX,
+            load_balancing: LoadBalancing::RoundRobin,
+            connect_eagerly: true,
+            partition_awareness: true,
+            tls: None,
+            user_attributes: HashMap::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            request_timeout: None,
+            heartbeat_interval: Some(DEFAULT_HEARTBEAT_INTERVAL),
+            tcp_nodelay: true,
+            tcp_keepalive: None,
         }
     }
 
@@ -24,9 +110,8 @@ impl ClientConfiguration {
     ///
     /// The format is `"<host>[:<port>[..<port>]][,...]"`.
     ///
-    /// IgniteClient is going to try connecting to hosts in random order checking all ports
-    /// subsequently in port range for evey host. If port is not specified then the default
-    /// port is used.
+    /// IgniteClient maintains a separate connection for every host and port in the port
+    /// range. If port is not specified then the default port is used.
     ///
     /// # Examples
     /// ```
@@ -39,7 +124,7 @@ impl ClientConfiguration {
     /// cfg.set_endpoints("127.0.0.1,example:1234..1500");
     /// ```
     pub fn set_endpoints(&mut self, end_points: &str) -> IgniteResult<()> {
-        self.end_points = utils::parse_endpoints(end_points.into())?;
+        self.end_points = utils::parse_endpoints(end_points)?;
         Ok(())
     }
 
@@ -49,14 +134,329 @@ impl ClientConfiguration {
         &self.end_points
     }
 
+    /// Set source of the endpoints, which is polled for the changes.
+    ///
+    /// It is polled every resolve interval, see set_resolve_interval().
+    /// If set, the endpoints set with set_endpoints() are ignored.
+    pub fn set_endpoint_discovery(&mut self, endpoint_discovery: Arc<dyn EndpointDiscovery>) {
+        self.endpoint_discovery = Some(endpoint_discovery);
+    }
+
+    /// Get source of the endpoints.
+    pub fn get_endpoint_discovery(&self) -> Option<&dyn EndpointDiscovery> {
+        self.endpoint_discovery.as_deref()
+    }
+
+    /// Get current endpoints, polling the endpoint discovery if it is set.
+    pub(crate) async fn current_endpoints(&self) -> IgniteResult<Vec<EndPoint>> {
+        match &self.endpoint_discovery {
+            Some(discovery) => discovery.endpoints().await,
+            None => Ok(self.end_points.clone()),
+        }
+    }
+
+    /// Set resolver of the endpoint host names.
+    ///
+    /// Default is DnsResolver.
+    pub fn set_resolver(&mut self, resolver: Arc<dyn Resolver>) {
+        self.resolver = resolver;
+    }
+
+    /// Get resolver of the endpoint host names.
+    pub fn get_resolver(&self) -> &dyn Resolver {
+        self.resolver.as_ref()
+    }
+
+    /// Set interval between resolutions of the endpoint host names.
+    /// Endpoint discovery is polled with the same interval.
+    ///
+    /// Connections are established to the newly resolved addresses, and the
+    /// addresses which are not resolved anymore are forgotten. Addresses of
+    /// the hosts which fail to resolve are kept.
+    ///
+    /// Default is DEFAULT_RESOLVE_INTERVAL. None disables re-resolution.
+    pub fn set_resolve_interval(&mut self, resolve_interval: Option<Duration>) {
+        self.resolve_interval = resolve_interval;
+    }
+
+    /// Get interval between resolutions of the endpoint host names.
+    pub fn get_resolve_interval(&self) -> Option<Duration> {
+        self.resolve_interval
+    }
+
+    /// Set whether the resolved addresses of every endpoint are used in a random order.
+    ///
+    /// Otherwise every address of the host is used with the first port of the
+    /// range, then with the next one and so on. Default is false.
+    pub fn set_shuffle_addresses(&mut self, shuffle_addresses: bool) {
+        self.shuffle_addresses = shuffle_addresses;
+    }
+
+    /// Get whether the resolved addresses of every endpoint are used in a random order.
+    pub fn get_shuffle_addresses(&self) -> bool {
+        self.shuffle_addresses
+    }
+
+    /// Set whether the server nodes of the cluster are discovered, so the client
+    /// connects to all of them, even if only some are listed in the endpoints.
+    ///
+    /// Requires protocol version 1.7.0 and the support of the feature by the nodes.
+    /// Nodes are discovered again every resolve interval. Default is true.
+    pub fn set_cluster_discovery(&mut self, cluster_discovery: bool) {
+        self.cluster_discovery = cluster_discovery;
+    }
+
+    /// Get whether the server nodes of the cluster are discovered.
+    pub fn get_cluster_discovery(&self) -> bool {
+        self.cluster_discovery
+    }
+
+    /// Set username for authentication
+    pub fn set_user(&mut self, user: &str) {
+        self.credentials = Credentials::new(user, self.credentials.password());
+    }
+
     /// Get username for authentication
     pub fn get_user(&self) -> &str {
-        &self.user
+        self.credentials.user()
+    }
+
+    /// Set password for authentication
+    pub fn set_password(&mut self, password: &str) {
+        self.credentials = Credentials::new(self.credentials.user(), password);
     }
 
     /// Get password for authentication
     pub fn get_password(&self) -> &str {
-        &self.pass
+        self.credentials.password()
+    }
+
+    /// Set provider, the credentials are requested from every time a new
+    /// connection is established. Overrides user and password if set.
+    ///
+    /// See EnvCredentials and FileCredentials.
+    pub fn set_credentials_provider(&mut self, provider: Arc<dyn CredentialsProvider>) {
+        self.credentials_provider = Some(provider);
+    }
+
+    /// Get credentials provider.
+    pub fn get_credentials_provider(&self) -> Option<&dyn CredentialsProvider> {
+        self.credentials_provider.as_deref()
+    }
+
+    /// Get credentials to authenticate a new connection with.
+    pub(crate) fn credentials(&self) -> IgniteResult<Credentials> {
+        match &self.credentials_provider {
+            Some(provider) => provider.credentials().map_err(|err| {
+                IgniteError::new_with_kind_and_source(
+                    ErrorKind::Authentication,
+                    "Can not get credentials from the provider",
+                    Box::new(err),
+                )
+            }),
+            None => Ok(self.credentials.clone()),
+        }
+    }
+
+    /// Set max number of retries of the operation, which failed because the
+    /// connection to the node was lost. Whether the operation is retried at all
+    /// is decided by the retry policy.
+    ///
+    /// Default is DEFAULT_RETRY_LIMIT.
+    pub fn set_retry_limit(&mut self, retry_limit: u32) {
+        self.retry_limit = retry_limit;
+    }
+
+    /// Get retry limit.
+    pub fn get_retry_limit(&self) -> u32 {
+        self.retry_limit
+    }
+
+    /// Set retry policy.
+    ///
+    /// By default only idempotent operations are retried. See RetryIdempotentPolicy.
+    pub fn set_retry_policy(&mut self, retry_policy: Arc<dyn RetryPolicy>) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Get retry policy.
+    pub fn get_retry_policy(&self) -> &dyn RetryPolicy {
+        self.retry_policy.as_ref()
+    }
+
+    /// Set backoff of the reconnection attempts.
+    ///
+    /// The first reconnection attempt is made right after the failure. Every
+    /// subsequent attempt is delayed twice as long as the previous one, starting
+    /// with `initial` and not exceeding `max`.
+    pub fn set_reconnect_backoff(&mut self, initial: Duration, max: Duration) {
+        self.reconnect_delay_initial = initial;
+        self.reconnect_delay_max = max;
+    }
+
+    /// Get backoff of the reconnection attempts as a pair of initial and max delays.
+    pub fn get_reconnect_backoff(&self) -> (Duration, Duration) {
+        (self.reconnect_delay_initial, self.reconnect_delay_max)
+    }
+
+    /// Get delay before the reconnection attempt, made after the specified
+    /// number of failed ones.
+    pub(crate) fn reconnect_delay(&self, failures: u32) -> Duration {
+        if failures == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let factor = 1u32.checked_shl(failures - 1).unwrap_or(u32::MAX);
+
+        self.reconnect_delay_initial
+            .checked_mul(factor)
+            .map_or(self.reconnect_delay_max, |delay| {
+                delay.min(self.reconnect_delay_max)
+            })
+    }
+
+    /// Set policy of choosing a connection for every request.
+    ///
+    /// Default is LoadBalancing::RoundRobin.
+    pub fn set_load_balancing(&mut self, load_balancing: LoadBalancing) {
+        self.load_balancing = load_balancing;
+    }
+
+    /// Get policy of choosing a connection for every request.
+    pub fn get_load_balancing(&self) -> LoadBalancing {
+        self.load_balancing
+    }
+
+    /// Set whether connections to all the endpoints are established when the
+    /// client is started. Otherwise the client connects to a single endpoint on
+    /// start and opens other connections when they are chosen to send a request.
+    ///
+    /// Default is true.
+    pub fn set_connect_eagerly(&mut self, connect_eagerly: bool) {
+        self.connect_eagerly = connect_eagerly;
+    }
+
+    /// Get whether connections to all the endpoints are established on start.
+    pub fn get_connect_eagerly(&self) -> bool {
+        self.connect_eagerly
+    }
+
+    /// Set whether single key operations are sent directly to the node storing
+    /// the key. Only has effect if the node supports it and the connection to it
+    /// is established, otherwise the load balancing policy is used.
+    ///
+    /// Default is true.
+    pub fn set_partition_awareness(&mut self, partition_awareness: bool) {
+        self.partition_awareness = partition_awareness;
+    }
+
+    /// Get whether single key operations are sent directly to the node storing the key.
+    pub fn get_partition_awareness(&self) -> bool {
+        self.partition_awareness
+    }
+
+    /// Set TLS configuration. Connections to all the nodes are secured if set.
+    ///
+    /// The client has to be built with the `tls-rustls` or `tls-native`
+    /// feature, otherwise it fails to start.
+    pub fn set_tls(&mut self, tls: TlsConfiguration) {
+        self.tls = Some(tls);
+    }
+
+    /// Get TLS configuration.
+    pub fn get_tls(&self) -> Option<&TlsConfiguration> {
+        self.tls.as_ref()
+    }
+
+    /// Set attributes, sent to the node in the handshake. They are available to
+    /// the security plugins on the server, e.g. to identify the tenant.
+    ///
+    /// Only sent to the nodes supporting protocol version 1.7.0 or newer.
+    pub fn set_user_attributes(&mut self, user_attributes: HashMap<String, String>) {
+        self.user_attributes = user_attributes;
+    }
+
+    /// Get attributes, sent to the node in the handshake.
+    pub fn get_user_attributes(&self) -> &HashMap<String, String> {
+        &self.user_attributes
+    }
+
+    /// Set timeout of establishing connection to a node, including TLS handshake.
+    ///
+    /// Default is DEFAULT_CONNECT_TIMEOUT.
+    pub fn set_connect_timeout(&mut self, connect_timeout: Duration) {
+        self.connect_timeout = connect_timeout;
+    }
+
+    /// Get timeout of establishing connection to a node.
+    pub fn get_connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// Set timeout of the protocol handshake with a node.
+    ///
+    /// Default is DEFAULT_HANDSHAKE_TIMEOUT.
+    pub fn set_handshake_timeout(&mut self, handshake_timeout: Duration) {
+        self.handshake_timeout = handshake_timeout;
+    }
+
+    /// Get timeout of the protocol handshake with a node.
+    pub fn get_handshake_timeout(&self) -> Duration {
+        self.handshake_timeout
+    }
+
+    /// Set default timeout of the operations, including retries.
+    /// Can be overridden for the cache with IgniteCache::with_timeout().
+    ///
+    /// Operations do not time out by default.
+    pub fn set_request_timeout(&mut self, request_timeout: Option<Duration>) {
+        self.request_timeout = request_timeout;
+    }
+
+    /// Get default timeout of the operations.
+    pub fn get_request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Set max interval between heartbeats, sent over the connections which
+    /// are idle, to keep them from being closed by the firewalls or the node.
+    /// Connections not responding to a heartbeat in time are reconnected.
+    ///
+    /// If the node reports its idle timeout, heartbeats are sent three times
+    /// as often. Nodes not supporting heartbeats get a cheap request instead.
+    ///
+    /// Default is DEFAULT_HEARTBEAT_INTERVAL. None disables heartbeats.
+    pub fn set_heartbeat_interval(&mut self, heartbeat_interval: Option<Duration>) {
+        self.heartbeat_interval = heartbeat_interval;
+    }
+
+    /// Get max interval between heartbeats.
+    pub fn get_heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat_interval
+    }
+
+    /// Set whether Nagle's algorithm is disabled on the connections.
+    ///
+    /// Default is true.
+    pub fn set_tcp_nodelay(&mut self, tcp_nodelay: bool) {
+        self.tcp_nodelay = tcp_nodelay;
+    }
+
+    /// Get whether Nagle's algorithm is disabled on the connections.
+    pub fn get_tcp_nodelay(&self) -> bool {
+        self.tcp_nodelay
+    }
+
+    /// Set idle time, after which TCP keepalive probes are sent.
+    ///
+    /// Keepalive is disabled by default.
+    pub fn set_tcp_keepalive(&mut self, tcp_keepalive: Option<Duration>) {
+        self.tcp_keepalive = tcp_keepalive;
+    }
+
+    /// Get idle time, after which TCP keepalive probes are sent.
+    pub fn get_tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
     }
 }
 
@@ -77,3 +477,35 @@ fn client_configuration_endpoints() {
     assert_eq!(get.len(), 1);
     assert_eq!(get[0], EndPoint::from_string(set).unwrap());
 }
+
+#[test]
+fn client_configuration_reconnect_delay() {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_reconnect_backoff(Duration::from_millis(100), Duration::from_millis(500));
+
+    assert_eq!(cfg.reconnect_delay(0), Duration::from_millis(0));
+    assert_eq!(cfg.reconnect_delay(1), Duration::from_millis(100));
+    assert_eq!(cfg.reconnect_delay(2), Duration::from_millis(200));
+    assert_eq!(cfg.reconnect_delay(3), Duration::from_millis(400));
+    assert_eq!(cfg.reconnect_delay(4), Duration::from_millis(500));
+    assert_eq!(cfg.reconnect_delay(100), Duration::from_millis(500));
+}
+
+#[test]
+fn client_configuration_credentials() {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_user("ignite");
+    cfg.set_password("secret");
+
+    assert_eq!(cfg.get_user(), "ignite");
+    assert_eq!(cfg.get_password(), "secret");
+    assert_eq!(
+        cfg.credentials().unwrap(),
+        Credentials::new("ignite", "secret")
+    );
+    assert!(!format!("{:?}", cfg).contains("secret"));
+
+    cfg.set_credentials_provider(Arc::new(Credentials::new("admin", "admin")));
+
+    assert_eq!(cfg.credentials().unwrap().user(), "admin");
+}
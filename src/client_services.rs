@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::cluster_group::ClusterGroup;
+use crate::from_row::FromValue;
+use crate::ignite_error::{IgniteError, IgniteResult};
+use crate::ignite_value::IgniteValue;
+use crate::net::MessageRouter;
+use crate::protocol::message::{
+    ServiceGetDescriptorReq, ServiceGetDescriptorsReq, ServiceInvokeReq,
+};
+use crate::service_descriptor::ServiceDescriptor;
+
+/// Services deployed in the cluster the client is connected to.
+///
+/// Operations require protocol version 1.7.0 and the node supporting them,
+/// and fail with the Unsupported error kind otherwise.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// # use std::time::Duration;
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let total: i64 = client
+///     .services()
+///     .with_cluster_group(&client.cluster().for_servers())
+///     .invoke(
+///         "calculator",
+///         "sum",
+///         &[1i32.into(), 2i32.into()],
+///         Some(Duration::from_secs(5)),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientServices {
+    router: Arc<MessageRouter>,
+    group: Option<ClusterGroup>,
+}
+
+impl ClientServices {
+    /// Make new instance, invoking services on all the nodes.
+    pub(crate) fn new(router: Arc<MessageRouter>) -> Self {
+        Self {
+            router,
+            group: None,
+        }
+    }
+
+    /// Get instance invoking services deployed on the nodes of the group.
+    pub fn with_cluster_group(&self, group: &ClusterGroup) -> Self {
+        Self {
+            group: Some(group.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Invoke method of the service, converting the returned value.
+    ///
+    /// The invocation fails on the server if it is not finished within the
+    /// timeout. None means no timeout.
+    pub async fn invoke<R: FromValue>(
+        &self,
+        name: &str,
+        method: &str,
+        args: &[IgniteValue],
+        timeout: Option<Duration>,
+    ) -> IgniteResult<R> {
+        self.invoke_inner(name, method, args, None, timeout).await
+    }
+
+    /// Invoke method of the service, passing Java types of its parameters,
+    /// one per argument, e.g. `"int"` or `"java.lang.String"`. The types are
+    /// used by the server to choose between the overloaded methods.
+    pub async fn invoke_with_param_types<R: FromValue>(
+        &self,
+        name: &str,
+        method: &str,
+        args: &[IgniteValue],
+        param_types: &[&str],
+        timeout: Option<Duration>,
+    ) -> IgniteResult<R> {
+        self.invoke_inner(name, method, args, Some(param_types), timeout)
+            .await
+    }
+
+    /// Get proxy of the service, which methods can be invoked by name, or
+    /// through the trait generated with `#[service_proxy]`.
+    pub fn service_proxy<S: Into<String>>(&self, name: S) -> ServiceProxy {
+        ServiceProxy {
+            services: self.clone(),
+            name: name.into(),
+            timeout: None,
+        }
+    }
+
+    /// Get descriptors of all the services deployed in the cluster.
+    pub async fn service_descriptors(&self) -> IgniteResult<Vec<ServiceDescriptor>> {
+        self.router.send(&ServiceGetDescriptorsReq::new()).await
+    }
+
+    /// Get descriptor of the service. Fails if there is no such service.
+    pub async fn service_descriptor(&self, name: &str) -> IgniteResult<ServiceDescriptor> {
+        self.router.send(&ServiceGetDescriptorReq::new(name)).await
+    }
+
+    /// Invoke method of the service on the nodes of the group.
+    async fn invoke_inner<R: FromValue>(
+        &self,
+        name: &str,
+        method: &str,
+        args: &[IgniteValue],
+        param_types: Option<&[&str]>,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<R> {
+        let node_ids: Option<Vec<Uuid>> = match &self.group {
+            Some(group) => {
+                let ids = group.node_ids().await?;
+                if ids.is_empty() {
+                    return Err(IgniteError::new(format!(
+                        "Can not invoke service {}: cluster group is empty",
+                        name
+                    )));
+                }
+
+                Some(ids)
+            }
+            None => None,
+        };
+
+        let req = ServiceInvokeReq::new(name, method, args)
+            .with_param_types(param_types)
+            .with_node_ids(node_ids.as_deref())
+            .with_timeout(timeout);
+
+        R::from_value(self.router.send(&req).await?)
+    }
+}
+
+/// Proxy of the service deployed in the cluster.
+///
+/// Typed proxies are made by implementing the trait with `#[service_proxy]`.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// #[service_proxy]
+/// trait Calculator {
+///     /// Invokes `calculate(int)`.
+///     async fn calculate(&self, x: i32) -> IgniteResult<i64>;
+///
+///     /// Invokes `sum(long, long)`, which is overloaded.
+///     #[ignite(rename = "sum", param_types("long", "long"))]
+///     async fn add(&self, a: i64, b: i64) -> IgniteResult<i64>;
+/// }
+///
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let calculator = client.services().service_proxy("calculator");
+/// let res = calculator.calculate(21).await? + calculator.add(1, 2).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ServiceProxy {
+    services: ClientServices,
+    name: String,
+    timeout: Option<Duration>,
+}
+
+impl ServiceProxy {
+    /// Get proxy of the same service, which invocations fail on the server if
+    /// they are not finished within the timeout.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Get name of the service.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get timeout of the invocations, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Invoke method of the service, converting the returned value.
+    /// Java types of the parameters are passed if given, see
+    /// `ClientServices::invoke_with_param_types()`.
+    pub async fn invoke<R: FromValue>(
+        &self,
+        method: &str,
+        args: &[IgniteValue],
+        param_types: Option<&[&str]>,
+    ) -> IgniteResult<R> {
+        self.services
+            .invoke_inner(&self.name, method, args, param_types, self.timeout)
+            .await
+    }
+}
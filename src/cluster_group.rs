@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::cluster_node::ClusterNode;
+use crate::ignite_error::IgniteResult;
+use crate::net::MessageRouter;
+use crate::protocol::message::ClusterGroupGetNodeInfoReq;
+use crate::protocol::message::{ClusterGroupFilter, ClusterGroupGetNodeIdsReq};
+
+/// Predicate selecting the nodes of the group.
+type NodePredicate = Arc<dyn Fn(&ClusterNode) -> bool + Send + Sync>;
+
+/// Step narrowing the group, applied in the order the steps were added.
+#[derive(Clone)]
+enum Step {
+    /// Filter which can be applied on the server.
+    Filter(ClusterGroupFilter),
+    /// Nodes with the given IDs.
+    NodeIds(HashSet<Uuid>),
+    /// Nodes matching the predicate.
+    Predicate(NodePredicate),
+    /// Node which joined the cluster first.
+    Oldest,
+    /// Node which joined the cluster last.
+    Youngest,
+}
+
+impl fmt::Debug for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Filter(filter) => f.debug_tuple("Filter").field(filter).finish(),
+            Step::NodeIds(ids) => f.debug_tuple("NodeIds").field(ids).finish(),
+            Step::Predicate(_) => f.write_str("Predicate"),
+            Step::Oldest => f.write_str("Oldest"),
+            Step::Youngest => f.write_str("Youngest"),
+        }
+    }
+}
+
+impl Step {
+    /// Apply the step to the nodes.
+    fn apply(&self, mut nodes: Vec<ClusterNode>) -> Vec<ClusterNode> {
+        match self {
+            Step::Filter(ClusterGroupFilter::Attribute(name, value)) => {
+                nodes.retain(|node| node.attribute(name) == Some(value.as_str()))
+            }
+            Step::Filter(ClusterGroupFilter::Servers) => nodes.retain(|node| !node.is_client()),
+            Step::NodeIds(ids) => nodes.retain(|node| ids.contains(&node.id())),
+            Step::Predicate(predicate) => nodes.retain(|node| predicate(node)),
+            Step::Oldest => {
+                let oldest = nodes.iter().min_by_key(|node| node.order()).cloned();
+                nodes = oldest.into_iter().collect();
+            }
+            Step::Youngest => {
+                let youngest = nodes.iter().max_by_key(|node| node.order()).cloned();
+                nodes = youngest.into_iter().collect();
+            }
+        }
+
+        nodes
+    }
+}
+
+/// Group of the cluster nodes, e.g. to run compute tasks or deploy services on.
+///
+/// Groups are narrowed down with the `for_*` methods, each of them returning a new group.
+/// Nodes are requested every time, so the group follows the topology changes.
+///
+/// # Examples
+/// ```no_run
+/// # use ignite_rust::*;
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let nodes = client
+///     .cluster()
+///     .for_servers()
+///     .for_attribute("region", "eu")
+///     .nodes()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClusterGroup {
+    router: Arc<MessageRouter>,
+    steps: Vec<Step>,
+}
+
+impl ClusterGroup {
+    /// Make new instance containing all the nodes.
+    pub(crate) fn new(router: Arc<MessageRouter>) -> Self {
+        Self {
+            router,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Get group of the nodes having the attribute of the given value.
+    /// Attribute values, which are not strings, are compared as strings.
+    pub fn for_attribute<N: Into<String>, V: Into<String>>(&self, name: N, value: V) -> Self {
+        self.with_step(Step::Filter(ClusterGroupFilter::Attribute(
+            name.into(),
+            value.into(),
+        )))
+    }
+
+    /// Get group of the server nodes.
+    pub fn for_servers(&self) -> Self {
+        self.with_step(Step::Filter(ClusterGroupFilter::Servers))
+    }
+
+    /// Get group of the nodes with the given IDs.
+    pub fn for_node_ids<I: IntoIterator<Item = Uuid>>(&self, ids: I) -> Self {
+        self.with_step(Step::NodeIds(ids.into_iter().collect()))
+    }
+
+    /// Get group of the nodes matching the predicate.
+    /// The predicate is applied on the client side, to the details of every node of the group.
+    pub fn for_predicate<F>(&self, predicate: F) -> Self
+    where
+        F: Fn(&ClusterNode) -> bool + Send + Sync + 'static,
+    {
+        self.with_step(Step::Predicate(Arc::new(predicate)))
+    }
+
+    /// Get group of the single node which joined the cluster first.
+    pub fn for_oldest(&self) -> Self {
+        self.with_step(Step::Oldest)
+    }
+
+    /// Get group of the single node which joined the cluster last.
+    pub fn for_youngest(&self) -> Self {
+        self.with_step(Step::Youngest)
+    }
+
+    /// Get the nodes of the group.
+    pub async fn nodes(&self) -> IgniteResult<Vec<ClusterNode>> {
+        let (filters, steps) = self.split_steps();
+
+        let ids = self
+            .router
+            .send(&ClusterGroupGetNodeIdsReq::new(&filters))
+            .await?;
+
+        let ids = match steps.first() {
+            Some(Step::NodeIds(only)) => ids.into_iter().filter(|id| only.contains(id)).collect(),
+            _ => ids,
+        };
+
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let nodes = self
+            .router
+            .send(&ClusterGroupGetNodeInfoReq::new(&ids))
+            .await?;
+
+        Ok(steps.iter().fold(nodes, |nodes, step| step.apply(nodes)))
+    }
+
+    /// Get IDs of the nodes of the group.
+    /// Details of the nodes are only requested if the group can not be resolved without them.
+    pub async fn node_ids(&self) -> IgniteResult<Vec<Uuid>> {
+        let (filters, steps) = self.split_steps();
+
+        match steps {
+            [] => {
+                self.router
+                    .send(&ClusterGroupGetNodeIdsReq::new(&filters))
+                    .await
+            }
+            [Step::NodeIds(only)] => {
+                let ids = self
+                    .router
+                    .send(&ClusterGroupGetNodeIdsReq::new(&filters))
+                    .await?;
+
+                Ok(ids.into_iter().filter(|id| only.contains(id)).collect())
+            }
+            _ => Ok(self.nodes().await?.iter().map(ClusterNode::id).collect()),
+        }
+    }
+
+    /// Make a copy of the group with one more step.
+    fn with_step(&self, step: Step) -> Self {
+        let mut group = self.clone();
+        group.steps.push(step);
+        group
+    }
+
+    /// Split the steps into the filters applied on the server and the rest.
+    /// Only the leading filters can be applied on the server, as the steps
+    /// like `for_oldest()` depend on all the nodes preceding them.
+    fn split_steps(&self) -> (Vec<ClusterGroupFilter>, &[Step]) {
+        let pos = self
+            .steps
+            .iter()
+            .position(|step| !matches!(step, Step::Filter(_)))
+            .unwrap_or(self.steps.len());
+
+        let filters = self.steps[..pos]
+            .iter()
+            .filter_map(|step| match step {
+                Step::Filter(filter) => Some(filter.clone()),
+                _ => None,
+            })
+            .collect();
+
+        (filters, &self.steps[pos..])
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// Node of the cluster, either server or client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterNode {
+    id: Uuid,
+    consistent_id: Option<String>,
+    addresses: Vec<String>,
+    host_names: Vec<String>,
+    attributes: HashMap<String, String>,
+    order: i64,
+    is_client: bool,
+    version: String,
+}
+
+impl ClusterNode {
+    /// Make new instance.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        id: Uuid,
+        consistent_id: Option<String>,
+        addresses: Vec<String>,
+        host_names: Vec<String>,
+        attributes: HashMap<String, String>,
+        order: i64,
+        is_client: bool,
+        version: String,
+    ) -> Self {
+        Self {
+            id,
+            consistent_id,
+            addresses,
+            host_names,
+            attributes,
+            order,
+            is_client,
+            version,
+        }
+    }
+
+    /// Get ID of the node. It changes every time the node is restarted.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Get consistent ID of the node, which survives restarts.
+    pub fn consistent_id(&self) -> Option<&str> {
+        self.consistent_id.as_deref()
+    }
+
+    /// Get IP addresses of the node.
+    pub fn addresses(&self) -> &[String] {
+        &self.addresses
+    }
+
+    /// Get host names of the node.
+    pub fn host_names(&self) -> &[String] {
+        &self.host_names
+    }
+
+    /// Get attributes of the node. Values of the attributes, which are not strings,
+    /// are converted to strings.
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    /// Get value of the attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    /// Get order of the node in the topology. Nodes joined later have bigger order.
+    pub fn order(&self) -> i64 {
+        self.order
+    }
+
+    /// Check if the node is a client node, not storing any data.
+    pub fn is_client(&self) -> bool {
+        self.is_client
+    }
+
+    /// Get version of Ignite running on the node, e.g. `2.9.0`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use crate::binary_object::BinaryObject;
+use crate::scan_query::ScanQuery;
+
+/// Default number of events the server buffers before sending them.
+const DEFAULT_BUFFER_SIZE: i32 = 1;
+
+/// Query listening to the changes of the cache entries, which the server pushes
+/// as they happen.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use ignite_rust::{ContinuousQuery, ScanQuery};
+///
+/// let mut query = ContinuousQuery::new();
+/// query.set_buffer_size(100);
+/// query.set_time_interval(Duration::from_millis(500));
+/// query.set_initial_query(Some(ScanQuery::new()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuousQuery {
+    buffer_size: i32,
+    time_interval: Duration,
+    include_expired: bool,
+    filter: Option<BinaryObject>,
+    initial_query: Option<ScanQuery>,
+}
+
+impl ContinuousQuery {
+    /// Make new query with the default parameters.
+    pub fn new() -> Self {
+        Self {
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            time_interval: Duration::from_millis(0),
+            include_expired: false,
+            filter: None,
+            initial_query: None,
+        }
+    }
+
+    /// Set number of events the server buffers before sending them.
+    pub fn set_buffer_size(&mut self, buffer_size: i32) {
+        self.buffer_size = buffer_size;
+    }
+
+    /// Get number of events the server buffers before sending them.
+    pub fn get_buffer_size(&self) -> i32 {
+        self.buffer_size
+    }
+
+    /// Set interval after which the buffered events are sent, even if the buffer
+    /// is not full. Zero means the events are only sent once the buffer is full.
+    pub fn set_time_interval(&mut self, time_interval: Duration) {
+        self.time_interval = time_interval;
+    }
+
+    /// Get interval after which the buffered events are sent.
+    pub fn get_time_interval(&self) -> Duration {
+        self.time_interval
+    }
+
+    /// Set whether the events of the entries expiring are sent.
+    pub fn set_include_expired(&mut self, include_expired: bool) {
+        self.include_expired = include_expired;
+    }
+
+    /// Get whether the events of the entries expiring are sent.
+    pub fn get_include_expired(&self) -> bool {
+        self.include_expired
+    }
+
+    /// Set filter of the events, applied on the server. The object has to be of the
+    /// Java class implementing `CacheEntryEventSerializableFilter`, available on the server.
+    pub fn set_filter(&mut self, filter: Option<BinaryObject>) {
+        self.filter = filter;
+    }
+
+    /// Get filter of the events.
+    pub fn get_filter(&self) -> Option<&BinaryObject> {
+        self.filter.as_ref()
+    }
+
+    /// Set query returning the entries which are in the cache already.
+    /// It is executed once the listening starts, so no change can be missed.
+    pub fn set_initial_query(&mut self, initial_query: Option<ScanQuery>) {
+        self.initial_query = initial_query;
+    }
+
+    /// Get query returning the entries which are in the cache already.
+    pub fn get_initial_query(&self) -> Option<&ScanQuery> {
+        self.initial_query.as_ref()
+    }
+}
+
+impl Default for ContinuousQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
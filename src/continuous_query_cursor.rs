@@ -0,0 +1,143 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::vec;
+
+use futures::{Stream, StreamExt};
+
+use crate::cache_entry_event::CacheEntryEvent;
+use crate::ignite_error::IgniteResult;
+use crate::net::{AsyncDataChannel, MessageRouter, Notifications};
+use crate::paged_cursor::close_resource;
+use crate::protocol::message::read_entry_events;
+use crate::protocol::{InStream, ProtocolType};
+use crate::scan_query_cursor::ScanQueryCursor;
+
+/// Stream of the changes of the cache entries, pushed by the server.
+///
+/// The query is bound to the connection it was started over. If the connection
+/// is lost, the error is returned and the stream ends, so the query has to be
+/// started again. Dropping the cursor stops the query on the server.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// use futures::TryStreamExt;
+///
+/// # async fn run(cache: IgniteCache<i32, String>) -> IgniteResult<()> {
+/// let mut events = cache.continuous_query(&ContinuousQuery::new()).await?;
+///
+/// while let Some(event) = events.try_next().await? {
+///     println!("{:?} {}: {:?}", event.event_type(), event.key(), event.new_value());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ContinuousQueryCursor<K, V> {
+    router: Arc<MessageRouter>,
+    channel: Arc<AsyncDataChannel>,
+    query_id: i64,
+    notifications: Notifications,
+    events: vec::IntoIter<CacheEntryEvent<K, V>>,
+    initial: Option<ScanQueryCursor<K, V>>,
+    done: bool,
+}
+
+impl<K, V> ContinuousQueryCursor<K, V>
+where
+    K: ProtocolType<Item = K> + Send + 'static,
+    V: ProtocolType<Item = V> + Send + 'static,
+{
+    /// Make new instance, listening to the notifications of the started query.
+    pub(crate) fn new(
+        router: Arc<MessageRouter>,
+        channel: Arc<AsyncDataChannel>,
+        query_id: i64,
+        notifications: Notifications,
+    ) -> Self {
+        Self {
+            router,
+            channel,
+            query_id,
+            notifications,
+            events: Vec::new().into_iter(),
+            initial: None,
+            done: false,
+        }
+    }
+
+    /// Set cursor of the initial query.
+    pub(crate) fn set_initial(&mut self, initial: ScanQueryCursor<K, V>) {
+        self.initial = Some(initial);
+    }
+
+    /// Take cursor over the entries returned by the initial query.
+    /// None if the initial query was not set, or the cursor is taken already.
+    pub fn initial_entries(&mut self) -> Option<ScanQueryCursor<K, V>> {
+        self.initial.take()
+    }
+}
+
+/// Events are never pinned, so the cursor can be moved regardless of their type.
+impl<K, V> Unpin for ContinuousQueryCursor<K, V> {}
+
+impl<K, V> Stream for ContinuousQueryCursor<K, V>
+where
+    K: ProtocolType<Item = K> + Send + 'static,
+    V: ProtocolType<Item = V> + Send + 'static,
+{
+    type Item = IgniteResult<CacheEntryEvent<K, V>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(event) = this.events.next() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let data = match this.notifications.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(data))) => data,
+                Poll::Ready(Some(Err(err))) => {
+                    // Nothing comes after the connection is lost.
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Ready(None) => {
+                    this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match read_entry_events(&InStream::new(&data)) {
+                Ok(events) => this.events = events.into_iter(),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+impl<K, V> Drop for ContinuousQueryCursor<K, V> {
+    fn drop(&mut self) {
+        self.channel.unsubscribe(self.query_id);
+
+        if !self.done {
+            close_resource(&self.router, &self.channel, self.query_id);
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for ContinuousQueryCursor<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContinuousQueryCursor")
+            .field("query_id", &self.query_id)
+            .field("done", &self.done)
+            .finish()
+    }
+}
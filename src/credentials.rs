@@ -0,0 +1,189 @@
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::ignite_error::{ChainResult, IgniteResult};
+use crate::IgniteError;
+
+/// User name and password, the client authenticates with.
+/// Password is never printed in the debug output.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    user: String,
+    password: String,
+}
+
+impl Credentials {
+    /// Make new instance.
+    pub fn new<U: Into<String>, P: Into<String>>(user: U, password: P) -> Self {
+        Self {
+            user: user.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Get user name.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Get password.
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &self.user)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Source of the credentials, which are requested every time a new connection
+/// is established. Allows the password to be rotated without restarting the client.
+///
+/// # Examples
+/// ```
+/// use ignite_rust::{ClientConfiguration, Credentials, CredentialsProvider, IgniteResult};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct Vault;
+///
+/// impl CredentialsProvider for Vault {
+///     fn credentials(&self) -> IgniteResult<Credentials> {
+///         Ok(Credentials::new("ignite", "secret"))
+///     }
+/// }
+///
+/// let mut cfg = ClientConfiguration::new();
+/// cfg.set_credentials_provider(Arc::new(Vault));
+/// ```
+pub trait CredentialsProvider: fmt::Debug + Send + Sync {
+    /// Get current credentials.
+    fn credentials(&self) -> IgniteResult<Credentials>;
+}
+
+impl CredentialsProvider for Credentials {
+    fn credentials(&self) -> IgniteResult<Credentials> {
+        Ok(self.clone())
+    }
+}
+
+/// Reads credentials from the environment variables.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    user_var: String,
+    password_var: String,
+}
+
+impl EnvCredentials {
+    /// Make new instance, reading user name and password from the given variables.
+    pub fn new<U: Into<String>, P: Into<String>>(user_var: U, password_var: P) -> Self {
+        Self {
+            user_var: user_var.into(),
+            password_var: password_var.into(),
+        }
+    }
+}
+
+impl CredentialsProvider for EnvCredentials {
+    fn credentials(&self) -> IgniteResult<Credentials> {
+        let read = |var: &str| {
+            env::var(var).map_err(|err| {
+                IgniteError::new_with_source(
+                    format!("Can not read environment variable {}", var),
+                    Box::new(err),
+                )
+            })
+        };
+
+        Ok(Credentials::new(
+            read(&self.user_var)?,
+            read(&self.password_var)?,
+        ))
+    }
+}
+
+/// Reads password from the file, e.g. a mounted secret.
+/// File is re-read for every connection, so the password can be rotated in place.
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    user: String,
+    password_file: PathBuf,
+}
+
+impl FileCredentials {
+    /// Make new instance for the given user, reading password from the file.
+    pub fn new<U: Into<String>, P: AsRef<Path>>(user: U, password_file: P) -> Self {
+        Self {
+            user: user.into(),
+            password_file: password_file.as_ref().to_owned(),
+        }
+    }
+}
+
+impl CredentialsProvider for FileCredentials {
+    /// Trailing line break is not considered a part of the password.
+    fn credentials(&self) -> IgniteResult<Credentials> {
+        let password = std::fs::read_to_string(&self.password_file).chain_error(format!(
+            "Can not read password from file {}",
+            self.password_file.display()
+        ))?;
+
+        let password = password.trim_end_matches(&['\n', '\r'][..]);
+
+        Ok(Credentials::new(self.user.clone(), password))
+    }
+}
+
+#[test]
+fn credentials_debug_redacted() {
+    let creds = Credentials::new("ignite", "secret");
+
+    let debug = format!("{:?}", creds);
+
+    assert!(debug.contains("ignite"));
+    assert!(!debug.contains("secret"));
+}
+
+#[test]
+fn env_credentials() {
+    env::set_var("IGNITE_RUST_TEST_USER", "ignite");
+    env::set_var("IGNITE_RUST_TEST_PASSWORD", "secret");
+
+    let provider = EnvCredentials::new("IGNITE_RUST_TEST_USER", "IGNITE_RUST_TEST_PASSWORD");
+    assert_eq!(
+        provider.credentials().unwrap(),
+        Credentials::new("ignite", "secret")
+    );
+
+    let provider = EnvCredentials::new("IGNITE_RUST_TEST_USER", "IGNITE_RUST_TEST_MISSING");
+    assert!(provider.credentials().is_err());
+}
+
+#[test]
+fn file_credentials() {
+    let path = env::temp_dir().join(format!("ignite-rust-password-{}", std::process::id()));
+
+    let provider = FileCredentials::new("ignite", &path);
+    assert!(provider.credentials().is_err());
+
+    std::fs::write(&path, "secret\n").unwrap();
+    assert_eq!(
+        provider.credentials().unwrap(),
+        Credentials::new("ignite", "secret")
+    );
+
+    // Rotated password is picked up.
+    std::fs::write(&path, "rotated").unwrap();
+    assert_eq!(
+        provider.credentials().unwrap(),
+        Credentials::new("ignite", "rotated")
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
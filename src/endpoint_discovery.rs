@@ -0,0 +1,200 @@
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::ignite_error::{ChainResult, IgniteResult};
+use crate::net::EndPoint;
+use crate::IgniteError;
+
+/// Source of the endpoints of the cluster nodes.
+///
+/// The client polls it periodically, connecting to the new endpoints and
+/// forgetting the ones which are not listed anymore. If polling fails, the
+/// known endpoints are kept.
+///
+/// # Examples
+/// ```
+/// use futures::future::{self, BoxFuture, FutureExt};
+/// use ignite_rust::{ClientConfiguration, EndPoint, EndpointDiscovery, IgniteResult};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct Registry;
+///
+/// impl EndpointDiscovery for Registry {
+///     fn endpoints(&self) -> BoxFuture<'_, IgniteResult<Vec<EndPoint>>> {
+///         future::ready(EndPoint::from_string("127.0.0.1:10800").map(|e| vec![e])).boxed()
+///     }
+/// }
+///
+/// let mut cfg = ClientConfiguration::new();
+/// cfg.set_endpoint_discovery(Arc::new(Registry));
+/// ```
+pub trait EndpointDiscovery: fmt::Debug + Send + Sync {
+    /// Get current endpoints.
+    fn endpoints(&self) -> BoxFuture<'_, IgniteResult<Vec<EndPoint>>>;
+}
+
+/// Fixed list of endpoints.
+#[derive(Debug, Clone)]
+pub struct StaticEndpoints {
+    end_points: Vec<EndPoint>,
+}
+
+impl StaticEndpoints {
+    /// Make new instance, parsing the endpoints.
+    /// The format is the one accepted by `ClientConfiguration::set_endpoints()`.
+    pub fn new(end_points: &str) -> IgniteResult<Self> {
+        Ok(Self {
+            end_points: parse_endpoint_list(end_points)?,
+        })
+    }
+}
+
+impl EndpointDiscovery for StaticEndpoints {
+    fn endpoints(&self) -> BoxFuture<'_, IgniteResult<Vec<EndPoint>>> {
+        futures::future::ready(Ok(self.end_points.clone())).boxed()
+    }
+}
+
+/// Reads endpoints from the environment variable.
+/// Variable is read on every poll, so it can be changed while the client is running.
+#[derive(Debug, Clone)]
+pub struct EnvEndpoints {
+    var: String,
+}
+
+impl EnvEndpoints {
+    /// Make new instance, reading endpoints from the given variable.
+    pub fn new<S: Into<String>>(var: S) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl EndpointDiscovery for EnvEndpoints {
+    fn endpoints(&self) -> BoxFuture<'_, IgniteResult<Vec<EndPoint>>> {
+        let res = env::var(&self.var)
+            .chain_error(format!("Can not read environment variable {}", self.var))
+            .and_then(|val| parse_endpoint_list(&val));
+
+        futures::future::ready(res).boxed()
+    }
+}
+
+/// Reads endpoints from the file, one or more per line.
+/// Empty lines and lines starting with '#' are ignored.
+///
+/// File is re-read on every poll, so it can be changed while the client is running.
+#[derive(Debug, Clone)]
+pub struct FileEndpoints {
+    path: PathBuf,
+}
+
+impl FileEndpoints {
+    /// Make new instance, reading endpoints from the given file.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_owned(),
+        }
+    }
+}
+
+impl EndpointDiscovery for FileEndpoints {
+    fn endpoints(&self) -> BoxFuture<'_, IgniteResult<Vec<EndPoint>>> {
+        async move {
+            let content = tokio::fs::read_to_string(&self.path)
+                .await
+                .chain_error(format!(
+                    "Can not read endpoints from file {}",
+                    self.path.display()
+                ))?;
+
+            let lines: Vec<&str> = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .collect();
+
+            parse_endpoint_list(&lines.join(","))
+        }
+        .boxed()
+    }
+}
+
+/// Parse endpoints, failing if the list is empty.
+fn parse_endpoint_list(end_points: &str) -> IgniteResult<Vec<EndPoint>> {
+    if end_points.trim().is_empty() {
+        return Err(IgniteError::new("List of endpoints is empty"));
+    }
+
+    crate::net::utils::parse_endpoints(end_points)
+}
+
+#[test]
+fn static_endpoints() {
+    let discovery = StaticEndpoints::new("127.0.0.1:42, example.com").unwrap();
+
+    assert_eq!(
+        futures::executor::block_on(discovery.endpoints()).unwrap(),
+        crate::net::utils::parse_endpoints("127.0.0.1:42,example.com").unwrap()
+    );
+
+    StaticEndpoints::new("").unwrap_err();
+    StaticEndpoints::new("127.0.0.1:0").unwrap_err();
+}
+
+#[test]
+fn env_endpoints() {
+    let discovery = EnvEndpoints::new("IGNITE_RUST_TEST_ENDPOINTS");
+    let endpoints = || futures::executor::block_on(discovery.endpoints());
+
+    env::remove_var("IGNITE_RUST_TEST_ENDPOINTS");
+    assert!(endpoints().is_err());
+
+    env::set_var("IGNITE_RUST_TEST_ENDPOINTS", "127.0.0.1:42..44");
+    assert_eq!(
+        endpoints().unwrap(),
+        vec![EndPoint::from_string("127.0.0.1:42..44").unwrap()]
+    );
+
+    env::set_var("IGNITE_RUST_TEST_ENDPOINTS", " ");
+    assert!(endpoints().is_err());
+}
+
+#[test]
+fn file_endpoints() {
+    let path = env::temp_dir().join(format!("ignite-rust-endpoints-{}", std::process::id()));
+
+    let discovery = FileEndpoints::new(&path);
+    let endpoints = || {
+        let mut rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(discovery.endpoints())
+    };
+
+    assert!(endpoints().is_err());
+
+    std::fs::write(
+        &path,
+        "# Cluster nodes\n127.0.0.1:42\n\n[::1]:43, example.com\n",
+    )
+    .unwrap();
+    assert_eq!(
+        endpoints().unwrap(),
+        crate::net::utils::parse_endpoints("127.0.0.1:42,[::1]:43,example.com").unwrap()
+    );
+
+    // Changes are picked up.
+    std::fs::write(&path, "127.0.0.1:44\n").unwrap();
+    assert_eq!(
+        endpoints().unwrap(),
+        vec![EndPoint::from_string("127.0.0.1:44").unwrap()]
+    );
+
+    std::fs::write(&path, "# Nothing here\n").unwrap();
+    assert!(endpoints().is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
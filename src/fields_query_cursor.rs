@@ -0,0 +1,175 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::{Stream, StreamExt};
+
+use crate::from_row::{FromRow, Row};
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::net::{AsyncDataChannel, MessageRouter};
+use crate::paged_cursor::PagedCursor;
+use crate::protocol::message::{QuerySqlFieldsCursorGetPageReq, QuerySqlFieldsRsp};
+
+/// Cursor over the rows of the SQL fields query.
+///
+/// Rows are fetched from the server page by page, as the stream is polled.
+/// The cursor is bound to the connection the query was executed over, so
+/// it fails if the connection is lost. If the cursor is dropped before all
+/// the rows are fetched, it is closed on the server in the background.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// use futures::TryStreamExt;
+///
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+/// let mut cursor = client.query_sql_fields(&query).await?;
+///
+/// while let Some(row) = cursor.try_next().await? {
+///     println!("{:?}: {:?}", row[0], row[1]);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct FieldsQueryCursor {
+    inner: PagedCursor<Vec<IgniteValue>, QuerySqlFieldsCursorGetPageReq>,
+    column_count: usize,
+    columns: Vec<String>,
+    column_types: Vec<Option<&'static str>>,
+}
+
+impl FieldsQueryCursor {
+    /// Make new instance out of the query response.
+    pub(crate) fn new(
+        router: Arc<MessageRouter>,
+        channel: Arc<AsyncDataChannel>,
+        rsp: QuerySqlFieldsRsp,
+    ) -> Self {
+        let cursor_id = rsp.cursor_id();
+        let column_count = rsp.column_count();
+        let (columns, page) = rsp.into_parts();
+
+        // Types are not sent by the server, so they are taken from the first non-null values.
+        let column_types = (0..column_count)
+            .map(|col| {
+                page.rows()
+                    .iter()
+                    .map(|row| &row[col])
+                    .find(|value| !value.is_null())
+                    .map(IgniteValue::type_name)
+            })
+            .collect();
+
+        let get_page = QuerySqlFieldsCursorGetPageReq::new(cursor_id, column_count);
+
+        Self {
+            inner: PagedCursor::new(router, channel, cursor_id, page, get_page),
+            column_count,
+            columns,
+            column_types,
+        }
+    }
+
+    /// Get names of the columns.
+    /// Empty if the names were not requested with `SqlFieldsQuery::set_include_field_names()`.
+    pub fn column_names(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Get number of the columns.
+    pub fn column_count(&self) -> usize {
+        self.column_count
+    }
+
+    /// Get types of the columns, as returned by `IgniteValue::type_name()`.
+    ///
+    /// The server does not send the types, so they are taken from the values of the
+    /// first page. Type of the column is None if it only has nulls on the first page.
+    pub fn column_types(&self) -> &[Option<&'static str>] {
+        &self.column_types
+    }
+}
+
+impl Stream for FieldsQueryCursor {
+    type Item = IgniteResult<Vec<IgniteValue>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next_unpin(cx)
+    }
+}
+
+impl fmt::Debug for FieldsQueryCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldsQueryCursor")
+            .field("cursor_id", &self.inner.cursor_id())
+            .field("columns", &self.columns)
+            .field("has_more", &self.inner.has_more())
+            .finish()
+    }
+}
+
+/// Cursor over the rows of the SQL fields query, converted into the given type.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// use futures::TryStreamExt;
+///
+/// #[derive(FromRow)]
+/// struct Person {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+/// let query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+/// let people: Vec<Person> = client.query_as(&query).await?.try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RowCursor<T> {
+    inner: FieldsQueryCursor,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRow> RowCursor<T> {
+    /// Make new instance.
+    pub(crate) fn new(inner: FieldsQueryCursor) -> Self {
+        Self {
+            inner,
+            _t: PhantomData,
+        }
+    }
+
+    /// Get names of the columns.
+    pub fn column_names(&self) -> &[String] {
+        self.inner.column_names()
+    }
+}
+
+impl<T: FromRow> Stream for RowCursor<T> {
+    type Item = IgniteResult<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        this.inner.poll_next_unpin(cx).map(|row| {
+            row.map(|values| {
+                let mut row = Row::new(this.inner.column_names(), values?);
+                T::from_row(&mut row)
+            })
+        })
+    }
+}
+
+impl<T> fmt::Debug for RowCursor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowCursor")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
@@ -0,0 +1,251 @@
+use std::any;
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::binary_object::BinaryObject;
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::IgniteError;
+
+/// Type which can be made out of the value of SQL query result.
+///
+/// Integers can be converted into the wider types, and into the narrower ones
+/// if the value fits. `DECIMAL` values can be converted into `f64`, `TIMESTAMP`
+/// and `DATE` values into `SystemTime`. Nulls can only be converted into `Option`.
+pub trait FromValue: Sized {
+    /// Convert the value.
+    fn from_value(value: IgniteValue) -> IgniteResult<Self>;
+}
+
+/// Type which can be made out of the row of SQL query result.
+///
+/// Usually it is derived, mapping columns to the fields of the struct by name.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::FromRow;
+///
+/// #[derive(FromRow)]
+/// struct Person {
+///     id: i64,
+///     #[ignite(rename = "FULL_NAME")]
+///     name: Option<String>,
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Make the value out of the row, taking values of the columns.
+    fn from_row(row: &mut Row<'_>) -> IgniteResult<Self>;
+}
+
+/// Row of SQL query result with the column names.
+#[derive(Debug)]
+pub struct Row<'a> {
+    columns: &'a [String],
+    values: Vec<IgniteValue>,
+}
+
+impl<'a> Row<'a> {
+    /// Make new instance.
+    pub(crate) fn new(columns: &'a [String], values: Vec<IgniteValue>) -> Self {
+        Self { columns, values }
+    }
+
+    /// Get names of the columns.
+    pub fn columns(&self) -> &[String] {
+        self.columns
+    }
+
+    /// Get value of the column. Names are compared case-insensitively,
+    /// as the server returns them in the upper case unless they are quoted.
+    pub fn get(&self, column: &str) -> Option<&IgniteValue> {
+        self.index(column).map(|idx| &self.values[idx])
+    }
+
+    /// Take value of the column out of the row, converting it into the given type.
+    pub fn take<T: FromValue>(&mut self, column: &str) -> IgniteResult<T> {
+        let idx = self
+            .index(column)
+            .ok_or_else(|| IgniteError::new(format!("Column {} is not found", column)))?;
+
+        let value = std::mem::replace(&mut self.values[idx], IgniteValue::Null);
+
+        T::from_value(value).map_err(|err| {
+            IgniteError::new(format!(
+                "Can not read column {}: {}",
+                self.columns[idx], err
+            ))
+        })
+    }
+
+    /// Find index of the column.
+    fn index(&self, column: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(column))
+    }
+}
+
+/// Make error of the value, which can not be converted into the type.
+fn mismatch<T>(value: &IgniteValue) -> IgniteError {
+    IgniteError::new(format!(
+        "{} value can not be converted into {}",
+        value.type_name(),
+        any::type_name::<T>()
+    ))
+}
+
+macro_rules! impl_from_value_for_int {
+    ($ttype:ty) => {
+        impl FromValue for $ttype {
+            fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+                let wide = value.as_i64().ok_or_else(|| mismatch::<Self>(&value))?;
+
+                <$ttype>::try_from(wide).map_err(|_| {
+                    IgniteError::new(format!(
+                        "Value {} does not fit into {}",
+                        wide,
+                        any::type_name::<Self>()
+                    ))
+                })
+            }
+        }
+    };
+}
+
+impl_from_value_for_int!(i8);
+impl_from_value_for_int!(i16);
+impl_from_value_for_int!(i32);
+impl_from_value_for_int!(i64);
+
+macro_rules! impl_from_value_for_variant {
+    ($ttype:ty, $variant:ident) => {
+        impl FromValue for $ttype {
+            fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+                match value {
+                    IgniteValue::$variant(v) => Ok(v),
+                    value => Err(mismatch::<Self>(&value)),
+                }
+            }
+        }
+    };
+}
+
+impl_from_value_for_variant!(bool, Bool);
+impl_from_value_for_variant!(char, Char);
+impl_from_value_for_variant!(String, String);
+impl_from_value_for_variant!(Uuid, Uuid);
+impl_from_value_for_variant!(Vec<u8>, ByteArray);
+impl_from_value_for_variant!(BinaryObject, Object);
+
+impl FromValue for f32 {
+    fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+        match value {
+            IgniteValue::Float(v) => Ok(v),
+            value => Err(mismatch::<Self>(&value)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    /// Decimal values are converted with the possible loss of precision.
+    fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+        match value {
+            IgniteValue::Float(v) => Ok(f64::from(v)),
+            IgniteValue::Double(v) => Ok(v),
+            IgniteValue::Decimal(unscaled, scale) => Ok(unscaled as f64 / 10f64.powi(scale)),
+            value => Err(mismatch::<Self>(&value)),
+        }
+    }
+}
+
+impl FromValue for SystemTime {
+    fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+        let (millis, nanos) = match value {
+            IgniteValue::Timestamp(millis, nanos) => (millis, nanos),
+            IgniteValue::Date(millis) => (millis, 0),
+            value => return Err(mismatch::<Self>(&value)),
+        };
+
+        let since_epoch = Duration::from_millis(millis.unsigned_abs());
+        let time = if millis >= 0 {
+            UNIX_EPOCH.checked_add(since_epoch)
+        } else {
+            UNIX_EPOCH.checked_sub(since_epoch)
+        };
+
+        // Nanoseconds within the millisecond are never negative.
+        let nanos = Duration::from_nanos(u64::try_from(nanos).unwrap_or(0));
+        let time = time.and_then(|time| time.checked_add(nanos));
+
+        time.ok_or_else(|| IgniteError::new(format!("Timestamp {} is out of range", millis)))
+    }
+}
+
+impl FromValue for IgniteValue {
+    fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+        Ok(value)
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: IgniteValue) -> IgniteResult<Self> {
+        match value {
+            IgniteValue::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_value_conversions() {
+        assert_eq!(i64::from_value(IgniteValue::Int(42)).unwrap(), 42);
+        assert_eq!(i8::from_value(IgniteValue::Long(-1)).unwrap(), -1);
+        assert!(i8::from_value(IgniteValue::Long(300)).is_err());
+        assert!(i32::from_value(IgniteValue::Null).is_err());
+        assert_eq!(Option::<i32>::from_value(IgniteValue::Null).unwrap(), None);
+
+        assert_eq!(
+            f64::from_value(IgniteValue::Decimal(-12345, 2)).unwrap(),
+            -123.45
+        );
+
+        let err = String::from_value(IgniteValue::Int(1)).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Int value can not be converted into"));
+
+        let time = SystemTime::from_value(IgniteValue::Timestamp(1500, 1000)).unwrap();
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_micros(1_500_001)
+        );
+
+        let time = SystemTime::from_value(IgniteValue::Date(-1000)).unwrap();
+        assert_eq!(
+            UNIX_EPOCH.duration_since(time).unwrap(),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn row_take() {
+        let columns = vec!["ID".to_owned(), "NAME".to_owned()];
+        let mut row = Row::new(&columns, vec![IgniteValue::Int(1), IgniteValue::Null]);
+
+        assert_eq!(row.get("id"), Some(&IgniteValue::Int(1)));
+        assert_eq!(row.take::<i64>("Id").unwrap(), 1);
+        assert_eq!(row.take::<Option<String>>("name").unwrap(), None);
+
+        let err = row.take::<String>("name").unwrap_err();
+        assert!(err.to_string().starts_with("Can not read column NAME"));
+
+        assert!(row.take::<i32>("age").is_err());
+    }
+}
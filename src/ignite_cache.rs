@@ -1,17 +1,235 @@
+use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::continuous_query::ContinuousQuery;
+use crate::continuous_query_cursor::ContinuousQueryCursor;
+use crate::ignite_error::IgniteResult;
+use crate::net::MessageRouter;
+use crate::protocol::message::{CacheContainsKeyReq, CacheGetReq, CachePutReq, CacheRemoveKeyReq};
+use crate::protocol::message::{QueryContinuousReq, QueryScanReq, Request};
+use crate::protocol::{utils, IgniteHash, ProtocolType};
+use crate::scan_query::ScanQuery;
+use crate::scan_query_cursor::ScanQueryCursor;
+use crate::transactions::{Transaction, TxContext};
 
 /// Ignite cache
 /// Interface for all the cache operations.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct IgniteCache<K, V> {
-    _a : PhantomData<K>,
-    _b : PhantomData<V>,
+    id: i32,
+    name: String,
+    router: Arc<MessageRouter>,
+    timeout: Option<Duration>,
+    tx: Option<TxContext>,
+    _a: PhantomData<K>,
+    _b: PhantomData<V>,
 }
-//  {
-//     id: i32,
-//     name: String,
-// }
 
-// impl IgniteCache<K,V> {
+impl<K, V> IgniteCache<K, V> {
+    /// Make new instance.
+    pub(crate) fn new(name: String, router: Arc<MessageRouter>) -> Self {
+        Self {
+            id: utils::java_string_hash(&name),
+            name,
+            router,
+            timeout: None,
+            tx: None,
+            _a: PhantomData,
+            _b: PhantomData,
+        }
+    }
+
+    /// Get instance of the same cache, which operations time out after the
+    /// given duration instead of the configured request timeout.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::{IgniteCache, IgniteResult};
+    /// # use std::time::Duration;
+    /// # async fn example(cache: IgniteCache<i32, String>) -> IgniteResult<()> {
+    /// let value = cache.with_timeout(Duration::from_millis(200)).get(&1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Get instance of the same cache, which key operations are made within the transaction.
+    ///
+    /// The operations are sent over the connection the transaction was started over,
+    /// regardless of the node storing the key. They fail once the transaction is finished.
+    pub fn with_transaction(&self, tx: &Transaction) -> Self {
+        Self {
+            tx: Some(tx.context().clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Get ID of the transaction the key operations are made within, if any.
+    pub fn transaction_id(&self) -> Option<i32> {
+        self.tx.as_ref().map(TxContext::id)
+    }
+
+    /// Get timeout of the operations, if it is overridden for this instance.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Get cache ID.
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// Get cache name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-// }
+    /// Send key operation request, over the transaction connection if there is one.
+    async fn send_key_op<R: Request>(&self, req: &R) -> IgniteResult<R::Response> {
+        match &self.tx {
+            Some(tx) => self.router.send_on(tx.channel(), req, self.timeout).await,
+            None => self.router.send_with_timeout(req, self.timeout).await,
+        }
+    }
+}
+
+impl<K, V> IgniteCache<K, V>
+where
+    K: ProtocolType + IgniteHash + Sync,
+    V: ProtocolType<Item = V>,
+{
+    /// Get value by key. None if there is no value for the key.
+    pub async fn get(&self, key: &K) -> IgniteResult<Option<V>> {
+        let tx_id = self.transaction_id();
+        self.send_key_op(&CacheGetReq::<K, V>::new(self.id, key).with_tx(tx_id))
+            .await
+    }
+
+    /// Put value by key, replacing the existing one.
+    pub async fn put(&self, key: &K, value: &V) -> IgniteResult<()> {
+        let tx_id = self.transaction_id();
+        self.send_key_op(&CachePutReq::new(self.id, key, value).with_tx(tx_id))
+            .await
+    }
+
+    /// Check if there is a value for the key.
+    pub async fn contains_key(&self, key: &K) -> IgniteResult<bool> {
+        let tx_id = self.transaction_id();
+        self.send_key_op(&CacheContainsKeyReq::new(self.id, key).with_tx(tx_id))
+            .await
+    }
+
+    /// Remove value by key. Returns true if the value was removed.
+    pub async fn remove(&self, key: &K) -> IgniteResult<bool> {
+        let tx_id = self.transaction_id();
+        self.send_key_op(&CacheRemoveKeyReq::new(self.id, key).with_tx(tx_id))
+            .await
+    }
+}
+
+impl<K, V> IgniteCache<K, V>
+where
+    K: ProtocolType<Item = K> + Send + 'static,
+    V: ProtocolType<Item = V> + Send + 'static,
+{
+    /// Iterate over the entries of the cache, optionally filtered on the server.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::*;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn run(cache: IgniteCache<i32, String>) -> IgniteResult<()> {
+    /// let mut query = ScanQuery::new();
+    /// query.set_partition(Some(0));
+    ///
+    /// let entries: Vec<(i32, String)> = cache.scan(&query).await?.try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan(&self, query: &ScanQuery) -> IgniteResult<ScanQueryCursor<K, V>> {
+        let req = QueryScanReq::<K, V>::new(self.id, query);
+        let (rsp, channel) = self.router.send_bound(&req, self.timeout).await?;
+
+        Ok(ScanQueryCursor::new(self.router.clone(), channel, rsp))
+    }
+
+    /// Start listening to the changes of the cache entries.
+    ///
+    /// The changes are streamed until the cursor is dropped or the connection is lost.
+    /// If the initial query is set, it is executed over the same connection once the
+    /// listening starts, and its entries are available with `initial_entries()`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::*;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn run(cache: IgniteCache<i32, String>) -> IgniteResult<()> {
+    /// let mut query = ContinuousQuery::new();
+    /// query.set_initial_query(Some(ScanQuery::new()));
+    ///
+    /// let mut events = cache.continuous_query(&query).await?;
+    /// let existing: Vec<(i32, String)> = match events.initial_entries() {
+    ///     Some(entries) => entries.try_collect().await?,
+    ///     None => Vec::new(),
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn continuous_query(
+        &self,
+        query: &ContinuousQuery,
+    ) -> IgniteResult<ContinuousQueryCursor<K, V>> {
+        let req = QueryContinuousReq::new(self.id, query);
+        let (query_id, notifications, channel) = self.router.subscribe(&req, self.timeout).await?;
+
+        let mut cursor = ContinuousQueryCursor::new(
+            self.router.clone(),
+            channel.clone(),
+            query_id,
+            notifications,
+        );
+
+        // Query is stopped on the server if the initial one fails, as the cursor is dropped.
+        if let Some(initial) = query.get_initial_query() {
+            let req = QueryScanReq::<K, V>::new(self.id, initial);
+            let rsp = self.router.send_on(&channel, &req, self.timeout).await?;
+
+            cursor.set_initial(ScanQueryCursor::new(self.router.clone(), channel, rsp));
+        }
+
+        Ok(cursor)
+    }
+}
+
+impl<K, V> Clone for IgniteCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            name: self.name.clone(),
+            router: self.router.clone(),
+            timeout: self.timeout,
+            tx: self.tx.clone(),
+            _a: PhantomData,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for IgniteCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IgniteCache")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("timeout", &self.timeout)
+            .field("tx", &self.transaction_id())
+            .finish()
+    }
+}
@@ -1,18 +1,34 @@
+use std::convert::TryFrom;
 use std::sync::Arc;
 
+use futures::future::BoxFuture;
+use futures::StreamExt;
+
+use super::client_cluster::ClientCluster;
+use super::client_compute::ClientCompute;
 use super::client_configuration::ClientConfiguration;
+use super::client_services::ClientServices;
 use super::ignite_error::IgniteResult;
 use super::net::MessageRouter;
 
-use crate::IgniteError;
+use crate::fields_query_cursor::{FieldsQueryCursor, RowCursor};
+use crate::from_row::FromRow;
+use crate::ignite_value::IgniteValue;
+use crate::protocol::message::{
+    CacheCreateWithNameReq, CacheGetNamesReq, CacheGetOrCreateWithNameReq, QuerySqlFieldsReq,
+};
+use crate::sql_fields_query::{SqlFieldsQuery, StatementType};
+use crate::transaction_options::TransactionOptions;
+use crate::transactions::{ClientTransactions, Transaction};
 use crate::IgniteCache;
+use crate::IgniteError;
 
 /// Ignite client
 /// Main entry point for the Ignite Rust thin client API.
 #[derive(Debug)]
 pub struct IgniteClient {
     cfg: Arc<ClientConfiguration>,
-    router: MessageRouter,
+    router: Arc<MessageRouter>,
 }
 
 impl IgniteClient {
@@ -20,16 +36,19 @@ impl IgniteClient {
     pub async fn start(cfg: ClientConfiguration) -> IgniteResult<IgniteClient> {
         Self::validate_cfg(&cfg)?;
 
-        let client = Self::new(cfg);
+        let client = Self::new(cfg)?;
 
         client.router.establish_connection().await?;
 
+        MessageRouter::start_heartbeats(&client.router);
+        MessageRouter::start_resolving(&client.router);
+
         Ok(client)
     }
 
     /// Validate configuration
     fn validate_cfg(cfg: &ClientConfiguration) -> IgniteResult<()> {
-        if cfg.get_endpoints().is_empty() {
+        if cfg.get_endpoints().is_empty() && cfg.get_endpoint_discovery().is_none() {
             return Err(IgniteError::new("No endpoints of nodes are specified"));
         }
 
@@ -37,19 +56,167 @@ impl IgniteClient {
     }
 
     /// Create new instance.
-    fn new(cfg0: ClientConfiguration) -> IgniteClient {
+    fn new(cfg0: ClientConfiguration) -> IgniteResult<IgniteClient> {
         let cfg = Arc::new(cfg0);
-        let router = MessageRouter::new(cfg.clone());
+        let router = Arc::new(MessageRouter::new(cfg.clone())?);
+
+        Ok(IgniteClient { cfg, router })
+    }
+
+    /// Get configuration the client was started with.
+    pub fn configuration(&self) -> &ClientConfiguration {
+        &self.cfg
+    }
+
+    /// Get the cluster the client is connected to.
+    pub fn cluster(&self) -> ClientCluster {
+        ClientCluster::new(self.router.clone())
+    }
 
-        IgniteClient { cfg, router }
+    /// Get compute tasks of the cluster the client is connected to.
+    pub fn compute(&self) -> ClientCompute {
+        ClientCompute::new(self.router.clone())
+    }
+
+    /// Get services deployed in the cluster the client is connected to.
+    pub fn services(&self) -> ClientServices {
+        ClientServices::new(self.router.clone())
+    }
+
+    /// Get transactions of the cluster the client is connected to.
+    pub fn transactions(&self) -> ClientTransactions {
+        ClientTransactions::new(self.router.clone())
+    }
+
+    /// Run the closure within the new transaction, committing it on success.
+    /// See `ClientTransactions::transactional()`.
+    pub async fn transactional<T, F>(&self, opts: &TransactionOptions, f: F) -> IgniteResult<T>
+    where
+        F: for<'t> FnMut(&'t Transaction) -> BoxFuture<'t, IgniteResult<T>>,
+    {
+        self.transactions().transactional(opts, f).await
     }
 
     /// Create a new cache instance.
     /// Fails if the cache already exists.
-    pub async fn create_cache<K, V>(&self, _name: String) -> IgniteResult<IgniteCache<K, V>> {
-        // self.router.
+    pub async fn create_cache<K, V>(&self, name: String) -> IgniteResult<IgniteCache<K, V>> {
+        self.router
+            .send(&CacheCreateWithNameReq::new(&name))
+            .await?;
+
+        Ok(IgniteCache::new(name, self.router.clone()))
+    }
+
+    /// Get existing cache instance or create a new one.
+    pub async fn get_or_create_cache<K, V>(&self, name: String) -> IgniteResult<IgniteCache<K, V>> {
+        self.router
+            .send(&CacheGetOrCreateWithNameReq::new(&name))
+            .await?;
+
+        Ok(IgniteCache::new(name, self.router.clone()))
+    }
+
+    /// Get names of all the existing caches.
+    pub async fn cache_names(&self) -> IgniteResult<Vec<String>> {
+        self.router.send(&CacheGetNamesReq::new()).await
+    }
+
+    /// Execute SQL query, returning cursor over the rows of its fields.
+    pub async fn query_sql_fields(
+        &self,
+        query: &SqlFieldsQuery,
+    ) -> IgniteResult<FieldsQueryCursor> {
+        let (rsp, channel) = self
+            .router
+            .send_bound(&QuerySqlFieldsReq::new(query), None)
+            .await?;
+
+        Ok(FieldsQueryCursor::new(self.router.clone(), channel, rsp))
+    }
+
+    /// Execute DML or DDL statement, returning number of the affected rows.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::*;
+    /// # async fn run(client: IgniteClient) -> IgniteResult<()> {
+    /// client
+    ///     .execute("CREATE TABLE Person (id INT PRIMARY KEY, name VARCHAR)", vec![])
+    ///     .await?;
+    ///
+    /// let inserted = client
+    ///     .execute("INSERT INTO Person (id, name) VALUES (?, ?)", vec![1.into(), "Alice".into()])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute(&self, sql: &str, args: Vec<IgniteValue>) -> IgniteResult<u64> {
+        let mut query = SqlFieldsQuery::new(sql);
+        query.set_statement_type(StatementType::Update);
+        query.set_args(args);
+
+        self.execute_query(&query).await
+    }
+
+    /// Execute the statement once for every set of the arguments, in order,
+    /// returning number of the affected rows for every set.
+    ///
+    /// Statements are executed one by one, not in a transaction, so the sets
+    /// preceding the failed one stay applied.
+    pub async fn execute_batch(
+        &self,
+        sql: &str,
+        arg_sets: Vec<Vec<IgniteValue>>,
+    ) -> IgniteResult<Vec<u64>> {
+        let mut query = SqlFieldsQuery::new(sql);
+        query.set_statement_type(StatementType::Update);
+
+        let mut counts = Vec::with_capacity(arg_sets.len());
+        for (idx, args) in arg_sets.into_iter().enumerate() {
+            query.set_args(args);
+
+            let count = self.execute_query(&query).await.map_err(|err| {
+                IgniteError::new_with_kind_and_source(
+                    err.kind(),
+                    format!("Failed to execute argument set {}", idx),
+                    Box::new(err),
+                )
+            })?;
+
+            counts.push(count);
+        }
+
+        Ok(counts)
+    }
+
+    /// Execute the query, which is expected to return the update count,
+    /// e.g. the query with the update statement type.
+    pub async fn execute_query(&self, query: &SqlFieldsQuery) -> IgniteResult<u64> {
+        let mut cursor = self.query_sql_fields(query).await?;
+
+        let count = match cursor.next().await {
+            Some(row) => row?.first().and_then(IgniteValue::as_i64),
+            None => None,
+        };
+
+        count
+            .and_then(|count| u64::try_from(count).ok())
+            .ok_or_else(|| IgniteError::new("Statement did not return the update count"))
+    }
+
+    /// Execute SQL query, returning cursor over its rows converted into the given type.
+    /// Names of the columns are always requested, as the rows are mapped by them.
+    pub async fn query_as<T: FromRow>(&self, query: &SqlFieldsQuery) -> IgniteResult<RowCursor<T>> {
+        let cursor = if query.get_include_field_names() {
+            self.query_sql_fields(query).await?
+        } else {
+            let mut query = query.clone();
+            query.set_include_field_names(true);
+
+            self.query_sql_fields(&query).await?
+        };
 
-        unimplemented!();
+        Ok(RowCursor::new(cursor))
     }
 }
 
@@ -57,7 +224,7 @@ impl IgniteClient {
 fn test_ignite_client_sync() {
     use std::thread;
 
-    let client = IgniteClient::new(ClientConfiguration::new());
+    let client = IgniteClient::new(ClientConfiguration::new()).unwrap();
 
     let arc = Arc::new(client);
 
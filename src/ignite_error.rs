@@ -3,17 +3,42 @@ use std::convert::Into;
 use std::error::Error;
 use std::fmt;
 
+/// Boxed cause of the error.
+type ErrorCause = Box<dyn Error + Send + Sync + 'static>;
+
+/// Kind of the error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Error without any specific category.
+    General,
+    /// Connection to a node can not be established or was lost.
+    Connection,
+    /// Credentials were rejected by the node, or could not be obtained.
+    Authentication,
+    /// Operation did not complete in time.
+    Timeout,
+    /// Operation is not supported by the node the client is connected to.
+    Unsupported,
+    /// Request was rejected by the server with the given status code.
+    Server(i32),
+}
+
 /// We keep all the content here
 #[derive(Debug)]
 struct IgniteErrorContents {
+    kind: ErrorKind,
     message: String,
-    cause: Option<Box<dyn Error + 'static>>,
+    cause: Option<ErrorCause>,
 }
 
 impl IgniteErrorContents {
     /// Make new instance
-    fn new(message: String, cause: Option<Box<dyn Error>>) -> IgniteErrorContents {
-        IgniteErrorContents { message, cause }
+    fn new(kind: ErrorKind, message: String, cause: Option<ErrorCause>) -> IgniteErrorContents {
+        IgniteErrorContents {
+            kind,
+            message,
+            cause,
+        }
     }
 }
 
@@ -26,17 +51,36 @@ pub struct IgniteError {
 impl IgniteError {
     /// Create new IgniteError instance
     pub fn new<S: Into<String>>(message: S) -> IgniteError {
+        Self::new_with_kind(ErrorKind::General, message)
+    }
+
+    /// Create new IgniteError instance with cause
+    pub fn new_with_source<S: Into<String>>(message: S, cause: ErrorCause) -> IgniteError {
+        Self::new_with_kind_and_source(ErrorKind::General, message, cause)
+    }
+
+    /// Create new IgniteError instance of the specified kind
+    pub fn new_with_kind<S: Into<String>>(kind: ErrorKind, message: S) -> IgniteError {
         IgniteError {
-            err: Box::new(IgniteErrorContents::new(message.into(), None)),
+            err: Box::new(IgniteErrorContents::new(kind, message.into(), None)),
         }
     }
 
-    /// Create new IgniteError instance with cause
-    pub fn new_with_source<S: Into<String>>(message: S, cause: Box<dyn Error>) -> IgniteError {
+    /// Create new IgniteError instance of the specified kind with cause
+    pub fn new_with_kind_and_source<S: Into<String>>(
+        kind: ErrorKind,
+        message: S,
+        cause: ErrorCause,
+    ) -> IgniteError {
         IgniteError {
-            err: Box::new(IgniteErrorContents::new(message.into(), Some(cause))),
+            err: Box::new(IgniteErrorContents::new(kind, message.into(), Some(cause))),
         }
     }
+
+    /// Get kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.err.kind
+    }
 }
 
 impl From<std::io::Error> for IgniteError {
@@ -49,12 +93,10 @@ impl From<std::io::Error> for IgniteError {
 impl Error for IgniteError {
     /// Get a source of the error if any.
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.err.cause.as_ref().map(|bs| bs.as_ref())
-    }
-
-    /// Get error description.
-    fn description(&self) -> &str {
-        self.err.message.as_ref()
+        self.err
+            .cause
+            .as_ref()
+            .map(|bs| bs.as_ref() as &(dyn Error + 'static))
     }
 }
 
@@ -80,9 +122,12 @@ pub type IgniteResult<T> = Result<T, IgniteError>;
 /// For internal use only.
 pub trait LogResult<R> {
     fn log_error<S: Into<String>>(self, lvl: Level, message: S) -> Option<R>;
+    #[allow(dead_code)]
     fn log_error_e<S: Into<String>>(self, message: S) -> Option<R>;
     fn log_error_w<S: Into<String>>(self, message: S) -> Option<R>;
+    #[allow(dead_code)]
     fn log_error_i<S: Into<String>>(self, message: S) -> Option<R>;
+    #[allow(dead_code)]
     fn log_error_d<S: Into<String>>(self, message: S) -> Option<R>;
 }
 
@@ -131,7 +176,7 @@ pub trait ChainResult<R> {
 
 impl<R, E> ChainResult<R> for Result<R, E>
 where
-    E: Error + 'static,
+    E: Error + Send + Sync + 'static,
 {
     /// FIXME: Can cause overhead on hot (Ok) route of execution. Consider using macros instead.
     fn chain_error<S: Into<String>>(self, message: S) -> IgniteResult<R> {
@@ -145,6 +190,7 @@ where
 /// Trait that intended to be implemented for Result types, allowing for
 /// replacing any results with IgniteResult.
 /// For internal use only.
+#[allow(dead_code)]
 pub trait ReplaceResult<R> {
     fn replace_error<S: Into<String>>(self, message: S) -> IgniteResult<R>;
 }
@@ -164,7 +210,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::ignite_error::IgniteError;
+    use crate::ignite_error::{ErrorKind, IgniteError};
     use std::error::Error;
 
     static TEST_MSG: &str = "Test error";
@@ -205,4 +251,14 @@ mod tests {
         assert_eq!(err.to_string(), TEST_MSG);
         assert_eq!(err_nested.to_string(), TEST_MSG);
     }
+
+    #[test]
+    fn error_kind() {
+        let err = get_err().expect_err("Error is expected");
+        assert_eq!(err.kind(), ErrorKind::General);
+
+        let err = IgniteError::new_with_kind(ErrorKind::Server(1001), TEST_MSG);
+        assert_eq!(err.kind(), ErrorKind::Server(1001));
+        assert_eq!(err.to_string(), TEST_MSG);
+    }
 }
@@ -0,0 +1,568 @@
+use std::convert::TryFrom;
+
+use uuid::Uuid;
+
+use crate::binary_object::BinaryObject;
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{header, write_full, InStream, OutStream, ProtocolType};
+use crate::IgniteError;
+
+/// Value of any type supported by the client, e.g. a field of the SQL query result
+/// or an argument of the query.
+///
+/// Dates and times are represented the same way the server does it:
+/// `Date` and `Timestamp` are milliseconds since the Unix epoch, `Time` is
+/// milliseconds since the midnight.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::IgniteValue;
+///
+/// let args: Vec<IgniteValue> = vec![42i32.into(), "Acme".into(), None::<i64>.into()];
+///
+/// assert_eq!(args[0].as_i64(), Some(42));
+/// assert_eq!(args[1].as_str(), Some("Acme"));
+/// assert!(args[2].is_null());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum IgniteValue {
+    Null,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Char(char),
+    Bool(bool),
+    String(String),
+    Uuid(Uuid),
+    Date(i64),
+    /// Milliseconds since the Unix epoch and nanoseconds within the millisecond.
+    Timestamp(i64, i32),
+    Time(i64),
+    /// Unscaled value and scale, so the value is `unscaled * 10^(-scale)`.
+    Decimal(i128, i32),
+    ByteArray(Vec<u8>),
+    Object(BinaryObject),
+}
+
+impl IgniteValue {
+    /// Check if the value is null.
+    pub fn is_null(&self) -> bool {
+        *self == IgniteValue::Null
+    }
+
+    /// Get name of the value type, which is the name of the variant.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            IgniteValue::Null => "Null",
+            IgniteValue::Byte(_) => "Byte",
+            IgniteValue::Short(_) => "Short",
+            IgniteValue::Int(_) => "Int",
+            IgniteValue::Long(_) => "Long",
+            IgniteValue::Float(_) => "Float",
+            IgniteValue::Double(_) => "Double",
+            IgniteValue::Char(_) => "Char",
+            IgniteValue::Bool(_) => "Bool",
+            IgniteValue::String(_) => "String",
+            IgniteValue::Uuid(_) => "Uuid",
+            IgniteValue::Date(_) => "Date",
+            IgniteValue::Timestamp(..) => "Timestamp",
+            IgniteValue::Time(_) => "Time",
+            IgniteValue::Decimal(..) => "Decimal",
+            IgniteValue::ByteArray(_) => "ByteArray",
+            IgniteValue::Object(_) => "Object",
+        }
+    }
+
+    /// Get value of any integer type.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            IgniteValue::Byte(v) => Some(i64::from(v)),
+            IgniteValue::Short(v) => Some(i64::from(v)),
+            IgniteValue::Int(v) => Some(i64::from(v)),
+            IgniteValue::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get value of any floating point type.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            IgniteValue::Float(v) => Some(f64::from(v)),
+            IgniteValue::Double(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get boolean value.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            IgniteValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            IgniteValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Write value with the type header.
+    pub(crate) fn write(&self, out: &OutStream) {
+        match self {
+            IgniteValue::Null => out.write_i8(header::NULL),
+            IgniteValue::Byte(v) => write_full(v, out),
+            IgniteValue::Short(v) => write_full(v, out),
+            IgniteValue::Int(v) => write_full(v, out),
+            IgniteValue::Long(v) => write_full(v, out),
+            IgniteValue::Float(v) => write_full(v, out),
+            IgniteValue::Double(v) => write_full(v, out),
+            IgniteValue::Char(v) => {
+                // Characters outside of the BMP can not be represented by a single Java char.
+                out.write_i8(header::CHAR);
+                out.write_i16(*v as u32 as u16 as i16);
+            }
+            IgniteValue::Bool(v) => write_full(v, out),
+            IgniteValue::String(v) => out.write_str(v),
+            IgniteValue::Uuid(v) => write_full(v, out),
+            IgniteValue::Date(v) => {
+                out.write_i8(header::DATE);
+                out.write_i64(*v);
+            }
+            IgniteValue::Timestamp(millis, nanos) => {
+                out.write_i8(header::TIMESTAMP);
+                out.write_i64(*millis);
+                out.write_i32(*nanos);
+            }
+            IgniteValue::Time(v) => {
+                out.write_i8(header::TIME);
+                out.write_i64(*v);
+            }
+            IgniteValue::Decimal(unscaled, scale) => {
+                out.write_i8(header::DECIMAL);
+                out.write_i32(*scale);
+                out.write_u8_array_raw(decimal_magnitude(*unscaled));
+            }
+            IgniteValue::ByteArray(v) => {
+                out.write_i8(header::BYTE_ARRAY);
+                out.write_u8_array_raw(v);
+            }
+            IgniteValue::Object(v) => write_full(v, out),
+        }
+    }
+
+    /// Read value with the type header.
+    /// Values of other types can not be skipped, so they fail the whole response.
+    pub(crate) fn read(stream: &InStream) -> IgniteResult<IgniteValue> {
+        let hdr = stream.read_i8();
+
+        let value = match hdr {
+            header::NULL => IgniteValue::Null,
+            header::BYTE => IgniteValue::Byte(stream.read_i8()),
+            header::SHORT => IgniteValue::Short(stream.read_i16()),
+            header::INT => IgniteValue::Int(stream.read_i32()),
+            header::LONG => IgniteValue::Long(stream.read_i64()),
+            header::FLOAT => IgniteValue::Float(stream.read_f32()),
+            header::DOUBLE => IgniteValue::Double(stream.read_f64()),
+            header::CHAR => IgniteValue::Char(
+                std::char::from_u32(stream.read_i16() as u16 as u32)
+                    .unwrap_or(std::char::REPLACEMENT_CHARACTER),
+            ),
+            header::BOOL => IgniteValue::Bool(stream.read_bool()),
+            header::STRING => IgniteValue::String(stream.read_str_raw().into_owned()),
+            header::UUID => IgniteValue::Uuid(Uuid::read_payload(stream)),
+            header::DATE => IgniteValue::Date(stream.read_i64()),
+            header::TIMESTAMP => IgniteValue::Timestamp(stream.read_i64(), stream.read_i32()),
+            header::TIME => IgniteValue::Time(stream.read_i64()),
+            header::DECIMAL => {
+                let scale = stream.read_i32();
+                let len = stream.read_i32();
+
+                IgniteValue::Decimal(read_decimal(stream.read_bytes(len as usize))?, scale)
+            }
+            header::BYTE_ARRAY => {
+                let len = stream.read_i32();
+
+                IgniteValue::ByteArray(stream.read_bytes(len as usize).to_vec())
+            }
+            header::OBJECT => IgniteValue::Object(BinaryObject::read_checked(stream)?),
+            header::BINARY_OBJECT => {
+                let len = stream.read_i32();
+                let data = stream.read_bytes(len as usize);
+                let offset = stream.read_i32();
+
+                let inner = InStream::new(&data[offset as usize..]);
+                IgniteValue::read(&inner)?
+            }
+            _ => {
+                return Err(IgniteError::new(format!(
+                    "Value of type {} is not supported",
+                    hdr
+                )))
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Skip value of any type with the type header, e.g. the one which can not be read.
+    pub(crate) fn skip(stream: &InStream) -> IgniteResult<()> {
+        let hdr = read_checked_i8(stream)?;
+
+        IgniteValue::skip_payload(hdr, stream)
+    }
+
+    /// Skip value of any type, which type header is already read.
+    pub(crate) fn skip_payload(hdr: i8, stream: &InStream) -> IgniteResult<()> {
+        match hdr {
+            header::NULL => Ok(()),
+            header::BYTE | header::BOOL => skip_bytes(stream, 1),
+            header::SHORT | header::CHAR => skip_bytes(stream, 2),
+            header::INT | header::FLOAT | header::HANDLE => skip_bytes(stream, 4),
+            header::LONG | header::DOUBLE | header::DATE | header::TIME => skip_bytes(stream, 8),
+            header::TIMESTAMP => skip_bytes(stream, 12),
+            header::UUID => skip_bytes(stream, 16),
+            header::STRING => {
+                let len = read_len(stream)?;
+                skip_bytes(stream, len)
+            }
+            header::DECIMAL => {
+                skip_bytes(stream, 4)?;
+                let len = read_len(stream)?;
+                skip_bytes(stream, len)
+            }
+            header::BYTE_ARRAY | header::BOOL_ARRAY => skip_array(stream, 1),
+            header::SHORT_ARRAY | header::CHAR_ARRAY => skip_array(stream, 2),
+            header::INT_ARRAY | header::FLOAT_ARRAY => skip_array(stream, 4),
+            header::LONG_ARRAY | header::DOUBLE_ARRAY => skip_array(stream, 8),
+            header::STRING_ARRAY
+            | header::UUID_ARRAY
+            | header::DATE_ARRAY
+            | header::DECIMAL_ARRAY
+            | header::TIMESTAMP_ARRAY
+            | header::TIME_ARRAY => skip_values(stream, 1),
+            header::OBJECT_ARRAY => {
+                skip_bytes(stream, 4)?;
+                skip_values(stream, 1)
+            }
+            header::OBJECT_COLLECTION => skip_collection(stream, 1),
+            header::OBJECT_MAP => skip_collection(stream, 2),
+            header::ENUM | header::BINARY_ENUM => {
+                skip_type_id(stream)?;
+                skip_bytes(stream, 4)
+            }
+            header::ENUM_ARRAY => {
+                skip_type_id(stream)?;
+                skip_values(stream, 1)
+            }
+            header::BINARY_OBJECT => {
+                let len = read_len(stream)?;
+                skip_bytes(stream, len + 4)
+            }
+            header::OBJECT => {
+                // Length of the object is stored after the version, flags, type ID and hash.
+                if stream.remaining() < 15 {
+                    return Err(malformed_value());
+                }
+
+                let len = InStream::new(&stream.peek_bytes(15)[11..]).read_i32();
+                if len < 1 {
+                    return Err(malformed_value());
+                }
+
+                skip_bytes(stream, len as usize - 1)
+            }
+            _ => Err(IgniteError::new(format!(
+                "Value of type {} is not supported",
+                hdr
+            ))),
+        }
+    }
+}
+
+/// Error of the value which can not be skipped, as it is truncated or malformed.
+fn malformed_value() -> IgniteError {
+    IgniteError::new("Value is malformed")
+}
+
+/// Skip specified number of bytes, checking that the stream has them.
+fn skip_bytes(stream: &InStream, len: usize) -> IgniteResult<()> {
+    if stream.remaining() < len {
+        return Err(malformed_value());
+    }
+
+    stream.read_bytes(len);
+
+    Ok(())
+}
+
+/// Read byte, checking that the stream has it.
+fn read_checked_i8(stream: &InStream) -> IgniteResult<i8> {
+    if stream.remaining() < 1 {
+        return Err(malformed_value());
+    }
+
+    Ok(stream.read_i8())
+}
+
+/// Read length or count, checking that the stream has it and it is not negative.
+fn read_len(stream: &InStream) -> IgniteResult<usize> {
+    if stream.remaining() < 4 {
+        return Err(malformed_value());
+    }
+
+    let len = stream.read_i32();
+    if len < 0 {
+        return Err(malformed_value());
+    }
+
+    Ok(len as usize)
+}
+
+/// Skip array of elements of the fixed size, prefixed with its length.
+fn skip_array(stream: &InStream, elem_size: usize) -> IgniteResult<()> {
+    let len = read_len(stream)?;
+
+    let size = len.checked_mul(elem_size).ok_or_else(malformed_value)?;
+    skip_bytes(stream, size)
+}
+
+/// Skip values with the type headers, prefixed with their count.
+/// Each element consists of the given number of values, e.g. 2 for the map entries.
+fn skip_values(stream: &InStream, per_elem: usize) -> IgniteResult<()> {
+    let count = read_len(stream)?;
+
+    for _ in 0..count * per_elem {
+        IgniteValue::skip(stream)?;
+    }
+
+    Ok(())
+}
+
+/// Skip collection or map, which has the collection type after its size.
+fn skip_collection(stream: &InStream, per_elem: usize) -> IgniteResult<()> {
+    let count = read_len(stream)?;
+    skip_bytes(stream, 1)?;
+
+    for _ in 0..count * per_elem {
+        IgniteValue::skip(stream)?;
+    }
+
+    Ok(())
+}
+
+/// Skip type ID, followed by the class name if the type is not registered.
+fn skip_type_id(stream: &InStream) -> IgniteResult<()> {
+    if stream.remaining() < 4 {
+        return Err(malformed_value());
+    }
+
+    if stream.read_i32() == 0 {
+        IgniteValue::skip(stream)?;
+    }
+
+    Ok(())
+}
+
+/// Convert unscaled decimal value into the magnitude in the big-endian order,
+/// with the sign in the highest bit, the same way the server does it.
+fn decimal_magnitude(unscaled: i128) -> Vec<u8> {
+    let mut bytes = vec![0];
+    bytes.extend_from_slice(&unscaled.unsigned_abs().to_be_bytes());
+
+    // Keep one leading zero byte if the highest bit is needed for the sign.
+    let first = bytes
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(bytes.len() - 1);
+    let first = if bytes[first] & 0x80 != 0 {
+        first - 1
+    } else {
+        first
+    };
+
+    let mut mag = bytes.split_off(first);
+    if unscaled < 0 {
+        mag[0] |= 0x80;
+    }
+
+    mag
+}
+
+/// Read unscaled decimal value from the magnitude with the sign in the highest bit.
+fn read_decimal(mag: &[u8]) -> IgniteResult<i128> {
+    let negative = mag.first().is_some_and(|b| b & 0x80 != 0);
+
+    let mut value: u128 = 0;
+    for (i, b) in mag.iter().enumerate() {
+        let b = if i == 0 { b & 0x7F } else { *b };
+
+        if value.leading_zeros() < 8 {
+            return Err(IgniteError::new("Decimal value is too large"));
+        }
+
+        value = (value << 8) | u128::from(b);
+    }
+
+    if negative {
+        0i128.checked_sub_unsigned(value)
+    } else {
+        i128::try_from(value).ok()
+    }
+    .ok_or_else(|| IgniteError::new("Decimal value is too large"))
+}
+
+macro_rules! impl_from_for_value {
+    ($ttype:ty, $variant:ident) => {
+        impl From<$ttype> for IgniteValue {
+            fn from(value: $ttype) -> Self {
+                IgniteValue::$variant(value)
+            }
+        }
+    };
+}
+
+impl_from_for_value!(i8, Byte);
+impl_from_for_value!(i16, Short);
+impl_from_for_value!(i32, Int);
+impl_from_for_value!(i64, Long);
+impl_from_for_value!(f32, Float);
+impl_from_for_value!(f64, Double);
+impl_from_for_value!(char, Char);
+impl_from_for_value!(bool, Bool);
+impl_from_for_value!(String, String);
+impl_from_for_value!(Uuid, Uuid);
+impl_from_for_value!(Vec<u8>, ByteArray);
+impl_from_for_value!(BinaryObject, Object);
+
+impl From<&str> for IgniteValue {
+    fn from(value: &str) -> Self {
+        IgniteValue::String(value.to_owned())
+    }
+}
+
+impl<T: Into<IgniteValue>> From<Option<T>> for IgniteValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(IgniteValue::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write value and read it back.
+    fn round_trip(value: &IgniteValue) -> IgniteValue {
+        let out = OutStream::new();
+        value.write(&out);
+
+        let mem = out.into_memory();
+        let stream = InStream::new(&mem);
+
+        let read = IgniteValue::read(&stream).unwrap();
+        assert_eq!(stream.remaining(), 0);
+
+        read
+    }
+
+    #[test]
+    fn ignite_value_round_trip() {
+        let values = vec![
+            IgniteValue::Null,
+            IgniteValue::Byte(-1),
+            IgniteValue::Short(300),
+            IgniteValue::Int(42),
+            IgniteValue::Long(-1 << 40),
+            IgniteValue::Float(1.5),
+            IgniteValue::Double(-0.25),
+            IgniteValue::Char('ж'),
+            IgniteValue::Bool(true),
+            IgniteValue::String("Lorem".to_owned()),
+            IgniteValue::Uuid(Uuid::from_u128(42)),
+            IgniteValue::Date(1_600_000_000_000),
+            IgniteValue::Timestamp(1_600_000_000_000, 999_999),
+            IgniteValue::Time(3_600_000),
+            IgniteValue::Decimal(-12345, 3),
+            IgniteValue::ByteArray(vec![1, 2, 3]),
+        ];
+
+        for value in &values {
+            assert_eq!(&round_trip(value), value);
+        }
+    }
+
+    #[test]
+    fn ignite_value_decimal() {
+        // Reference values are produced by Java's BinaryWriterExImpl.
+        assert_eq!(decimal_magnitude(0), vec![0]);
+        assert_eq!(decimal_magnitude(127), vec![127]);
+        assert_eq!(decimal_magnitude(128), vec![0, 128]);
+        assert_eq!(decimal_magnitude(-1), vec![0x81]);
+        assert_eq!(decimal_magnitude(-128), vec![0x80, 0x80]);
+
+        for unscaled in &[0, 1, -1, 255, -256, i128::MAX, i128::MIN] {
+            assert_eq!(
+                read_decimal(&decimal_magnitude(*unscaled)).unwrap(),
+                *unscaled
+            );
+        }
+
+        assert!(read_decimal(&[0x7F; 17]).is_err());
+        assert!(read_decimal(&[0x00; 20]).is_ok());
+    }
+
+    #[test]
+    fn ignite_value_skip() {
+        let out = OutStream::new();
+
+        out.write_i8(header::INT_ARRAY);
+        out.write_i32(2);
+        out.write_bytes(&[0; 8]);
+
+        // Collection of a string and a null.
+        out.write_i8(header::OBJECT_COLLECTION);
+        out.write_i32(2);
+        out.write_i8(1);
+        out.write_str("ok");
+        out.write_i8(header::NULL);
+
+        // Map of an integer to the array of strings.
+        out.write_i8(header::OBJECT_MAP);
+        out.write_i32(1);
+        out.write_i8(1);
+        IgniteValue::Int(1).write(&out);
+        out.write_i8(header::STRING_ARRAY);
+        out.write_i32(1);
+        out.write_i8(header::NULL);
+
+        // Enum of the unregistered type, followed by its class name.
+        out.write_i8(header::ENUM);
+        out.write_i32(0);
+        out.write_str("org.apache.ignite.TestEnum");
+        out.write_i32(3);
+
+        IgniteValue::Byte(7).write(&out);
+
+        let mem = out.into_memory();
+        let stream = InStream::new(&mem);
+        for _ in 0..4 {
+            IgniteValue::skip(&stream).unwrap();
+        }
+
+        assert_eq!(IgniteValue::read(&stream).unwrap(), IgniteValue::Byte(7));
+        assert_eq!(stream.remaining(), 0);
+
+        // Truncated values are reported instead of reading past the end.
+        let truncated = [header::LONG_ARRAY as u8, 2, 0, 0, 0, 0];
+        assert!(IgniteValue::skip(&InStream::new(&truncated)).is_err());
+
+        let negative = [header::STRING as u8, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert!(IgniteValue::skip(&InStream::new(&negative)).is_err());
+    }
+}
@@ -2,15 +2,71 @@
 extern crate log;
 extern crate rand;
 
+mod binary_object;
+mod cache_entry_event;
+mod client_cluster;
+mod client_compute;
 mod client_configuration;
+mod client_services;
+mod cluster_group;
+mod cluster_node;
+mod continuous_query;
+mod continuous_query_cursor;
+mod credentials;
+mod endpoint_discovery;
+mod fields_query_cursor;
+mod from_row;
 mod ignite_cache;
 mod ignite_client;
 mod ignite_error;
+mod ignite_value;
 mod net;
+mod paged_cursor;
 mod protocol;
 mod protocol_version;
+mod resolver;
+mod retry_policy;
+mod scan_query;
+mod scan_query_cursor;
+mod service_descriptor;
+mod sql_fields_query;
+mod tls_configuration;
+mod transaction_options;
+mod transactions;
 
-pub use crate::client_configuration::ClientConfiguration;
-pub use crate::ignite_error::{IgniteError, IgniteResult};
-pub use crate::ignite_client::IgniteClient;
+pub use crate::binary_object::BinaryObject;
+pub use crate::cache_entry_event::{CacheEntryEvent, CacheEntryEventType};
+pub use crate::client_cluster::{ClientCluster, ClusterState};
+pub use crate::client_compute::ClientCompute;
+pub use crate::client_configuration::{ClientConfiguration, LoadBalancing};
+pub use crate::client_services::{ClientServices, ServiceProxy};
+pub use crate::cluster_group::ClusterGroup;
+pub use crate::cluster_node::ClusterNode;
+pub use crate::continuous_query::ContinuousQuery;
+pub use crate::continuous_query_cursor::ContinuousQueryCursor;
+pub use crate::credentials::{Credentials, CredentialsProvider, EnvCredentials, FileCredentials};
+pub use crate::endpoint_discovery::{
+    EndpointDiscovery, EnvEndpoints, FileEndpoints, StaticEndpoints,
+};
+pub use crate::fields_query_cursor::{FieldsQueryCursor, RowCursor};
+pub use crate::from_row::{FromRow, FromValue, Row};
 pub use crate::ignite_cache::IgniteCache;
+pub use crate::ignite_client::IgniteClient;
+pub use crate::ignite_error::{ErrorKind, IgniteError, IgniteResult};
+pub use crate::ignite_value::IgniteValue;
+pub use crate::net::EndPoint;
+pub use crate::protocol::{IgniteHash, ProtocolType};
+pub use crate::resolver::{DnsResolver, Resolver, StaticResolver};
+pub use crate::retry_policy::{
+    RetryAllPolicy, RetryContext, RetryIdempotentPolicy, RetryNonePolicy, RetryPolicy,
+};
+pub use crate::scan_query::ScanQuery;
+pub use crate::scan_query_cursor::ScanQueryCursor;
+pub use crate::service_descriptor::{ServiceDescriptor, ServicePlatform};
+pub use crate::sql_fields_query::{SqlFieldsQuery, StatementType};
+pub use crate::tls_configuration::{TlsConfiguration, TlsVerifyMode};
+pub use crate::transaction_options::TransactionOptions;
+pub use crate::transactions::{
+    ClientTransactions, Transaction, TransactionConcurrency, TransactionIsolation,
+};
+pub use ignite_rust_derive::{service_proxy, FromRow};
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use uuid::Uuid;
+
+use crate::protocol::message::{AffinityTopologyVersion, CachePartitionMap, KeyAffinity};
+
+/// Latest affinity topology version, reported by any of the nodes.
+#[derive(Debug, Default)]
+pub struct AffinityTopology {
+    ver: Mutex<Option<AffinityTopologyVersion>>,
+}
+
+impl AffinityTopology {
+    /// Get latest known version. None if no node has reported it yet.
+    pub fn version(&self) -> Option<AffinityTopologyVersion> {
+        *self.ver.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Update the version, if the reported one is newer.
+    pub fn update(&self, reported: AffinityTopologyVersion) {
+        let mut ver = self.ver.lock().unwrap_or_else(|e| e.into_inner());
+
+        if ver.is_none_or(|ver| ver < reported) {
+            debug!("Affinity topology version changed: {:?}", reported);
+
+            *ver = Some(reported);
+        }
+    }
+}
+
+/// Distribution of the cache partitions, actual for the certain topology version.
+/// None if the distribution is unknown, e.g. the node has not reported it.
+#[derive(Debug)]
+struct CacheAffinity {
+    ver: AffinityTopologyVersion,
+    map: Option<CachePartitionMap>,
+}
+
+/// Cache of partition distributions, used to find the primary node of a key.
+#[derive(Debug, Default)]
+pub struct AffinityContext {
+    topology: Arc<AffinityTopology>,
+    caches: Mutex<HashMap<i32, Arc<CacheAffinity>>>,
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+/// Result of the primary node lookup.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AffinityNode {
+    /// Primary node of the key is known.
+    Node(Uuid),
+    /// Primary node can not be determined.
+    Unknown,
+    /// Distribution of the cache partitions is not known or outdated.
+    Outdated,
+}
+
+impl AffinityContext {
+    /// Get topology, shared with the channels.
+    pub fn topology(&self) -> &Arc<AffinityTopology> {
+        &self.topology
+    }
+
+    /// Find the primary node of the key.
+    pub fn node(&self, key: &KeyAffinity<'_>) -> AffinityNode {
+        let ver = match self.topology.version() {
+            Some(ver) => ver,
+            // Nodes do not support partition awareness, or have not responded yet.
+            None => return AffinityNode::Unknown,
+        };
+
+        let cache = match self.caches().get(&key.cache_id()) {
+            Some(cache) if cache.ver >= ver => cache.clone(),
+            _ => return AffinityNode::Outdated,
+        };
+
+        let node = cache.map.as_ref().and_then(|map| {
+            let parts = map.partitions()?;
+            if parts.is_empty() {
+                return None;
+            }
+
+            let hash = key.key().affinity_hash(map.key_configs());
+
+            parts[partition(hash, parts.len())]
+        });
+
+        match node {
+            Some(node) => AffinityNode::Node(node),
+            None => AffinityNode::Unknown,
+        }
+    }
+
+    /// Lock, which should be held while the distributions are requested,
+    /// so concurrent requests do not refresh the same cache several times.
+    pub async fn lock_refresh(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.refreshing.lock().await
+    }
+
+    /// Store distributions, received for the given topology version.
+    /// Requested caches missing in the response are stored as unknown,
+    /// so they are not requested again until the topology changes.
+    pub fn update(
+        &self,
+        ver: AffinityTopologyVersion,
+        requested: &[i32],
+        caches: Vec<CachePartitionMap>,
+    ) {
+        self.topology.update(ver);
+
+        let mut cached = self.caches();
+        for cache_id in requested {
+            cached.insert(*cache_id, Arc::new(CacheAffinity { ver, map: None }));
+        }
+
+        for map in caches {
+            let cache_id = map.cache_id();
+            let map = Some(map);
+
+            cached.insert(cache_id, Arc::new(CacheAffinity { ver, map }));
+        }
+    }
+
+    /// Store distribution of the cache as unknown for the latest topology version,
+    /// e.g. if it can not be requested, so it is not requested again until the topology changes.
+    pub fn set_unknown(&self, cache_id: i32) {
+        if let Some(ver) = self.topology.version() {
+            self.caches()
+                .insert(cache_id, Arc::new(CacheAffinity { ver, map: None }));
+        }
+    }
+
+    /// Get cached distributions.
+    fn caches(&self) -> MutexGuard<'_, HashMap<i32, Arc<CacheAffinity>>> {
+        self.caches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Calculate partition of the key with the given hash code, the same way as
+/// the Rendezvous affinity function does.
+pub fn partition(hash: i32, parts: usize) -> usize {
+    if parts & (parts - 1) == 0 {
+        let mask = parts as i32 - 1;
+
+        ((hash ^ ((hash as u32) >> 16) as i32) & mask) as usize
+    } else {
+        // The same as Java's Math.abs(), except that i32::MIN gives 0.
+        (hash % parts as i32).checked_abs().unwrap_or(0) as usize
+    }
+}
+
+#[test]
+fn affinity_partition() {
+    // Reference values are produced by RendezvousAffinityFunction.calculatePartition().
+    assert_eq!(partition(0, 1024), 0);
+    assert_eq!(partition(42, 1024), 42);
+    assert_eq!(partition(1_544_803_905, 1024), 594);
+    assert_eq!(partition(-969_099_747, 1024), 33);
+    assert_eq!(partition(-969_099_747, 1000), 747);
+    assert_eq!(partition(i32::MIN, 1024), 0);
+    assert_eq!(partition(42, 1000), 42);
+    assert_eq!(partition(-42, 1000), 42);
+    assert_eq!(partition(1_544_803_905, 1000), 905);
+    assert_eq!(partition(i32::MIN, 1000), 648);
+}
+
+#[test]
+fn affinity_topology_update() {
+    let topology = AffinityTopology::default();
+    assert_eq!(topology.version(), None);
+
+    topology.update(AffinityTopologyVersion::new(2, 1));
+    topology.update(AffinityTopologyVersion::new(1, 5));
+
+    assert_eq!(topology.version(), Some(AffinityTopologyVersion::new(2, 1)));
+}
+
+#[test]
+fn affinity_unknown_cache() {
+    let affinity = AffinityContext::default();
+    let key = KeyAffinity::new(1, &42);
+
+    affinity.update(AffinityTopologyVersion::new(1, 0), &[1], Vec::new());
+    assert_eq!(affinity.node(&key), AffinityNode::Unknown);
+
+    affinity
+        .topology()
+        .update(AffinityTopologyVersion::new(2, 0));
+    assert_eq!(affinity.node(&key), AffinityNode::Outdated);
+
+    affinity.set_unknown(1);
+    assert_eq!(affinity.node(&key), AffinityNode::Unknown);
+}
@@ -1,71 +1,387 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::net::SocketAddr;
-
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bytes::{Bytes, BytesMut};
+use futures::channel::mpsc;
+use futures::future::{AbortHandle, Abortable};
+use futures::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::io::{ReadHalf, WriteHalf};
-use tokio::net::TcpStream;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
+use tokio_util::codec::{FramedRead, FramedWrite};
+use uuid::Uuid;
 
-use crate::ignite_error::ChainResult;
+use crate::credentials::Credentials;
+use crate::ignite_error::{unwind_error, ChainResult, ErrorKind};
 use crate::ignite_error::{IgniteResult, LogResult};
-use crate::protocol::message::{HandshakeReq, HandshakeRsp, Response};
-use crate::protocol::{InStream, OutStream, Readable, Writable};
-use crate::protocol_version::{ProtocolVersion, VERSION_1_2_0};
+use crate::net::affinity::AffinityTopology;
+use crate::net::tls::{AsyncStream, BoxedStream};
+use crate::protocol::message::{CacheGetNamesReq, GetIdleTimeoutReq, HeartbeatReq};
+use crate::protocol::message::{HandshakeAccept, HandshakeReq, HandshakeRsp};
+use crate::protocol::message::{Request, Response, ResponseHeader};
+use crate::protocol::{InStream, OutStream, Writable};
+use crate::protocol::{RequestEncoder, ResponseDecoder};
+use crate::protocol_version::{has_feature, ProtocolFeature};
+use crate::protocol_version::{ProtocolVersion, VERSION_1_2_0, VERSION_1_4_0, VERSION_1_7_0};
 use crate::{ClientConfiguration, IgniteError};
 
 /// Versions supported by the client
-const SUPPORTED_VERSIONS: [ProtocolVersion; 1] = [VERSION_1_2_0];
+const SUPPORTED_VERSIONS: [ProtocolVersion; 3] = [VERSION_1_7_0, VERSION_1_4_0, VERSION_1_2_0];
 
-/// Represents a single channel to a node of the cluster.
+/// Payloads of the notifications, pushed by the server for the resource.
+/// The connection loss is reported as an error, after which no notifications come.
+pub type Notifications = mpsc::UnboundedReceiver<IgniteResult<BytesMut>>;
+
+/// Sender of the notifications to the listener.
+type NotificationSender = mpsc::UnboundedSender<IgniteResult<BytesMut>>;
+
+/// Request which is waiting for the response.
 #[derive(Debug)]
+struct PendingRequest {
+    sender: oneshot::Sender<IgniteResult<BytesMut>>,
+    // Listener of the notifications for the resource created by the request.
+    listener: Option<NotificationSender>,
+}
+
+/// Requests which are waiting for their responses, by request ID.
+type PendingRequests = HashMap<i64, PendingRequest>;
+
+/// Listeners of the notifications, by resource ID.
+type Listeners = HashMap<i64, NotificationSender>;
+
+/// State of the channel, shared with the task reading responses.
+#[derive(Debug, Default)]
+struct SharedState {
+    pending: std::sync::Mutex<PendingRequests>,
+    listeners: std::sync::Mutex<Listeners>,
+    broken: AtomicBool,
+}
+
+impl SharedState {
+    /// Get pending requests.
+    fn pending(&self) -> std::sync::MutexGuard<'_, PendingRequests> {
+        // Map is always left in a consistent state, so we do not care if it is poisoned.
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Get listeners of the notifications.
+    fn listeners(&self) -> std::sync::MutexGuard<'_, Listeners> {
+        self.listeners.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Mark channel as broken and fail all the pending requests.
+    /// Listeners are notified of the failure as well.
+    fn set_broken(&self, reason: &str) {
+        self.broken.store(true, Ordering::SeqCst);
+
+        let error = || IgniteError::new_with_kind(ErrorKind::Connection, reason);
+
+        let pending: Vec<_> = self.pending().drain().collect();
+        for (_, pending) in pending {
+            let _ = pending.sender.send(Err(error()));
+        }
+
+        let listeners: Vec<_> = self.listeners().drain().collect();
+        for (_, listener) in listeners {
+            let _ = listener.unbounded_send(Err(error()));
+        }
+    }
+
+    /// Check if the channel is broken.
+    fn is_broken(&self) -> bool {
+        self.broken.load(Ordering::SeqCst)
+    }
+}
+
+/// Represents a single channel to a node of the cluster.
 pub struct AsyncDataChannel {
-    read_end_mutex: Mutex<ReadHalf<TcpStream>>,
-    write_end_mutex: Mutex<WriteHalf<TcpStream>>,
+    addr: SocketAddr,
+    write_end_mutex: Mutex<FramedWrite<WriteHalf<BoxedStream>, RequestEncoder>>,
+    state: Arc<SharedState>,
+    req_id: AtomicI64,
+    reader_handle: AbortHandle,
     ver: ProtocolVersion,
+    node_id: Option<Uuid>,
+    topology: Arc<AffinityTopology>,
+    features: Vec<u8>,
+    heartbeat_interval: Option<Duration>,
+    last_sent: std::sync::Mutex<Instant>,
 }
 
 impl AsyncDataChannel {
-    /// Try create new data channel between host and the node with a given address.
-    pub async fn connect(addr: &SocketAddr, cfg: &ClientConfiguration) -> IgniteResult<Self> {
-        debug!("Trying to connect to host: {}", addr);
-
-        let conn_res = tokio::net::TcpStream::connect(&addr).await;
-        let conn =
-            conn_res.chain_error(format!("Can not establish connection to host {}", addr))?;
+    /// Try create new data channel over the connection to the node with a given address.
+    /// Connection can be either plain or secure.
+    ///
+    /// Changes of the affinity topology version, reported by the node, are passed to the topology.
+    pub async fn open<S: AsyncStream>(
+        conn: S,
+        addr: &SocketAddr,
+        cfg: &ClientConfiguration,
+        topology: Arc<AffinityTopology>,
+    ) -> IgniteResult<Self> {
+        let conn: BoxedStream = Box::new(conn);
+
+        let credentials = cfg.credentials()?;
 
         let (mut read_end, mut write_end) = tokio::io::split(conn);
-        let ver = Self::negotiate_version(
+        let (ver, accept) = Self::negotiate_version(
             &mut write_end,
             &mut read_end,
-            cfg.get_user(),
-            cfg.get_password(),
+            &credentials,
+            cfg.get_user_attributes(),
         )
         .await?;
 
-        let read_end_mutex = Mutex::new(read_end);
-        let write_end_mutex = Mutex::new(write_end);
+        let state = Arc::new(SharedState::default());
+
+        let (reader_handle, reader_reg) = AbortHandle::new_pair();
+        let reader = FramedRead::new(read_end, ResponseDecoder::new());
+        tokio::spawn(Abortable::new(
+            read_responses(reader, state.clone(), *addr, ver),
+            reader_reg,
+        ));
+
+        let write_end_mutex = Mutex::new(FramedWrite::new(write_end, RequestEncoder::new()));
 
-        Ok(Self {
-            read_end_mutex,
+        let mut channel = Self {
+            addr: *addr,
             write_end_mutex,
+            state,
+            req_id: AtomicI64::new(0),
+            reader_handle,
             ver,
-        })
+            node_id: accept.node_id(),
+            topology,
+            features: accept.features().to_vec(),
+            heartbeat_interval: cfg.get_heartbeat_interval(),
+            last_sent: std::sync::Mutex::new(Instant::now()),
+        };
+
+        // Heartbeats have to be sent often enough for the node not to close the connection.
+        if channel.supports(ProtocolFeature::Heartbeat) {
+            if let Some(idle_timeout) = channel.request(&GetIdleTimeoutReq::new()).await? {
+                channel.heartbeat_interval = channel
+                    .heartbeat_interval
+                    .map(|interval| interval.min(idle_timeout / 3));
+            }
+        }
+
+        Ok(channel)
+    }
+
+    /// Get ID of the node the channel is connected to.
+    /// Only known if the node supports partition awareness.
+    pub fn node_id(&self) -> Option<Uuid> {
+        self.node_id
+    }
+
+    /// Check if the feature was negotiated with the node.
+    pub fn supports(&self, feature: ProtocolFeature) -> bool {
+        has_feature(&self.features, feature)
+    }
+
+    /// Get address of the node the channel is connected to.
+    pub fn addr(&self) -> &SocketAddr {
+        &self.addr
+    }
+
+    /// Get number of requests waiting for response.
+    pub fn in_flight(&self) -> usize {
+        self.state.pending().len()
+    }
+
+    /// Check if the channel is broken and can not be used anymore.
+    pub fn is_broken(&self) -> bool {
+        self.state.is_broken()
+    }
+
+    /// Close the channel, failing all the pending requests.
+    pub fn close(&self, reason: &str) {
+        self.state.set_broken(reason);
+    }
+
+    /// Get interval between heartbeats, if they are enabled.
+    pub fn heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat_interval
+    }
+
+    /// Get time passed since the last request was sent.
+    pub fn idle_time(&self) -> Duration {
+        self.last_sent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .elapsed()
+    }
+
+    /// Send heartbeat, or a cheap request if the node does not support heartbeats.
+    pub async fn heartbeat(&self) -> IgniteResult<()> {
+        if self.supports(ProtocolFeature::Heartbeat) {
+            return self.request(&HeartbeatReq::new()).await;
+        }
+
+        match self.request(&CacheGetNamesReq::new()).await {
+            // Node is alive, even if it rejected the request.
+            Err(err) if err.kind() != ErrorKind::Connection => Ok(()),
+            res => res.map(|_| ()),
+        }
+    }
+
+    /// Send request and wait for the response.
+    ///
+    /// If the returned future is dropped before completion, e.g. on timeout,
+    /// the request stops waiting for the response.
+    ///
+    /// Fails right away if the request requires a feature the node does not support.
+    pub async fn request<R: Request>(&self, req: &R) -> IgniteResult<R::Response> {
+        self.send_request(req, None).await
+    }
+
+    /// Send request creating the resource on the server, e.g. continuous query,
+    /// and start listening to the notifications the server pushes for it.
+    ///
+    /// Payload of the response has to start with the ID of the resource.
+    /// The listener is registered before any other message is read from the
+    /// connection, so no notification can be missed.
+    pub async fn subscribe<R: Request>(
+        &self,
+        req: &R,
+    ) -> IgniteResult<(R::Response, Notifications)> {
+        let (listener, notifications) = mpsc::unbounded();
+        let rsp = self.send_request(req, Some(listener)).await?;
+
+        Ok((rsp, notifications))
+    }
+
+    /// Stop listening to the notifications for the resource.
+    pub fn unsubscribe(&self, resource_id: i64) {
+        self.state.listeners().remove(&resource_id);
+    }
+
+    /// Send request and wait for the response, registering the listener
+    /// of the notifications once the response is received.
+    async fn send_request<R: Request>(
+        &self,
+        req: &R,
+        listener: Option<NotificationSender>,
+    ) -> IgniteResult<R::Response> {
+        if let Some(feature) = R::FEATURE {
+            if !self.supports(feature) {
+                return Err(IgniteError::new_with_kind(
+                    ErrorKind::Unsupported,
+                    format!(
+                        "Operation {:?} is not supported by the node {}: feature {:?} is missing",
+                        R::TYPE,
+                        self.addr,
+                        feature
+                    ),
+                ));
+            }
+        }
+
+        if let Some(ver) = R::MIN_VERSION {
+            if self.ver < ver {
+                return Err(IgniteError::new_with_kind(
+                    ErrorKind::Unsupported,
+                    format!(
+                        "Operation {:?} is not supported by the node {}: version {} is required, {} is used",
+                        R::TYPE,
+                        self.addr,
+                        ver,
+                        self.ver
+                    ),
+                ));
+            }
+        }
+
+        let id = self.req_id.fetch_add(1, Ordering::Relaxed);
+
+        let (sender, receiver) = oneshot::channel();
+        self.state
+            .pending()
+            .insert(id, PendingRequest { sender, listener });
+
+        let _guard = PendingGuard {
+            state: &self.state,
+            id,
+        };
+
+        // Checking after registration, so the request can not be missed by set_broken().
+        if self.is_broken() {
+            return Err(self.lost_error());
+        }
+
+        *self.last_sent.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+
+        let data = pack_request(req, id, &self.ver);
+        let res = self
+            .write_end_mutex
+            .lock()
+            .await
+            .send(Bytes::from(data.into_vec()))
+            .await;
+
+        if let Err(err) = res {
+            self.state
+                .set_broken(&format!("Can not send request to host {}", self.addr));
+
+            return Err(IgniteError::new_with_kind_and_source(
+                ErrorKind::Connection,
+                format!("Can not send request to host {}", self.addr),
+                Box::new(err),
+            ));
+        }
+
+        let data = match receiver.await {
+            Ok(res) => res?,
+            Err(_) => return Err(self.lost_error()),
+        };
+
+        let stream = InStream::new(&data);
+        let header = ResponseHeader::read(&stream, &self.ver);
+
+        if let Some(ver) = header.affinity_version() {
+            self.topology.update(ver);
+        }
+
+        match header.into_response() {
+            Response::Accept(_) => req.read_response(&stream, &self.ver),
+            Response::Reject(rej) => Err(rej.into()),
+        }
+    }
+
+    /// Make error for the case when the connection is lost.
+    fn lost_error(&self) -> IgniteError {
+        IgniteError::new_with_kind(
+            ErrorKind::Connection,
+            format!("Connection to host {} is lost", self.addr),
+        )
     }
 
     /// Negotiate protocol version to use.
-    async fn negotiate_version(
-        write_end: &mut WriteHalf<TcpStream>,
-        read_end: &mut ReadHalf<TcpStream>,
-        user: &str,
-        pwd: &str,
-    ) -> IgniteResult<ProtocolVersion> {
+    async fn negotiate_version<W, R>(
+        write_end: &mut W,
+        read_end: &mut R,
+        credentials: &Credentials,
+        attributes: &HashMap<String, String>,
+    ) -> IgniteResult<(ProtocolVersion, HandshakeAccept)>
+    where
+        W: AsyncWrite + Unpin,
+        R: AsyncRead + Unpin,
+    {
         for ver in SUPPORTED_VERSIONS.iter() {
-            let res = Self::handshake(write_end, read_end, ver, user, pwd).await;
+            let res = Self::handshake(write_end, read_end, ver, credentials, attributes).await;
 
             match res {
+                // Credentials are rejected regardless of the version.
+                Err(err) if err.kind() == ErrorKind::Authentication => return Err(err),
                 Err(_) => {
                     res.log_error_w(format!("Can not perform handshake using version {:?}", ver))
                 }
-                Ok(()) => return Ok(*ver),
+                Ok(accept) => return Ok((*ver, accept)),
             };
         }
 
@@ -74,26 +390,29 @@ impl AsyncDataChannel {
         ))
     }
 
-    async fn handshake(
-        write_end: &mut WriteHalf<TcpStream>,
-        read_end: &mut ReadHalf<TcpStream>,
+    async fn handshake<W, R>(
+        write_end: &mut W,
+        read_end: &mut R,
         ver: &ProtocolVersion,
-        user: &str,
-        pwd: &str,
-    ) -> IgniteResult<()> {
-        Self::handshake_request(write_end, ver, user, pwd).await?;
-        Self::handshake_response(read_end).await?;
-        Ok(())
+        credentials: &Credentials,
+        attributes: &HashMap<String, String>,
+    ) -> IgniteResult<HandshakeAccept>
+    where
+        W: AsyncWrite + Unpin,
+        R: AsyncRead + Unpin,
+    {
+        Self::handshake_request(write_end, ver, credentials, attributes).await?;
+        Self::handshake_response(read_end, ver).await
     }
 
     /// Send handshake request using a connection.
-    async fn handshake_request(
-        write_end: &mut WriteHalf<TcpStream>,
+    async fn handshake_request<W: AsyncWrite + Unpin>(
+        write_end: &mut W,
         ver: &ProtocolVersion,
-        user: &str,
-        pwd: &str,
+        credentials: &Credentials,
+        attributes: &HashMap<String, String>,
     ) -> IgniteResult<()> {
-        let req = HandshakeReq::new(*ver, user, pwd);
+        let req = HandshakeReq::new(*ver, credentials.user(), credentials.password(), attributes);
         let data = pack_writable(&req);
 
         write_end
@@ -105,18 +424,28 @@ impl AsyncDataChannel {
     }
 
     /// Receive handshake response from a connection.
-    async fn handshake_response(read_end: &mut ReadHalf<TcpStream>) -> IgniteResult<()> {
+    async fn handshake_response<R: AsyncRead + Unpin>(
+        read_end: &mut R,
+        ver: &ProtocolVersion,
+    ) -> IgniteResult<HandshakeAccept> {
         let data = Self::receive_rsp_raw(read_end).await?;
-        let resp = unpack_readable::<HandshakeRsp>(&data);
+        let resp = HandshakeRsp::read(&InStream::new(&data), ver);
 
         match resp {
-            Response::Accept(_) => Ok(()),
-            Response::Reject(rej) => Err(IgniteError::new(format!("Handshake failed with error: {}", rej.get_error()))),
+            Response::Accept(accept) => Ok(accept),
+            Response::Reject(rej) if rej.is_auth_failure() => Err(IgniteError::new_with_kind(
+                ErrorKind::Authentication,
+                format!("Authentication failed: {}", rej.get_error()),
+            )),
+            Response::Reject(rej) => Err(IgniteError::new(format!(
+                "Handshake failed with error: {}",
+                rej.get_error()
+            ))),
         }
     }
 
     /// Receive response in a raw byte array form
-    async fn receive_rsp_raw(read_end: &mut ReadHalf<TcpStream>) -> IgniteResult<Box<[u8]>> {
+    async fn receive_rsp_raw<R: AsyncRead + Unpin>(read_end: &mut R) -> IgniteResult<Box<[u8]>> {
         use crate::protocol::utils;
 
         let mut len_buf = [0u8; 4];
@@ -139,6 +468,150 @@ impl AsyncDataChannel {
     }
 }
 
+/// Removes the request from the pending ones once it is done or cancelled.
+struct PendingGuard<'a> {
+    state: &'a SharedState,
+    id: i64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.state.pending().remove(&self.id);
+    }
+}
+
+impl fmt::Debug for AsyncDataChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncDataChannel")
+            .field("addr", &self.addr)
+            .field("ver", &self.ver)
+            .field("node_id", &self.node_id)
+            .field("broken", &self.is_broken())
+            .finish()
+    }
+}
+
+impl Drop for AsyncDataChannel {
+    fn drop(&mut self) {
+        self.reader_handle.abort();
+        self.state.set_broken("Channel is closed");
+    }
+}
+
+/// Read responses from the connection and pass them to the requests waiting for them.
+/// Marks the channel as broken once the connection is lost.
+async fn read_responses(
+    mut reader: FramedRead<ReadHalf<BoxedStream>, ResponseDecoder>,
+    state: Arc<SharedState>,
+    addr: SocketAddr,
+    ver: ProtocolVersion,
+) {
+    while let Some(msg) = reader.next().await {
+        let data = match msg {
+            Ok(data) => data,
+            Err(err) => {
+                warn!(
+                    "Error while reading response from host {}: {}",
+                    addr,
+                    unwind_error(&err)
+                );
+                break;
+            }
+        };
+
+        if data.len() < 8 {
+            warn!("Malformed response received from host {}", addr);
+            break;
+        }
+
+        let req_id = ResponseHeader::peek_req_id(&data);
+
+        if ResponseHeader::peek_notification(&data, &ver) {
+            notify(&state, req_id, data, &ver, addr);
+            continue;
+        }
+
+        let pending = state.pending().remove(&req_id);
+
+        match pending {
+            Some(pending) => {
+                if let Some(listener) = pending.listener {
+                    listen(&state, &data, &ver, listener);
+                }
+
+                let _ = pending.sender.send(Ok(data));
+            }
+            None => debug!(
+                "Response for unknown request {} received from host {}",
+                req_id, addr
+            ),
+        }
+    }
+
+    info!("Connection to host {} is lost", addr);
+    state.set_broken(&format!("Connection to host {} is lost", addr));
+}
+
+/// Register listener of the notifications for the resource, created by the request.
+/// Nothing is registered if the request has failed.
+fn listen(state: &SharedState, data: &[u8], ver: &ProtocolVersion, listener: NotificationSender) {
+    let stream = InStream::new(data);
+
+    if let Response::Accept(_) = ResponseHeader::read(&stream, ver).into_response() {
+        let resource_id = stream.read_i64();
+        state.listeners().insert(resource_id, listener);
+
+        // Checking after registration, so the listener can not be missed by set_broken().
+        if state.is_broken() {
+            if let Some(listener) = state.listeners().remove(&resource_id) {
+                let _ = listener.unbounded_send(Err(IgniteError::new_with_kind(
+                    ErrorKind::Connection,
+                    "Channel is closed",
+                )));
+            }
+        }
+    }
+}
+
+/// Pass payload of the notification to the listener of the resource.
+fn notify(
+    state: &SharedState,
+    resource_id: i64,
+    mut data: BytesMut,
+    ver: &ProtocolVersion,
+    addr: SocketAddr,
+) {
+    let mut listeners = state.listeners();
+
+    let listener = match listeners.get(&resource_id) {
+        Some(listener) => listener,
+        None => {
+            debug!(
+                "Notification for unknown resource {} received from host {}",
+                resource_id, addr
+            );
+            return;
+        }
+    };
+
+    let (rsp, payload_pos) = {
+        let stream = InStream::new(&data);
+        let header = ResponseHeader::read(&stream, ver);
+
+        (header.into_response(), stream.position())
+    };
+
+    let msg = match rsp {
+        Response::Accept(_) => Ok(data.split_off(payload_pos)),
+        Response::Reject(rej) => Err(rej.into()),
+    };
+
+    // Listener is gone, so the notifications are of no interest anymore.
+    if listener.unbounded_send(msg).is_err() {
+        listeners.remove(&resource_id);
+    }
+}
+
 /// Pack any Writable value into boxed slice.
 fn pack_writable(req: &dyn Writable) -> Box<[u8]> {
     let stream = OutStream::new();
@@ -153,29 +626,16 @@ fn pack_writable(req: &dyn Writable) -> Box<[u8]> {
 }
 
 /// Pack request with ID into boxed slice.
-#[allow(dead_code)]
-fn pack_request(req: &dyn Writable, id: i32) -> Box<[u8]> {
+fn pack_request<R: Request>(req: &R, id: i64, ver: &ProtocolVersion) -> Box<[u8]> {
     let stream = OutStream::new();
 
     let len = stream.reserve_len();
 
-    stream.write_i32(id);
-    req.write(&stream);
+    stream.write_i16(R::TYPE as i16);
+    stream.write_i64(id);
+    req.write_payload(&stream, ver);
 
     len.set();
 
     stream.into_memory()
 }
-
-/// Unpack Readable value from slice of bytes.
-fn unpack_readable<T: Readable<Item = T>>(data: &[u8]) -> T {
-    let stream = InStream::new(data);
-
-    T::read(&stream)
-}
-
-// /// Encode and send message to specified stream.
-// /// Returns request ID.
-// async fn send_message<T>(out: &dyn AsyncWrite, req: T) -> IgniteResult<i32> {
-//
-// }
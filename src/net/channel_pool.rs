@@ -0,0 +1,614 @@
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use tokio::sync::Mutex;
+use tokio::time;
+use uuid::Uuid;
+
+use crate::client_configuration::{ClientConfiguration, LoadBalancing};
+use crate::ignite_error::{unwind_error, ErrorKind, IgniteError, IgniteResult};
+use crate::net::affinity::AffinityTopology;
+use crate::net::async_data_channel::AsyncDataChannel;
+use crate::net::tls::StreamConnector;
+use crate::protocol::message::ClusterGroupGetNodeEndpointsReq;
+use crate::protocol_version::ProtocolFeature;
+
+/// Everything needed to open new channels.
+#[derive(Debug)]
+struct ChannelFactory {
+    cfg: Arc<ClientConfiguration>,
+    topology: Arc<AffinityTopology>,
+    connector: StreamConnector,
+}
+
+impl ChannelFactory {
+    /// Open channel to the node with the given address.
+    async fn open(&self, addr: &SocketAddr, host: &str) -> IgniteResult<AsyncDataChannel> {
+        debug!("Trying to connect to host: {}", addr);
+
+        let connect = self.connector.connect(addr, host);
+        let conn = time::timeout(self.cfg.get_connect_timeout(), connect)
+            .await
+            .map_err(|_| {
+                IgniteError::new_with_kind(
+                    ErrorKind::Timeout,
+                    format!("Connection to host {} timed out", addr),
+                )
+            })??;
+
+        let open = AsyncDataChannel::open(conn, addr, &self.cfg, self.topology.clone());
+        time::timeout(self.cfg.get_handshake_timeout(), open)
+            .await
+            .map_err(|_| {
+                IgniteError::new_with_kind(
+                    ErrorKind::Timeout,
+                    format!("Handshake with host {} timed out", addr),
+                )
+            })?
+    }
+}
+
+/// State of the channel holder.
+#[derive(Debug, Default)]
+struct HolderState {
+    channel: Option<Arc<AsyncDataChannel>>,
+    failures: u32,
+    failed_at: Option<Instant>,
+}
+
+/// Holder of the channel to a single node, which is either reached through
+/// a resolved address of an endpoint, or was discovered in the cluster.
+#[derive(Debug)]
+struct ChannelHolder {
+    host: String,
+    addrs: Vec<SocketAddr>,
+    node_id: Option<Uuid>,
+    state: std::sync::Mutex<HolderState>,
+    connecting: Mutex<()>,
+}
+
+impl ChannelHolder {
+    /// Make new instance. Addresses are tried in order when connecting.
+    /// Node ID is only set for the discovered nodes.
+    fn new(host: String, addrs: Vec<SocketAddr>, node_id: Option<Uuid>) -> Self {
+        Self {
+            host,
+            addrs,
+            node_id,
+            state: std::sync::Mutex::new(HolderState::default()),
+            connecting: Mutex::new(()),
+        }
+    }
+
+    /// Get the first address of the holder.
+    fn addr(&self) -> SocketAddr {
+        self.addrs[0]
+    }
+
+    /// Get state of the holder.
+    fn state(&self) -> std::sync::MutexGuard<'_, HolderState> {
+        // State is always left consistent, so we do not care if it is poisoned.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Get channel if it is established and not broken.
+    /// Broken channel is released and counted as a failure.
+    fn healthy_channel(&self) -> Option<Arc<AsyncDataChannel>> {
+        let mut state = self.state();
+
+        match state.channel.as_ref() {
+            Some(channel) if !channel.is_broken() => Some(channel.clone()),
+            Some(channel) => {
+                info!("Connection to {} is broken", channel.addr());
+
+                state.channel = None;
+                state.failures += 1;
+                state.failed_at = Some(Instant::now());
+
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Get number of requests waiting for response.
+    /// Zero if the channel is not established.
+    fn in_flight(&self) -> usize {
+        self.state()
+            .channel
+            .as_ref()
+            .map_or(0, |channel| channel.in_flight())
+    }
+
+    /// Check if the holder should not try to connect yet because of the recent failures.
+    fn is_backing_off(&self, cfg: &ClientConfiguration) -> bool {
+        let state = self.state();
+
+        match state.failed_at {
+            Some(failed_at) => failed_at.elapsed() < cfg.reconnect_delay(state.failures),
+            None => false,
+        }
+    }
+
+    /// Get healthy channel, connecting if needed.
+    ///
+    /// If not forced, connection is not attempted while backing off after failures.
+    async fn get_or_connect(
+        &self,
+        factory: &ChannelFactory,
+        force: bool,
+    ) -> IgniteResult<Arc<AsyncDataChannel>> {
+        if let Some(channel) = self.healthy_channel() {
+            return Ok(channel);
+        }
+
+        if !force && self.is_backing_off(&factory.cfg) {
+            return Err(IgniteError::new_with_kind(
+                ErrorKind::Connection,
+                format!("Backing off connecting to host {}", self.addr()),
+            ));
+        }
+
+        let _guard = self.connecting.lock().await;
+
+        // Could have been connected while we were waiting for the lock.
+        if let Some(channel) = self.healthy_channel() {
+            return Ok(channel);
+        }
+
+        let res = self.connect(factory).await;
+        let mut state = self.state();
+
+        match res {
+            Ok(channel) => {
+                let channel = Arc::new(channel);
+
+                state.channel = Some(channel.clone());
+                state.failures = 0;
+                state.failed_at = None;
+
+                Ok(channel)
+            }
+            Err(err) => {
+                state.failures += 1;
+                state.failed_at = Some(Instant::now());
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Send heartbeat over the channel, if it was idle for too long.
+    /// Channel, which does not respond in time, is closed and reconnected.
+    ///
+    /// Returns the delay until the next heartbeat is due, if the channel is established.
+    async fn heartbeat(&self, factory: &ChannelFactory) -> Option<Duration> {
+        let channel = self.healthy_channel()?;
+        let interval = channel.heartbeat_interval()?;
+
+        let idle = channel.idle_time();
+        if idle < interval {
+            return Some(interval - idle);
+        }
+
+        let res = time::timeout(interval, channel.heartbeat()).await;
+        if let Ok(Ok(())) = res {
+            return Some(interval);
+        }
+
+        warn!("Heartbeat to host {} failed, reconnecting", channel.addr());
+        channel.close(&format!("Heartbeat to host {} failed", channel.addr()));
+
+        self.get_or_connect(factory, true)
+            .await
+            .ok()
+            .and_then(|channel| channel.heartbeat_interval())
+    }
+
+    /// Try to connect to any of the holder addresses.
+    ///
+    /// Authentication failure is returned right away, as other addresses
+    /// belong to the same cluster and are going to reject the credentials as well.
+    async fn connect(&self, factory: &ChannelFactory) -> IgniteResult<AsyncDataChannel> {
+        let mut last_err = None;
+        for addr in &self.addrs {
+            let res = factory.open(addr, &self.host).await;
+
+            match res {
+                Ok(channel) => return Ok(channel),
+                Err(err) if is_auth_error(&err) => return Err(err),
+                Err(err) => {
+                    warn!(
+                        "Can not connect to the host {}: {}",
+                        addr,
+                        unwind_error(&err)
+                    );
+                    last_err = Some(err);
+                }
+            };
+        }
+
+        Err(last_err.unwrap_or_else(no_connection_error))
+    }
+}
+
+/// Pool of channels to all the known nodes of the cluster.
+#[derive(Debug)]
+pub struct ChannelPool {
+    cfg: Arc<ClientConfiguration>,
+    factory: ChannelFactory,
+    holders: RwLock<Vec<Arc<ChannelHolder>>>,
+    next: AtomicUsize,
+}
+
+impl ChannelPool {
+    /// Make new instance. Endpoints are not resolved and no connections are established.
+    /// Fails if connections can not be set up with the configuration, e.g. TLS files can not be read.
+    ///
+    /// Channels report changes of the affinity topology to the given topology.
+    pub fn new(
+        cfg: Arc<ClientConfiguration>,
+        topology: Arc<AffinityTopology>,
+    ) -> IgniteResult<Self> {
+        let connector = StreamConnector::new(&cfg)?;
+
+        Ok(Self {
+            factory: ChannelFactory {
+                cfg: cfg.clone(),
+                topology,
+                connector,
+            },
+            cfg,
+            holders: RwLock::new(Vec::new()),
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Discover and resolve endpoints and establish initial connections.
+    ///
+    /// Connects to every address if configured to do it eagerly, or to a
+    /// single random one otherwise. Fails if no connection could be established.
+    pub async fn connect(&self) -> IgniteResult<()> {
+        self.update_holders().await;
+
+        if !self.cfg.get_connect_eagerly() {
+            self.connect_any().await?;
+            self.discover_nodes().await;
+
+            return Ok(());
+        }
+
+        let connected = join_all(
+            self.holders()
+                .iter()
+                .map(|holder| holder.get_or_connect(&self.factory, true)),
+        )
+        .await;
+
+        if connected.iter().any(Result::is_ok) {
+            let added = self.discover_nodes().await;
+            self.connect_eagerly(&added).await;
+
+            return Ok(());
+        }
+
+        Err(connected
+            .into_iter()
+            .filter_map(Result::err)
+            .find(is_auth_error)
+            .unwrap_or_else(no_connection_error))
+    }
+
+    /// Get channel to send the next request with, according to the load balancing policy.
+    ///
+    /// Channels which are not established yet, or were broken, are connected
+    /// when chosen, unless backing off after recent failures.
+    pub async fn channel(&self) -> IgniteResult<Arc<AsyncDataChannel>> {
+        let holders = self.holders();
+
+        let len = holders.len();
+        if len == 0 {
+            return self.connect_any().await;
+        }
+
+        let policy = self.cfg.get_load_balancing();
+        let start = match policy {
+            LoadBalancing::Random => thread_rng().gen_range(0, len),
+            _ => self.next.fetch_add(1, Ordering::Relaxed) % len,
+        };
+
+        let mut order: Vec<&ChannelHolder> = (0..len)
+            .map(|i| holders[(start + i) % len].as_ref())
+            .collect();
+
+        if policy == LoadBalancing::LeastInFlight {
+            // Sort is stable, so holders with equal load are still taken in turn.
+            order.sort_by_key(|holder| holder.in_flight());
+        }
+
+        for holder in order {
+            match holder.get_or_connect(&self.factory, false).await {
+                Ok(channel) => return Ok(channel),
+                Err(err) if is_auth_error(&err) => return Err(err),
+                Err(_) => {}
+            }
+        }
+
+        self.connect_any().await
+    }
+
+    /// Send heartbeats over the idle channels, reconnecting the ones not responding.
+    ///
+    /// Returns the delay until the next heartbeat is due, if any channel is established.
+    pub async fn heartbeat(&self) -> Option<Duration> {
+        join_all(
+            self.holders()
+                .iter()
+                .map(|holder| holder.heartbeat(&self.factory)),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    /// Get established healthy channel to the node with the given ID.
+    pub fn node_channel(&self, node_id: &Uuid) -> Option<Arc<AsyncDataChannel>> {
+        self.holders()
+            .iter()
+            .filter_map(|holder| holder.healthy_channel())
+            .find(|channel| channel.node_id().as_ref() == Some(node_id))
+    }
+
+    /// Discover and resolve endpoints and the cluster nodes again, and connect
+    /// to the new ones, if configured to connect eagerly.
+    pub async fn refresh(&self) {
+        let mut added = self.update_holders().await;
+        added.append(&mut self.discover_nodes().await);
+
+        self.connect_eagerly(&added).await;
+    }
+
+    /// Connect the holders, if configured to connect eagerly.
+    async fn connect_eagerly(&self, holders: &[Arc<ChannelHolder>]) {
+        if self.cfg.get_connect_eagerly() {
+            join_all(
+                holders
+                    .iter()
+                    .map(|holder| holder.get_or_connect(&self.factory, true)),
+            )
+            .await;
+        }
+    }
+
+    /// Discover server nodes of the cluster, and add holders for the ones the
+    /// client is not connected to. Holders of the nodes, which left the cluster,
+    /// are removed. Returns the added holders.
+    ///
+    /// Does nothing if disabled or not supported by the connected nodes.
+    async fn discover_nodes(&self) -> Vec<Arc<ChannelHolder>> {
+        if !self.cfg.get_cluster_discovery() {
+            return Vec::new();
+        }
+
+        let channel = self
+            .holders()
+            .iter()
+            .filter_map(|holder| holder.healthy_channel())
+            .find(|channel| channel.supports(ProtocolFeature::ClusterGroupGetNodesEndpoints));
+
+        let channel = match channel {
+            Some(channel) => channel,
+            None => return Vec::new(),
+        };
+
+        let nodes = match channel
+            .request(&ClusterGroupGetNodeEndpointsReq::new())
+            .await
+        {
+            Ok(nodes) => nodes,
+            Err(err) => {
+                warn!(
+                    "Can not discover nodes of the cluster: {}",
+                    unwind_error(&err)
+                );
+                return Vec::new();
+            }
+        };
+
+        // Loopback addresses of the nodes are only reachable from the same host.
+        let loopback = channel.addr().ip().is_loopback();
+
+        let mut holders = self.holders.write().unwrap_or_else(|e| e.into_inner());
+
+        holders.retain(|holder| {
+            holder
+                .node_id
+                .is_none_or(|id| nodes.iter().any(|node| node.id() == id))
+        });
+
+        let connected: HashSet<Uuid> = holders
+            .iter()
+            .filter_map(|holder| {
+                holder
+                    .node_id
+                    .or_else(|| holder.healthy_channel().and_then(|c| c.node_id()))
+            })
+            .collect();
+
+        let known: HashSet<SocketAddr> = holders
+            .iter()
+            .flat_map(|holder| holder.addrs.iter().copied())
+            .collect();
+
+        let mut added = Vec::new();
+        for node in nodes {
+            if connected.contains(&node.id()) {
+                continue;
+            }
+
+            let addrs: Vec<SocketAddr> = node
+                .socket_addrs()
+                .into_iter()
+                .filter(|addr| loopback || !addr.ip().is_loopback())
+                .collect();
+
+            // Node could be reached through an endpoint, which is not connected yet.
+            if addrs.is_empty() || addrs.iter().any(|addr| known.contains(addr)) {
+                continue;
+            }
+
+            debug!("Node {} is discovered at {:?}", node.id(), addrs);
+            added.push(Arc::new(ChannelHolder::new(
+                addrs[0].ip().to_string(),
+                addrs,
+                Some(node.id()),
+            )));
+        }
+
+        holders.extend(added.iter().cloned());
+
+        added
+    }
+
+    /// Get all the channel holders.
+    fn holders(&self) -> Vec<Arc<ChannelHolder>> {
+        // Holders are always left consistent, so we do not care if the lock is poisoned.
+        self.holders
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Discover and resolve endpoints and update the holders accordingly.
+    ///
+    /// Holders are added for the new addresses and removed for the ones not
+    /// discovered or resolved anymore. Addresses of the hosts failed to resolve
+    /// are kept, as well as all the addresses if the discovery fails.
+    /// Returns the added holders.
+    async fn update_holders(&self) -> Vec<Arc<ChannelHolder>> {
+        let resolver = self.cfg.get_resolver();
+        let end_points = match self.cfg.current_endpoints().await {
+            Ok(end_points) => end_points,
+            Err(err) => {
+                warn!("Can not discover endpoints: {}", unwind_error(&err));
+                return Vec::new();
+            }
+        };
+
+        let resolved = join_all(
+            end_points
+                .iter()
+                .map(|end_point| end_point.resolve(resolver)),
+        )
+        .await;
+
+        let mut failed = Vec::new();
+        let mut addrs = Vec::new();
+        for (end_point, res) in end_points.iter().zip(resolved) {
+            match res {
+                Ok(resolved) => {
+                    let iter = if self.cfg.get_shuffle_addresses() {
+                        resolved.into_shuffled_iter()
+                    } else {
+                        resolved.into_iter()
+                    };
+
+                    addrs.extend(iter.map(|addr| (end_point.host(), addr)));
+                }
+                Err(err) => {
+                    warn!(
+                        "Can not resolve host {}: {}",
+                        end_point.host(),
+                        unwind_error(&err)
+                    );
+                    failed.push(end_point.host());
+                }
+            }
+        }
+
+        let mut holders = self.holders.write().unwrap_or_else(|e| e.into_inner());
+
+        let mut known: HashSet<SocketAddr> = addrs.iter().map(|(_, addr)| *addr).collect();
+        // Discovered nodes are managed by the cluster discovery.
+        holders.retain(|holder| {
+            holder.node_id.is_some()
+                || known.contains(&holder.addr())
+                || failed.contains(&holder.host.as_str())
+        });
+
+        // Several endpoints can be resolved into the same address.
+        known = holders
+            .iter()
+            .flat_map(|holder| holder.addrs.iter().copied())
+            .collect();
+
+        let mut added = Vec::new();
+        for (host, addr) in addrs {
+            if known.insert(addr) {
+                debug!("Address {} of host {} is found", addr, host);
+                added.push(Arc::new(ChannelHolder::new(
+                    host.to_owned(),
+                    vec![addr],
+                    None,
+                )));
+            }
+        }
+
+        holders.extend(added.iter().cloned());
+
+        added
+    }
+
+    /// Connect to any node, ignoring backoff.
+    /// Holders which did not fail recently are tried first.
+    ///
+    /// Endpoints are resolved again if no address is known.
+    async fn connect_any(&self) -> IgniteResult<Arc<AsyncDataChannel>> {
+        let mut holders = self.holders();
+        if holders.is_empty() {
+            self.update_holders().await;
+            holders = self.holders();
+        }
+
+        let mut order: Vec<&ChannelHolder> = holders.iter().map(Arc::as_ref).collect();
+
+        order[..].shuffle(&mut thread_rng());
+        order.sort_by_key(|holder| holder.is_backing_off(&self.cfg));
+
+        debug!(
+            "Connecting to any of the hosts: {:?}",
+            order.iter().map(|h| h.addr()).collect::<Vec<_>>()
+        );
+
+        for holder in order {
+            match holder.get_or_connect(&self.factory, true).await {
+                Ok(channel) => return Ok(channel),
+                Err(err) if is_auth_error(&err) => return Err(err),
+                Err(_) => {}
+            }
+        }
+
+        Err(no_connection_error())
+    }
+}
+
+/// Check if the connection failed because the credentials were rejected.
+/// Such errors are reported to the user, instead of trying other nodes.
+fn is_auth_error(err: &IgniteError) -> bool {
+    err.kind() == ErrorKind::Authentication
+}
+
+/// Make error for the case when no connection can be established.
+fn no_connection_error() -> IgniteError {
+    IgniteError::new_with_kind(
+        ErrorKind::Connection,
+        "Can not connect to any host. See logs for details",
+    )
+}
@@ -1,8 +1,14 @@
+use std::cmp;
 use std::convert::Into;
 use std::iter::{IntoIterator, Iterator};
-use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
 
 use crate::ignite_error::{ChainResult, IgniteError, IgniteResult};
+use crate::resolver::Resolver;
 
 pub const DEFAULT_PORT: u16 = 10800;
 
@@ -29,18 +35,31 @@ impl EndPoint {
         self.host.as_ref()
     }
 
+    /// Get all the ports of the endpoint.
+    pub fn ports(&self) -> RangeInclusive<u16> {
+        self.port_begin..=cmp::max(self.port_begin, self.port_end)
+    }
+
+    /// Make endpoint pointing to the same host with a single port.
+    pub fn with_port(&self, port: u16) -> Self {
+        EndPoint::new(self.host.as_str(), port, 0)
+    }
+
     /// Convert from string.
     /// The format is `"<host>[:<port>[..<port>]][,...]"`.
+    ///
+    /// IPv6 address has to be enclosed in brackets to be followed by a port,
+    /// e.g. `"[::1]:10800..10802"`. Bare IPv6 address uses the default port.
     pub fn from_string<'a, S: Into<&'a str>>(sadr: S) -> IgniteResult<Self> {
-        let mut iter = sadr.into().trim().split(':');
-
-        let host = match iter.next() {
-            Some(h) => h,
-            None => {
-                return Err(IgniteError::new(
-                    "Parsing error: Host can not be an empty string",
-                ))
-            }
+        let sadr = sadr.into().trim();
+
+        let (host, range) = if sadr.starts_with('[') {
+            split_bracketed(sadr)?
+        } else if sadr.matches(':').count() > 1 {
+            (parse_ipv6(sadr)?, None)
+        } else {
+            let mut iter = sadr.splitn(2, ':');
+            (iter.next().unwrap_or_default(), iter.next())
         };
 
         if host.is_empty() {
@@ -49,17 +68,11 @@ impl EndPoint {
             ));
         }
 
-        let range = match iter.next() {
+        let range = match range {
             Some(r) => r,
             None => return Ok(EndPoint::new(host, DEFAULT_PORT, 0)),
         };
 
-        if iter.next().is_some() {
-            return Err(IgniteError::new(
-                "Parsing error: Unexpected number of semicolons ':' in endpoint",
-            ));
-        };
-
         let mut range_iter = range.trim().split("..");
 
         let port_begin_s = match range_iter.next() {
@@ -104,13 +117,16 @@ impl EndPoint {
     }
 
     /// Resolve host IPs
-    pub fn resolve(&self) -> IgniteResult<ResolvedEndPoint> {
-        let addr_tuple = (self.host.as_str(), self.port_begin);
-        let iter = addr_tuple
-            .to_socket_addrs()
-            .chain_error(format!("Failed to resolve host address: {}", self.host))?;
+    pub async fn resolve(&self, resolver: &dyn Resolver) -> IgniteResult<ResolvedEndPoint> {
+        let ips = resolver.resolve(&self.host).await?;
+
+        if ips.is_empty() {
+            return Err(IgniteError::new(format!(
+                "Host address is not found: {}",
+                self.host
+            )));
+        }
 
-        let ips = iter.map(|addr| addr.ip()).collect();
         Ok(ResolvedEndPoint {
             ips,
             port_begin: self.port_begin,
@@ -119,6 +135,49 @@ impl EndPoint {
     }
 }
 
+/// Split endpoint in the bracket notation, e.g. `"[::1]:42"`, into IPv6 address and port range.
+fn split_bracketed(sadr: &str) -> IgniteResult<(&str, Option<&str>)> {
+    let close = match sadr.find(']') {
+        Some(idx) => idx,
+        None => {
+            return Err(IgniteError::new(
+                "Parsing error: Closing bracket ']' is missing in endpoint",
+            ))
+        }
+    };
+
+    let host = parse_ipv6(&sadr[1..close])?;
+    let rest = &sadr[close + 1..];
+
+    if rest.is_empty() {
+        return Ok((host, None));
+    }
+
+    if !rest.starts_with(':') {
+        return Err(IgniteError::new(
+            "Parsing error: Closing bracket ']' can only be followed by a port in endpoint",
+        ));
+    }
+
+    Ok((host, Some(&rest[1..])))
+}
+
+/// Make sure the host is a valid IPv6 address.
+fn parse_ipv6(host: &str) -> IgniteResult<&str> {
+    host.parse::<Ipv6Addr>().map_err(|err| {
+        IgniteError::new_with_source(
+            format!(
+                "Parsing error: {} is not a valid IPv6 address. \
+                 IPv6 address followed by a port has to be enclosed in brackets, e.g. [::1]:10800",
+                host
+            ),
+            Box::new(err),
+        )
+    })?;
+
+    Ok(host)
+}
+
 /// Endpoint, pointing to a single host with a possible range of TCP ports.
 #[derive(Debug)]
 pub struct ResolvedEndPoint {
@@ -127,23 +186,46 @@ pub struct ResolvedEndPoint {
     port_end: u16,
 }
 
-/// Iterates over all possible addresses for the endpoint
+impl ResolvedEndPoint {
+    /// Iterate over all the addresses in a random order.
+    pub fn into_shuffled_iter(self) -> EndPointIterator {
+        let mut iter = EndPointIterator::new(self);
+        let mut order: Vec<usize> = (0..iter.len).collect();
+
+        order.shuffle(&mut thread_rng());
+        iter.order = Some(order);
+
+        iter
+    }
+}
+
+/// Iterates over all possible addresses for the endpoint.
+///
+/// Every address of the host is returned for the first port, then for the
+/// next one and so on, unless the order is shuffled.
 #[derive(Debug)]
 pub struct EndPointIterator {
     ips: Vec<IpAddr>,
-    ip_idx: usize,
-    port: u16,
-    end_port: u16,
+    port_begin: u16,
+    order: Option<Vec<usize>>,
+    idx: usize,
+    len: usize,
 }
 
 impl EndPointIterator {
     /// Create new instance
     fn new(end_point: ResolvedEndPoint) -> Self {
+        // Zero end of the range means a single port.
+        let ports = usize::from(cmp::max(end_point.port_begin, end_point.port_end))
+            - usize::from(end_point.port_begin)
+            + 1;
+
         EndPointIterator {
+            len: end_point.ips.len() * ports,
             ips: end_point.ips,
-            ip_idx: 0,
-            port: end_point.port_begin,
-            end_port: end_point.port_end,
+            port_begin: end_point.port_begin,
+            order: None,
+            idx: 0,
         }
     }
 }
@@ -152,21 +234,30 @@ impl Iterator for EndPointIterator {
     type Item = SocketAddr;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ip_idx < self.ips.len() {
-            self.ip_idx += 1;
-            return Some(SocketAddr::new(self.ips[self.ip_idx - 1], self.port));
+        if self.idx >= self.len {
+            return None;
         }
 
-        if self.port < self.end_port {
-            self.port += 1;
-            self.ip_idx = 0;
-            return Some(SocketAddr::new(self.ips[self.ip_idx], self.port));
-        }
+        let pos = match &self.order {
+            Some(order) => order[self.idx],
+            None => self.idx,
+        };
+        self.idx += 1;
 
-        None
+        let ip = self.ips[pos % self.ips.len()];
+        let port = self.port_begin + (pos / self.ips.len()) as u16;
+
+        Some(SocketAddr::new(ip, port))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.len - self.idx;
+        (left, Some(left))
     }
 }
 
+impl ExactSizeIterator for EndPointIterator {}
+
 impl IntoIterator for ResolvedEndPoint {
     type Item = SocketAddr;
     type IntoIter = EndPointIterator;
@@ -176,6 +267,19 @@ impl IntoIterator for ResolvedEndPoint {
     }
 }
 
+#[test]
+fn end_point_ports() {
+    let single = EndPoint::from_string("127.0.0.1:42").unwrap();
+    assert_eq!(single.ports(), 42..=42);
+
+    let default = EndPoint::from_string("127.0.0.1").unwrap();
+    assert_eq!(default.ports(), DEFAULT_PORT..=DEFAULT_PORT);
+
+    let range = EndPoint::from_string("127.0.0.1:42..44").unwrap();
+    assert_eq!(range.ports(), 42..=44);
+    assert_eq!(range.with_port(43), EndPoint::from_string("127.0.0.1:43").unwrap());
+}
+
 #[test]
 fn end_point_from_string() {
     EndPoint::from_string("127.0.0.1").unwrap();
@@ -235,3 +339,109 @@ fn end_point_from_string() {
     EndPoint::from_string("example.com:12234546..42341245436").unwrap_err();
     EndPoint::from_string("example.com:1..47293875987").unwrap_err();
 }
+
+#[test]
+fn end_point_from_string_ipv6() {
+    let bracketed = EndPoint::from_string("[::1]:42").unwrap();
+    assert_eq!(bracketed.host(), "::1");
+    assert_eq!(bracketed.ports(), 42..=42);
+
+    let range = EndPoint::from_string("[fe80::1]:42..44").unwrap();
+    assert_eq!(range.host(), "fe80::1");
+    assert_eq!(range.ports(), 42..=44);
+
+    let default = EndPoint::from_string("[::1]").unwrap();
+    assert_eq!(default.host(), "::1");
+    assert_eq!(default.ports(), DEFAULT_PORT..=DEFAULT_PORT);
+
+    let bare = EndPoint::from_string("fe80::1").unwrap();
+    assert_eq!(bare.host(), "fe80::1");
+    assert_eq!(bare.ports(), DEFAULT_PORT..=DEFAULT_PORT);
+
+    EndPoint::from_string("::").unwrap();
+    EndPoint::from_string("2001:db8::ff00:42:8329").unwrap();
+    EndPoint::from_string(" [::ffff:127.0.0.1]:42 ").unwrap();
+
+    EndPoint::from_string("[]").unwrap_err();
+    EndPoint::from_string("[]:42").unwrap_err();
+    EndPoint::from_string("[::1").unwrap_err();
+    EndPoint::from_string("[::1]:").unwrap_err();
+    EndPoint::from_string("[::1]:0").unwrap_err();
+    EndPoint::from_string("[::1]:42..41").unwrap_err();
+    EndPoint::from_string("[::1]42").unwrap_err();
+    EndPoint::from_string("[::1]:42:43").unwrap_err();
+    EndPoint::from_string("[127.0.0.1]:42").unwrap_err();
+    EndPoint::from_string("[example.com]:42").unwrap_err();
+    EndPoint::from_string("::1:42..44").unwrap_err();
+    EndPoint::from_string("fe80::1::2").unwrap_err();
+    EndPoint::from_string("example.com:42:43").unwrap_err();
+}
+
+#[cfg(test)]
+fn resolved(ips: &[&str], port_begin: u16, port_end: u16) -> ResolvedEndPoint {
+    ResolvedEndPoint {
+        ips: ips.iter().map(|ip| ip.parse().unwrap()).collect(),
+        port_begin,
+        port_end,
+    }
+}
+
+#[cfg(test)]
+fn addrs(addrs: &[&str]) -> Vec<SocketAddr> {
+    addrs.iter().map(|addr| addr.parse().unwrap()).collect()
+}
+
+#[test]
+fn end_point_iterator() {
+    let all: Vec<SocketAddr> = resolved(&["10.0.0.1", "10.0.0.2"], 42, 44)
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        all,
+        addrs(&[
+            "10.0.0.1:42",
+            "10.0.0.2:42",
+            "10.0.0.1:43",
+            "10.0.0.2:43",
+            "10.0.0.1:44",
+            "10.0.0.2:44",
+        ])
+    );
+
+    let iter = resolved(&["10.0.0.1", "::1"], 42, 0).into_iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        addrs(&["10.0.0.1:42", "[::1]:42"])
+    );
+
+    let single: Vec<SocketAddr> = resolved(&["10.0.0.1"], 42, 42).into_iter().collect();
+    assert_eq!(single, addrs(&["10.0.0.1:42"]));
+
+    let max: Vec<SocketAddr> = resolved(&["10.0.0.1"], 65534, 65535).into_iter().collect();
+    assert_eq!(max, addrs(&["10.0.0.1:65534", "10.0.0.1:65535"]));
+
+    assert_eq!(resolved(&[], 42, 0).into_iter().next(), None);
+    assert_eq!(resolved(&[], 42, 44).into_iter().next(), None);
+}
+
+#[test]
+fn end_point_iterator_shuffled() {
+    let mut all: Vec<SocketAddr> = resolved(&["10.0.0.1", "10.0.0.2", "10.0.0.3"], 42, 45)
+        .into_shuffled_iter()
+        .collect();
+
+    assert_eq!(all.len(), 12);
+
+    // Every address is still returned exactly once.
+    let mut expected: Vec<SocketAddr> = resolved(&["10.0.0.1", "10.0.0.2", "10.0.0.3"], 42, 45)
+        .into_iter()
+        .collect();
+
+    all.sort();
+    expected.sort();
+    assert_eq!(all, expected);
+
+    assert_eq!(resolved(&[], 42, 44).into_shuffled_iter().next(), None);
+}
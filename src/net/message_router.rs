@@ -1,11 +1,15 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::time;
 
-use crate::ignite_error::{IgniteError, IgniteResult, LogResult};
-use crate::net::async_data_channel::AsyncDataChannel;
+use crate::ignite_error::{ErrorKind, IgniteError, IgniteResult};
+use crate::net::affinity::{AffinityContext, AffinityNode};
+use crate::net::async_data_channel::{AsyncDataChannel, Notifications};
+use crate::net::channel_pool::ChannelPool;
+use crate::protocol::message::{CachePartitionsReq, KeyAffinity, Request};
+use crate::retry_policy::RetryContext;
 
 use crate::client_configuration::ClientConfiguration;
 
@@ -13,76 +17,275 @@ use crate::client_configuration::ClientConfiguration;
 /// connection link to the Ignite cluster.
 ///
 /// It also responsible for choosing which connection to use for a certain
-/// request.
+/// request. Single key requests are sent directly to the node storing the key,
+/// if it is known.
 #[derive(Debug)]
 pub struct MessageRouter {
     cfg: Arc<ClientConfiguration>,
-    channel: Mutex<Option<AsyncDataChannel>>,
+    pool: ChannelPool,
+    affinity: AffinityContext,
 }
 
 impl MessageRouter {
     /// Make new instance.
-    pub fn new(cfg: Arc<ClientConfiguration>) -> Self {
-        Self {
+    pub fn new(cfg: Arc<ClientConfiguration>) -> IgniteResult<Self> {
+        let affinity = AffinityContext::default();
+        let pool = ChannelPool::new(cfg.clone(), affinity.topology().clone())?;
+
+        Ok(Self {
             cfg,
-            channel: Mutex::new(None),
-        }
+            pool,
+            affinity,
+        })
     }
 
-    /// Ensure that connection with cluster is established.
-    async fn ensure_connected(&self) -> IgniteResult<MutexGuard<'_, Option<AsyncDataChannel>>> {
-        // We do not care if the inner value is poisoned, as we are going to reassign it
-        // without reading.
-        let mut guard = self.channel.lock().await;
+    pub async fn establish_connection(&self) -> IgniteResult<()> {
+        self.pool.connect().await
+    }
 
-        if guard.is_none() {
-            info!("Connection is not established. Connecting");
-            debug!("Re-connecting to a random node");
-            let channel = connect_random_node(&self.cfg).await?;
+    /// Start sending heartbeats over the idle channels in the background,
+    /// until the router is dropped. Does nothing if heartbeats are disabled.
+    pub fn start_heartbeats(router: &Arc<Self>) {
+        let interval = match router.cfg.get_heartbeat_interval() {
+            Some(interval) => interval,
+            None => return,
+        };
 
-            *guard = Some(channel);
-        }
+        let router = Arc::downgrade(router);
+        tokio::spawn(async move {
+            loop {
+                // The negotiated interval may be shorter than the configured one,
+                // so the pool is asked when the next heartbeat is due.
+                let delay = match router.upgrade() {
+                    Some(router) => router.pool.heartbeat().await,
+                    None => return,
+                };
 
-        Ok(guard)
+                time::delay_for(delay.map_or(interval, |due| due.min(interval))).await;
+            }
+        });
     }
 
-    pub async fn establish_connection(&self) -> IgniteResult<()> {
-        let _ = self.ensure_connected().await?;
+    /// Start discovering and resolving endpoints periodically in the background,
+    /// until the router is dropped. Does nothing if re-resolution is disabled.
+    pub fn start_resolving(router: &Arc<Self>) {
+        let interval = match router.cfg.get_resolve_interval() {
+            Some(interval) => interval,
+            None => return,
+        };
 
-        Ok(())
+        let router = Arc::downgrade(router);
+        tokio::spawn(async move {
+            loop {
+                time::delay_for(interval).await;
+
+                match router.upgrade() {
+                    Some(router) => router.pool.refresh().await,
+                    None => return,
+                };
+            }
+        });
+    }
+
+    /// Send request to the cluster and wait for the response.
+    /// Times out according to the configuration.
+    pub async fn send<R: Request>(&self, req: &R) -> IgniteResult<R::Response> {
+        self.send_with_timeout(req, None).await
+    }
+
+    /// Send request to the cluster and wait for the response.
+    ///
+    /// Fails if the response is not received within the timeout, including
+    /// retries. If the timeout is not specified, the configured one is used.
+    pub async fn send_with_timeout<R: Request>(
+        &self,
+        req: &R,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<R::Response> {
+        let (rsp, _) = self.send_bound(req, timeout).await?;
+
+        Ok(rsp)
+    }
+
+    /// Send request to the cluster and wait for the response, returning the
+    /// channel the request was sent with as well.
+    ///
+    /// Resources created by the request on the server, like query cursors,
+    /// can only be accessed over the same channel.
+    pub async fn send_bound<R: Request>(
+        &self,
+        req: &R,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<(R::Response, Arc<AsyncDataChannel>)> {
+        self.with_timeout::<R, _>(self.send_with_retries(req), timeout)
+            .await
     }
-}
 
-/// Try connect to a random node in a cluster.
-async fn connect_random_node(cfg: &ClientConfiguration) -> IgniteResult<AsyncDataChannel> {
-    let mut end_points = cfg.get_endpoints().to_owned();
+    /// Send request over the given channel and wait for the response.
+    /// The request is not retried, as the channel is the only one it can be sent with.
+    pub async fn send_on<R: Request>(
+        &self,
+        channel: &AsyncDataChannel,
+        req: &R,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<R::Response> {
+        self.with_timeout::<R, _>(channel.request(req), timeout)
+            .await
+    }
 
-    &mut end_points[..].shuffle(&mut thread_rng());
+    /// Send request creating the resource on the server, e.g. continuous query,
+    /// and start listening to the notifications the server pushes for it.
+    ///
+    /// The request is not retried, as the resource may have been created already.
+    pub async fn subscribe<R: Request>(
+        &self,
+        req: &R,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<(R::Response, Notifications, Arc<AsyncDataChannel>)> {
+        let subscribe = async {
+            let channel = self.channel(req).await?;
+            let (rsp, notifications) = channel.subscribe(req).await?;
 
-    debug!("End points after shuffle: {:?}", end_points);
+            Ok((rsp, notifications, channel))
+        };
 
-    let resolved = end_points.iter().filter_map(|x| {
-        x.resolve()
-            .log_error_w(format!("Can not resolve host {}", x.host()))
-    });
+        self.with_timeout::<R, _>(subscribe, timeout).await
+    }
 
-    for end_point in resolved {
-        for addr in end_point {
-            let res = AsyncDataChannel::connect(&addr, cfg).await;
+    /// Wait for the request to complete, failing if it takes longer than the
+    /// timeout. If the timeout is not specified, the configured one is used.
+    async fn with_timeout<R: Request, T>(
+        &self,
+        fut: impl Future<Output = IgniteResult<T>>,
+        timeout: Option<Duration>,
+    ) -> IgniteResult<T> {
+        let timeout = match timeout.or_else(|| self.cfg.get_request_timeout()) {
+            Some(timeout) => timeout,
+            None => return fut.await,
+        };
 
-            let channel = match res {
-                Ok(s) => s,
-                Err(_) => {
-                    res.log_error_w(format!("Can not connect to the host {}", addr));
-                    continue;
-                }
+        time::timeout(timeout, fut).await.map_err(|_| {
+            IgniteError::new_with_kind(
+                ErrorKind::Timeout,
+                format!("Operation {:?} timed out after {:?}", R::TYPE, timeout),
+            )
+        })?
+    }
+
+    /// Send request to the cluster and wait for the response.
+    ///
+    /// If the connection is lost, the request is retried using a new connection
+    /// as long as the retry policy allows it.
+    async fn send_with_retries<R: Request>(
+        &self,
+        req: &R,
+    ) -> IgniteResult<(R::Response, Arc<AsyncDataChannel>)> {
+        let mut iteration = 0;
+
+        loop {
+            let res = match self.channel(req).await {
+                Ok(channel) => channel.request(req).await.map(|rsp| (rsp, channel)),
+                Err(err) => Err(err),
             };
 
-            return Ok(channel);
+            let err = match res {
+                Ok(rsp) => return Ok(rsp),
+                Err(err) => err,
+            };
+
+            if !self.should_retry::<R>(&err, iteration) {
+                return Err(err);
+            }
+
+            warn!("Operation {:?} failed, retrying: {}", R::TYPE, err);
+
+            time::delay_for(self.cfg.reconnect_delay(iteration)).await;
+            iteration += 1;
+        }
+    }
+
+    /// Get channel to send the request with.
+    ///
+    /// Uses the channel to the primary node of the request key if it is known
+    /// and connected, otherwise falls back to the load balancing policy.
+    async fn channel<R: Request>(&self, req: &R) -> IgniteResult<Arc<AsyncDataChannel>> {
+        if self.cfg.get_partition_awareness() {
+            if let Some(key) = req.affinity() {
+                if let Some(channel) = self.affinity_channel(&key).await {
+                    return Ok(channel);
+                }
+            }
+        }
+
+        self.pool.channel().await
+    }
+
+    /// Get channel to the primary node of the key, refreshing partition
+    /// distribution of the cache if needed.
+    async fn affinity_channel(&self, key: &KeyAffinity<'_>) -> Option<Arc<AsyncDataChannel>> {
+        let node = match self.affinity.node(key) {
+            AffinityNode::Outdated => {
+                self.refresh_affinity(key).await;
+                self.affinity.node(key)
+            }
+            node => node,
+        };
+
+        match node {
+            AffinityNode::Node(node_id) => self.pool.node_channel(&node_id),
+            _ => None,
         }
     }
 
-    Err(IgniteError::new(
-        "Can not connect to any host. See logs for details",
-    ))
+    /// Request partition distribution of the key cache.
+    /// Failures are logged and the distribution is stored as unknown,
+    /// as requests can still be sent to any node.
+    async fn refresh_affinity(&self, key: &KeyAffinity<'_>) {
+        let _guard = self.affinity.lock_refresh().await;
+
+        // Could have been refreshed while we were waiting for the lock.
+        if self.affinity.node(key) != AffinityNode::Outdated {
+            return;
+        }
+
+        let cache_ids = [key.cache_id()];
+        let req = CachePartitionsReq::new(&cache_ids);
+
+        let res = match self.pool.channel().await {
+            Ok(channel) => channel.request(&req).await,
+            Err(err) => Err(err),
+        };
+
+        match res {
+            Ok(rsp) => self
+                .affinity
+                .update(rsp.version(), &cache_ids, rsp.into_caches()),
+            Err(err) => {
+                warn!(
+                    "Can not get partitions of the cache {}: {}",
+                    key.cache_id(),
+                    err
+                );
+
+                self.affinity.set_unknown(key.cache_id());
+            }
+        }
+    }
+
+    /// Decide whether the failed request should be retried.
+    fn should_retry<R: Request>(&self, err: &IgniteError, iteration: u32) -> bool {
+        if err.kind() != ErrorKind::Connection {
+            return false;
+        }
+
+        let ctx = RetryContext::new(
+            R::TYPE as i16,
+            R::IDEMPOTENT,
+            iteration,
+            self.cfg.get_retry_limit(),
+            err,
+        );
+
+        self.cfg.get_retry_policy().should_retry(&ctx)
+    }
 }
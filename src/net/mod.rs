@@ -1,7 +1,11 @@
+mod affinity;
 mod async_data_channel;
+mod channel_pool;
 mod message_router;
 mod end_point;
+mod tls;
 pub mod utils;
 
+pub use self::async_data_channel::{AsyncDataChannel, Notifications};
 pub use self::message_router::MessageRouter;
 pub use self::end_point::EndPoint;
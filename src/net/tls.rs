@@ -0,0 +1,398 @@
+use std::fmt;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+use crate::client_configuration::ClientConfiguration;
+use crate::ignite_error::{ErrorKind, IgniteError, IgniteResult};
+
+/// Stream, the data channel can work over.
+pub trait AsyncStream: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+
+impl<T> AsyncStream for T where T: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+
+/// Stream with the type erased, so plain and TLS connections are used the same way.
+pub type BoxedStream = Box<dyn AsyncStream>;
+
+/// Opens connections to the nodes, wrapping them into TLS if it is configured.
+#[derive(Clone)]
+pub struct StreamConnector {
+    tls: Option<imp::TlsConnector>,
+    nodelay: bool,
+    keepalive: Option<Duration>,
+}
+
+impl StreamConnector {
+    /// Make new instance. Fails if TLS is configured but can not be set up,
+    /// e.g. certificate files can not be read.
+    pub fn new(cfg: &ClientConfiguration) -> IgniteResult<Self> {
+        let tls = match cfg.get_tls() {
+            Some(tls) => Some(imp::TlsConnector::new(tls)?),
+            None => None,
+        };
+
+        Ok(Self {
+            tls,
+            nodelay: cfg.get_tcp_nodelay(),
+            keepalive: cfg.get_tcp_keepalive(),
+        })
+    }
+
+    /// Open connection to the node with the given address.
+    /// Host name is used to verify the node certificate.
+    pub async fn connect(&self, addr: &SocketAddr, host: &str) -> IgniteResult<BoxedStream> {
+        let conn = TcpStream::connect(addr).await.map_err(|err| {
+            IgniteError::new_with_kind_and_source(
+                ErrorKind::Connection,
+                format!("Can not establish connection to host {}", addr),
+                Box::new(err),
+            )
+        })?;
+
+        conn.set_nodelay(self.nodelay)
+            .and_then(|_| conn.set_keepalive(self.keepalive))
+            .map_err(|err| {
+                IgniteError::new_with_kind_and_source(
+                    ErrorKind::Connection,
+                    format!("Can not configure connection to host {}", addr),
+                    Box::new(err),
+                )
+            })?;
+
+        match &self.tls {
+            Some(tls) => tls.connect(conn, host).await,
+            None => Ok(Box::new(conn)),
+        }
+    }
+}
+
+impl fmt::Debug for StreamConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamConnector")
+            .field("tls", &self.tls.is_some())
+            .field("nodelay", &self.nodelay)
+            .field("keepalive", &self.keepalive)
+            .finish()
+    }
+}
+
+/// Make error for the failed TLS handshake.
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+fn handshake_error<E>(host: &str, err: E) -> IgniteError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    IgniteError::new_with_kind_and_source(
+        ErrorKind::Connection,
+        format!("TLS handshake with host {} failed", host),
+        Box::new(err),
+    )
+}
+
+/// Read file contents.
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+fn read_file(path: &std::path::Path) -> IgniteResult<Vec<u8>> {
+    use crate::ignite_error::ChainResult;
+
+    std::fs::read(path).chain_error(format!("Can not read file {}", path.display()))
+}
+
+#[cfg(feature = "tls-rustls")]
+mod imp {
+    use std::io::BufReader;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use rustls::internal::pemfile;
+    use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerCertVerified};
+    use rustls::{ServerCertVerifier, TLSError, WebPKIVerifier};
+    use tokio::net::TcpStream;
+    use webpki::DNSNameRef;
+
+    use super::{handshake_error, read_file, BoxedStream};
+    use crate::ignite_error::{IgniteError, IgniteResult};
+    use crate::tls_configuration::{TlsConfiguration, TlsVerifyMode};
+
+    /// Name sent to the node when the host is not a valid DNS name and the
+    /// certificate is not checked against it anyway.
+    const UNVERIFIED_SERVER_NAME: &str = "localhost";
+
+    /// Verifies the certificate chain, but not the host name.
+    struct CaOnlyVerifier(WebPKIVerifier);
+
+    impl ServerCertVerifier for CaOnlyVerifier {
+        fn verify_server_cert(
+            &self,
+            roots: &RootCertStore,
+            presented_certs: &[Certificate],
+            dns_name: DNSNameRef<'_>,
+            ocsp_response: &[u8],
+        ) -> Result<ServerCertVerified, TLSError> {
+            // Name is only checked once the chain is verified.
+            match self
+                .0
+                .verify_server_cert(roots, presented_certs, dns_name, ocsp_response)
+            {
+                Err(TLSError::WebPKIError(webpki::Error::CertNotValidForName)) => {
+                    Ok(ServerCertVerified::assertion())
+                }
+                res => res,
+            }
+        }
+    }
+
+    /// Accepts any certificate.
+    struct NoVerifier;
+
+    impl ServerCertVerifier for NoVerifier {
+        fn verify_server_cert(
+            &self,
+            _roots: &RootCertStore,
+            _presented_certs: &[Certificate],
+            _dns_name: DNSNameRef<'_>,
+            _ocsp_response: &[u8],
+        ) -> Result<ServerCertVerified, TLSError> {
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+
+    /// TLS connector, implemented with rustls.
+    #[derive(Clone)]
+    pub struct TlsConnector {
+        connector: tokio_rustls::TlsConnector,
+        server_name: Option<String>,
+        verify_mode: TlsVerifyMode,
+    }
+
+    impl TlsConnector {
+        /// Make new instance.
+        pub fn new(cfg: &TlsConfiguration) -> IgniteResult<Self> {
+            let mut config = ClientConfig::new();
+
+            if let Some(ca_file) = cfg.get_ca_file() {
+                for cert in load_certs(ca_file)? {
+                    config.root_store.add(&cert).map_err(|err| {
+                        IgniteError::new_with_source(
+                            format!("Invalid CA certificate in file {}", ca_file.display()),
+                            Box::new(err),
+                        )
+                    })?;
+                }
+            }
+
+            if let Some((cert_file, key_file)) = cfg.get_client_certificate() {
+                config
+                    .set_single_client_cert(load_certs(cert_file)?, load_key(key_file)?)
+                    .map_err(|err| {
+                        IgniteError::new_with_source("Invalid client certificate", Box::new(err))
+                    })?;
+            }
+
+            match cfg.get_verify_mode() {
+                TlsVerifyMode::Full => {}
+                TlsVerifyMode::CaOnly => config
+                    .dangerous()
+                    .set_certificate_verifier(Arc::new(CaOnlyVerifier(WebPKIVerifier::new()))),
+                TlsVerifyMode::None => config
+                    .dangerous()
+                    .set_certificate_verifier(Arc::new(NoVerifier)),
+            }
+
+            Ok(Self {
+                connector: tokio_rustls::TlsConnector::from(Arc::new(config)),
+                server_name: cfg.get_server_name().map(str::to_owned),
+                verify_mode: cfg.get_verify_mode(),
+            })
+        }
+
+        /// Perform TLS handshake over the connection.
+        pub async fn connect(&self, conn: TcpStream, host: &str) -> IgniteResult<BoxedStream> {
+            let name = self.server_name.as_deref().unwrap_or(host);
+
+            let dns_name = match DNSNameRef::try_from_ascii_str(name) {
+                Ok(dns_name) => dns_name,
+                Err(_) if self.verify_mode != TlsVerifyMode::Full => {
+                    DNSNameRef::try_from_ascii_str(UNVERIFIED_SERVER_NAME).unwrap()
+                }
+                Err(_) => {
+                    return Err(IgniteError::new(format!(
+                        "Host name {} can not be used to verify the node certificate. \
+                         Set the server name explicitly",
+                        name
+                    )))
+                }
+            };
+
+            let stream = self
+                .connector
+                .connect(dns_name, conn)
+                .await
+                .map_err(|err| handshake_error(host, err))?;
+
+            Ok(Box::new(stream))
+        }
+    }
+
+    /// Load certificates from the PEM file.
+    fn load_certs(path: &Path) -> IgniteResult<Vec<Certificate>> {
+        let data = read_file(path)?;
+
+        pemfile::certs(&mut BufReader::new(&data[..]))
+            .ok()
+            .filter(|certs| !certs.is_empty())
+            .ok_or_else(|| {
+                IgniteError::new(format!("No certificates found in file {}", path.display()))
+            })
+    }
+
+    /// Load private key from the PEM file. Both PKCS#8 and RSA keys are supported.
+    fn load_key(path: &Path) -> IgniteResult<PrivateKey> {
+        let data = read_file(path)?;
+
+        let pkcs8 = pemfile::pkcs8_private_keys(&mut BufReader::new(&data[..]));
+        let rsa = pemfile::rsa_private_keys(&mut BufReader::new(&data[..]));
+
+        pkcs8
+            .unwrap_or_default()
+            .into_iter()
+            .chain(rsa.unwrap_or_default())
+            .next()
+            .ok_or_else(|| {
+                IgniteError::new(format!("No private key found in file {}", path.display()))
+            })
+    }
+}
+
+#[cfg(all(feature = "tls-native", not(feature = "tls-rustls")))]
+mod imp {
+    use std::path::Path;
+
+    use native_tls::{Certificate, Identity};
+    use tokio::net::TcpStream;
+
+    use super::{handshake_error, read_file, BoxedStream};
+    use crate::ignite_error::{IgniteError, IgniteResult};
+    use crate::tls_configuration::{TlsConfiguration, TlsVerifyMode};
+
+    /// Marker of the certificate end in the PEM file.
+    const PEM_CERT_END: &str = "-----END CERTIFICATE-----";
+
+    /// TLS connector, implemented with the native TLS library of the platform.
+    #[derive(Clone)]
+    pub struct TlsConnector {
+        connector: tokio_tls::TlsConnector,
+        server_name: Option<String>,
+    }
+
+    impl TlsConnector {
+        /// Make new instance.
+        pub fn new(cfg: &TlsConfiguration) -> IgniteResult<Self> {
+            let mut builder = native_tls::TlsConnector::builder();
+
+            if let Some(ca_file) = cfg.get_ca_file() {
+                for cert in load_certs(ca_file)? {
+                    builder.add_root_certificate(cert);
+                }
+            }
+
+            if let Some((cert_file, key_file)) = cfg.get_client_certificate() {
+                let identity = Identity::from_pkcs8(&read_file(cert_file)?, &read_file(key_file)?)
+                    .map_err(|err| {
+                        IgniteError::new_with_source("Invalid client certificate", Box::new(err))
+                    })?;
+
+                builder.identity(identity);
+            }
+
+            match cfg.get_verify_mode() {
+                TlsVerifyMode::Full => {}
+                TlsVerifyMode::CaOnly => {
+                    builder.danger_accept_invalid_hostnames(true);
+                }
+                TlsVerifyMode::None => {
+                    builder.danger_accept_invalid_certs(true);
+                }
+            }
+
+            let connector = builder
+                .build()
+                .map_err(|err| IgniteError::new_with_source("Can not set up TLS", Box::new(err)))?;
+
+            Ok(Self {
+                connector: tokio_tls::TlsConnector::from(connector),
+                server_name: cfg.get_server_name().map(str::to_owned),
+            })
+        }
+
+        /// Perform TLS handshake over the connection.
+        pub async fn connect(&self, conn: TcpStream, host: &str) -> IgniteResult<BoxedStream> {
+            let name = self.server_name.as_deref().unwrap_or(host);
+
+            let stream = self
+                .connector
+                .connect(name, conn)
+                .await
+                .map_err(|err| handshake_error(host, err))?;
+
+            Ok(Box::new(stream))
+        }
+    }
+
+    /// Load certificates from the PEM file.
+    /// Native library only reads a single certificate at once, so the file is split.
+    fn load_certs(path: &Path) -> IgniteResult<Vec<Certificate>> {
+        let data = read_file(path)?;
+        let text = String::from_utf8_lossy(&data);
+
+        let certs = text
+            .split_inclusive(PEM_CERT_END)
+            .filter(|pem| pem.contains(PEM_CERT_END))
+            .map(|pem| Certificate::from_pem(pem.trim().as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                IgniteError::new_with_source(
+                    format!("Invalid CA certificate in file {}", path.display()),
+                    Box::new(err),
+                )
+            })?;
+
+        if certs.is_empty() {
+            return Err(IgniteError::new(format!(
+                "No certificates found in file {}",
+                path.display()
+            )));
+        }
+
+        Ok(certs)
+    }
+}
+
+#[cfg(not(any(feature = "tls-rustls", feature = "tls-native")))]
+mod imp {
+    use tokio::net::TcpStream;
+
+    use super::BoxedStream;
+    use crate::ignite_error::{IgniteError, IgniteResult};
+    use crate::tls_configuration::TlsConfiguration;
+
+    /// TLS connector placeholder, used when no TLS implementation is enabled.
+    #[derive(Clone)]
+    pub enum TlsConnector {}
+
+    impl TlsConnector {
+        /// Always fails, as TLS is not supported.
+        pub fn new(_cfg: &TlsConfiguration) -> IgniteResult<Self> {
+            Err(IgniteError::new(
+                "TLS is configured, but the client is built without TLS support. \
+                 Enable either tls-rustls or tls-native feature",
+            ))
+        }
+
+        /// Never called, as the instance can not be made.
+        pub async fn connect(&self, _conn: TcpStream, _host: &str) -> IgniteResult<BoxedStream> {
+            match *self {}
+        }
+    }
+}
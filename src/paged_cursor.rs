@@ -0,0 +1,162 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::vec;
+
+use futures::future::BoxFuture;
+use futures::{FutureExt, Stream};
+
+use crate::ignite_error::IgniteResult;
+use crate::net::{AsyncDataChannel, MessageRouter};
+use crate::protocol::message::{CursorPage, Request, ResourceCloseReq};
+
+/// Cursor of the query, fetching the rows from the server page by page.
+///
+/// The cursor is bound to the channel the query was executed over.
+/// If the cursor is dropped before all the rows are fetched, it is closed
+/// on the server in the background.
+pub(crate) struct PagedCursor<T, R> {
+    router: Arc<MessageRouter>,
+    channel: Arc<AsyncDataChannel>,
+    cursor_id: i64,
+    get_page: Arc<R>,
+    rows: vec::IntoIter<T>,
+    has_more: bool,
+    fetch: Option<BoxFuture<'static, IgniteResult<CursorPage<T>>>>,
+}
+
+impl<T, R> PagedCursor<T, R>
+where
+    T: Send + 'static,
+    R: Request<Response = CursorPage<T>> + Send + Sync + 'static,
+{
+    /// Make new instance out of the first page.
+    /// The request is sent every time the next page is needed.
+    pub(crate) fn new(
+        router: Arc<MessageRouter>,
+        channel: Arc<AsyncDataChannel>,
+        cursor_id: i64,
+        first: CursorPage<T>,
+        get_page: R,
+    ) -> Self {
+        Self {
+            router,
+            channel,
+            cursor_id,
+            get_page: Arc::new(get_page),
+            has_more: first.has_more(),
+            rows: first.into_rows().into_iter(),
+            fetch: None,
+        }
+    }
+
+    /// Start fetching the next page.
+    fn fetch_page(&self) -> BoxFuture<'static, IgniteResult<CursorPage<T>>> {
+        let router = self.router.clone();
+        let channel = self.channel.clone();
+        let req = self.get_page.clone();
+
+        async move { router.send_on(&channel, &*req, None).await }.boxed()
+    }
+}
+
+impl<T, R> PagedCursor<T, R> {
+    /// Get ID of the cursor on the server.
+    pub(crate) fn cursor_id(&self) -> i64 {
+        self.cursor_id
+    }
+
+    /// Check if there are rows on the server, which are not fetched yet.
+    pub(crate) fn has_more(&self) -> bool {
+        self.has_more
+    }
+}
+
+/// Rows are never pinned, so the cursor can be moved regardless of their type.
+impl<T, R> Unpin for PagedCursor<T, R> {}
+
+impl<T, R> Stream for PagedCursor<T, R>
+where
+    T: Send + 'static,
+    R: Request<Response = CursorPage<T>> + Send + Sync + 'static,
+{
+    type Item = IgniteResult<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(row) = this.rows.next() {
+                return Poll::Ready(Some(Ok(row)));
+            }
+
+            if let Some(fetch) = &mut this.fetch {
+                let res = match fetch.poll_unpin(cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => return Poll::Pending,
+                };
+
+                this.fetch = None;
+
+                match res {
+                    Ok(page) => {
+                        this.has_more = page.has_more();
+                        this.rows = page.into_rows().into_iter();
+                        continue;
+                    }
+                    Err(err) => {
+                        // Cursor is closed by the server if fetching failed.
+                        this.has_more = false;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+            }
+
+            if !this.has_more {
+                return Poll::Ready(None);
+            }
+
+            this.fetch = Some(this.fetch_page());
+        }
+    }
+}
+
+impl<T, R> Drop for PagedCursor<T, R> {
+    fn drop(&mut self) {
+        // Server closes the cursor itself once the last page is fetched.
+        if self.has_more {
+            close_resource(&self.router, &self.channel, self.cursor_id);
+        }
+    }
+}
+
+/// Release the resource held by the server, e.g. query cursor, in the background.
+/// Nothing is done if the channel is broken, as the resource is released with it.
+pub(crate) fn close_resource(
+    router: &Arc<MessageRouter>,
+    channel: &Arc<AsyncDataChannel>,
+    resource_id: i64,
+) {
+    if channel.is_broken() {
+        return;
+    }
+
+    let runtime = match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => runtime,
+        Err(_) => {
+            warn!("Can not close resource {}: no runtime", resource_id);
+            return;
+        }
+    };
+
+    let router = router.clone();
+    let channel = channel.clone();
+
+    runtime.spawn(async move {
+        let req = ResourceCloseReq::new(resource_id);
+
+        if let Err(err) = router.send_on(&channel, &req, None).await {
+            warn!("Can not close resource {}: {}", resource_id, err);
+        }
+    });
+}
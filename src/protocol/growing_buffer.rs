@@ -8,7 +8,7 @@ use std::ptr;
 const MIN_CAPACITY: usize = 1024;
 
 /// Max capacity of the underlying memory
-const MAX_CAPACITY: usize = ::std::i32::MAX as usize;
+const MAX_CAPACITY: usize = i32::MAX as usize;
 
 /// Writing stream abstraction
 pub struct GrowingBuffer {
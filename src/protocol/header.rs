@@ -81,6 +81,12 @@ pub const OBJECT_MAP_ENTRY: i8 = 26;
 /// Binary wrapper type header
 pub const BINARY_OBJECT: i8 = 27;
 
+/// Enum type header
+pub const ENUM: i8 = 28;
+
+/// Enum array type header
+pub const ENUM_ARRAY: i8 = 29;
+
 /// Decimal type header
 pub const DECIMAL: i8 = 30;
 
@@ -99,6 +105,9 @@ pub const TIME: i8 = 35;
 /// Time array type header
 pub const TIME_ARRAY: i8 = 36;
 
+/// Binary enum type header
+pub const BINARY_ENUM: i8 = 38;
+
 /// Null type header
 pub const NULL: i8 = 101;
 
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::utils;
+
+/// Hash code of the value, calculated the same way as Java's `hashCode()` of
+/// the deserialized value on the server. Used to find the partition the key belongs to.
+pub trait IgniteHash {
+    /// Get hash code of the value.
+    fn ignite_hash(&self) -> i32;
+
+    /// Get hash code of the affinity key of the value, which defines the
+    /// partition the value is stored in.
+    ///
+    /// Affinity key fields are given as field IDs by type IDs. Only binary
+    /// objects can have affinity key fields, all other values are affinity
+    /// keys themselves.
+    fn affinity_hash(&self, _key_fields: &HashMap<i32, i32>) -> i32 {
+        self.ignite_hash()
+    }
+}
+
+/// Fold 64-bit value into 32-bit hash the same way as Java's `Long.hashCode()`.
+fn fold_i64(value: i64) -> i32 {
+    (value ^ ((value as u64) >> 32) as i64) as i32
+}
+
+impl IgniteHash for i8 {
+    fn ignite_hash(&self) -> i32 {
+        i32::from(*self)
+    }
+}
+
+impl IgniteHash for i16 {
+    fn ignite_hash(&self) -> i32 {
+        i32::from(*self)
+    }
+}
+
+impl IgniteHash for i32 {
+    fn ignite_hash(&self) -> i32 {
+        *self
+    }
+}
+
+impl IgniteHash for i64 {
+    fn ignite_hash(&self) -> i32 {
+        fold_i64(*self)
+    }
+}
+
+impl IgniteHash for f32 {
+    fn ignite_hash(&self) -> i32 {
+        // Java collapses all NaN values into the canonical one.
+        if self.is_nan() {
+            return 0x7fc0_0000;
+        }
+
+        self.to_bits() as i32
+    }
+}
+
+impl IgniteHash for f64 {
+    fn ignite_hash(&self) -> i32 {
+        // Java collapses all NaN values into the canonical one.
+        if self.is_nan() {
+            return fold_i64(0x7ff8_0000_0000_0000);
+        }
+
+        fold_i64(self.to_bits() as i64)
+    }
+}
+
+impl IgniteHash for bool {
+    fn ignite_hash(&self) -> i32 {
+        if *self {
+            1231
+        } else {
+            1237
+        }
+    }
+}
+
+impl IgniteHash for str {
+    fn ignite_hash(&self) -> i32 {
+        utils::java_string_hash(self)
+    }
+}
+
+impl IgniteHash for &str {
+    fn ignite_hash(&self) -> i32 {
+        utils::java_string_hash(self)
+    }
+}
+
+impl IgniteHash for String {
+    fn ignite_hash(&self) -> i32 {
+        utils::java_string_hash(self)
+    }
+}
+
+impl IgniteHash for Uuid {
+    fn ignite_hash(&self) -> i32 {
+        let bits = self.as_u128();
+
+        fold_i64(((bits >> 64) as i64) ^ (bits as i64))
+    }
+}
+
+#[test]
+fn test_ignite_hash_primitives() {
+    // Reference values are produced by Java's hashCode().
+    assert_eq!((-3i8).ignite_hash(), -3);
+    assert_eq!((-5i16).ignite_hash(), -5);
+    assert_eq!((-42i32).ignite_hash(), -42);
+    assert_eq!(42i64.ignite_hash(), 42);
+    assert_eq!((-1i64).ignite_hash(), 0);
+    assert_eq!(i64::MIN.ignite_hash(), -2_147_483_648);
+    assert_eq!(1_234_567_890_123i64.ignite_hash(), 1_912_276_436);
+    assert_eq!(true.ignite_hash(), 1231);
+    assert_eq!(false.ignite_hash(), 1237);
+}
+
+#[test]
+fn test_ignite_hash_floating() {
+    // Reference values are produced by Java's hashCode().
+    assert_eq!(1.5f32.ignite_hash(), 1_069_547_520);
+    assert_eq!((-0.0f32).ignite_hash(), -2_147_483_648);
+    assert_eq!(f32::NAN.ignite_hash(), 2_143_289_344);
+    assert_eq!(0.1f64.ignite_hash(), -1_507_852_285);
+    assert_eq!((-0.0f64).ignite_hash(), -2_147_483_648);
+    assert_eq!(f64::NAN.ignite_hash(), 2_146_959_360);
+}
+
+#[test]
+fn test_ignite_hash_objects() {
+    // Reference values are produced by Java's hashCode().
+    assert_eq!("default".ignite_hash(), 1_544_803_905);
+    assert_eq!("Привет".to_owned().ignite_hash(), 1_177_014_952);
+
+    let uuid = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+    assert_eq!(uuid.ignite_hash(), 1_256_478_162);
+}
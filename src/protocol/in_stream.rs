@@ -85,7 +85,7 @@ impl<'a> InStream<'a> {
 
                 let pos = self.pos.get();
 
-                self.inc_pos(4 + len as usize);
+                self.inc_pos(len as usize);
 
                 Some(String::from_utf8_lossy(&self.mem[pos..pos + len as usize]))
             }
@@ -99,13 +99,80 @@ impl<'a> InStream<'a> {
 
         let pos = self.pos.get();
 
-        self.inc_pos(4 + len as usize);
+        self.inc_pos(len as usize);
 
         String::from_utf8_lossy(&self.mem[pos..pos + len as usize])
     }
 
+    /// Read f32 value from the stream
+    pub fn read_f32(&self) -> f32 {
+        f32::from_bits(self.read_i32() as u32)
+    }
+
+    /// Read f64 value from the stream
+    pub fn read_f64(&self) -> f64 {
+        f64::from_bits(self.read_i64() as u64)
+    }
+
+    /// Read specified number of bytes
+    pub fn read_bytes(&self, len: usize) -> &'a [u8] {
+        let pos = self.pos.get();
+
+        self.inc_pos(len);
+
+        &self.mem[pos..pos + len]
+    }
+
+    /// Get specified number of bytes without advancing the position
+    pub fn peek_bytes(&self, len: usize) -> &'a [u8] {
+        let pos = self.pos.get();
+
+        &self.mem[pos..pos + len]
+    }
+
+    /// Get number of bytes left in the stream
+    pub fn remaining(&self) -> usize {
+        self.mem.len().saturating_sub(self.pos.get())
+    }
+
+    /// Get current position in the stream
+    pub fn position(&self) -> usize {
+        self.pos.get()
+    }
+
     /// Advance position for the specified value
     fn inc_pos(&self, val: usize) {
         self.pos.set(self.pos.get() + val);
     }
 }
+
+#[test]
+fn test_read_str_sequence() {
+    let out = super::OutStream::new();
+
+    out.write_str("Lorem");
+    out.write_str_raw("ipsum");
+    out.write_i32(42);
+
+    let mem = out.into_memory();
+    let stream = InStream::new(&mem);
+
+    assert_eq!(stream.read_str().unwrap(), "Lorem");
+    assert_eq!(stream.read_str_raw(), "ipsum");
+    assert_eq!(stream.read_i32(), 42);
+}
+
+#[test]
+fn test_read_floating() {
+    let out = super::OutStream::new();
+
+    out.write_f32(-1.5);
+    out.write_f64(0.1);
+
+    let mem = out.into_memory();
+    let stream = InStream::new(&mem);
+
+    assert_eq!(stream.read_f32(), -1.5);
+    assert_eq!(stream.read_f64(), 0.1);
+    assert_eq!(stream.position(), 12);
+}
@@ -0,0 +1,59 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{write_full, IgniteHash, ProtocolType};
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to check if the cache contains the key.
+pub struct CacheContainsKeyReq<'a, K> {
+    cache_id: i32,
+    tx_id: Option<i32>,
+    key: &'a K,
+}
+
+impl<'a, K> CacheContainsKeyReq<'a, K> {
+    /// Create new instance of the request.
+    pub fn new(cache_id: i32, key: &'a K) -> Self {
+        Self {
+            cache_id,
+            tx_id: None,
+            key,
+        }
+    }
+
+    /// Make the operation within the transaction.
+    pub fn with_tx(self, tx_id: Option<i32>) -> Self {
+        Self { tx_id, ..self }
+    }
+}
+
+impl<'a, K> Request for CacheContainsKeyReq<'a, K>
+where
+    K: ProtocolType + IgniteHash + Sync,
+{
+    /// Request type.
+    const TYPE: RequestType = RequestType::CacheContainsKey;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = bool;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        write_tx_cache_header(out, self.cache_id, self.tx_id);
+        write_full(self.key, out);
+    }
+
+    /// The request can be sent to the node storing the key.
+    fn affinity(&self) -> Option<KeyAffinity<'_>> {
+        Some(KeyAffinity::new(self.cache_id, self.key))
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<bool> {
+        Ok(stream.read_bool())
+    }
+}
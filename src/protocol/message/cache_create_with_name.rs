@@ -1,4 +1,5 @@
-use crate::protocol::OutStream;
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
 use crate::protocol_version::ProtocolVersion;
 
 use super::common::*;
@@ -10,21 +11,25 @@ pub struct CacheCreateWithNameReq<'a> {
 
 impl<'a> CacheCreateWithNameReq<'a> {
     /// Create new instance of the request.
-    #[allow(dead_code)]
     pub fn new(cache_name: &'a str) -> Self {
-        Self{cache_name}
+        Self { cache_name }
     }
 }
 
-impl <'a> Request for CacheCreateWithNameReq<'a> {
+impl<'a> Request for CacheCreateWithNameReq<'a> {
     /// Request type.
     const TYPE: RequestType = RequestType::CacheCreateWithName;
 
     /// Response type.
-    type Response = SimpleResponse<()>;
+    type Response = ();
 
     /// Write payload of the request message.
-    fn write_payload(&self, out: &mut OutStream, _ver: &ProtocolVersion) {
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
         out.write_str(self.cache_name);
     }
-}
\ No newline at end of file
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::marker::PhantomData;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{try_read_full, write_full, IgniteHash, ProtocolType};
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to get value by key.
+pub struct CacheGetReq<'a, K, V> {
+    cache_id: i32,
+    tx_id: Option<i32>,
+    key: &'a K,
+    _v: PhantomData<V>,
+}
+
+impl<'a, K, V> CacheGetReq<'a, K, V> {
+    /// Create new instance of the request.
+    pub fn new(cache_id: i32, key: &'a K) -> Self {
+        Self {
+            cache_id,
+            tx_id: None,
+            key,
+            _v: PhantomData,
+        }
+    }
+
+    /// Make the operation within the transaction.
+    pub fn with_tx(self, tx_id: Option<i32>) -> Self {
+        Self { tx_id, ..self }
+    }
+}
+
+impl<'a, K, V> Request for CacheGetReq<'a, K, V>
+where
+    K: ProtocolType + IgniteHash + Sync,
+    V: ProtocolType<Item = V>,
+{
+    /// Request type.
+    const TYPE: RequestType = RequestType::CacheGet;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = Option<V>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        write_tx_cache_header(out, self.cache_id, self.tx_id);
+        write_full(self.key, out);
+    }
+
+    /// The request can be sent to the node storing the key.
+    fn affinity(&self) -> Option<KeyAffinity<'_>> {
+        Some(KeyAffinity::new(self.cache_id, self.key))
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<Option<V>> {
+        try_read_full::<V, V>(stream)
+    }
+}
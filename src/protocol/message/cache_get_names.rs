@@ -0,0 +1,45 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to get names of all the caches.
+pub struct CacheGetNamesReq;
+
+impl CacheGetNamesReq {
+    /// Create new instance of the request.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for CacheGetNamesReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::CacheGetNames;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = Vec<String>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, _out: &OutStream, _ver: &ProtocolVersion) {}
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<Vec<String>> {
+        let count = stream.read_i32();
+
+        let mut names = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            names.push(stream.read_str().unwrap_or_default().into_owned());
+        }
+
+        Ok(names)
+    }
+}
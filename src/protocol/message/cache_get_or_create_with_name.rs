@@ -0,0 +1,38 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent when getting or creating cache with name.
+pub struct CacheGetOrCreateWithNameReq<'a> {
+    cache_name: &'a str,
+}
+
+impl<'a> CacheGetOrCreateWithNameReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(cache_name: &'a str) -> Self {
+        Self { cache_name }
+    }
+}
+
+impl<'a> Request for CacheGetOrCreateWithNameReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::CacheGetOrCreateWithName;
+
+    /// Repeating the request has no additional effect.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_str(self.cache_name);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
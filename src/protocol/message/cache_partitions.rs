@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{read_full, InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to get distribution of the cache partitions between the nodes.
+pub struct CachePartitionsReq<'a> {
+    cache_ids: &'a [i32],
+}
+
+impl<'a> CachePartitionsReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(cache_ids: &'a [i32]) -> Self {
+        Self { cache_ids }
+    }
+}
+
+/// Distribution of the partitions of a single cache.
+#[derive(Debug, Clone)]
+pub struct CachePartitionMap {
+    cache_id: i32,
+    key_configs: HashMap<i32, i32>,
+    partitions: Option<Vec<Option<Uuid>>>,
+}
+
+impl CachePartitionMap {
+    /// Get ID of the cache.
+    pub fn cache_id(&self) -> i32 {
+        self.cache_id
+    }
+
+    /// Get IDs of the affinity key fields, by the key type IDs.
+    pub fn key_configs(&self) -> &HashMap<i32, i32> {
+        &self.key_configs
+    }
+
+    /// Get IDs of the primary nodes, indexed by partition.
+    /// None if partition awareness is not applicable to the cache,
+    /// e.g. if it uses custom affinity function.
+    pub fn partitions(&self) -> Option<&[Option<Uuid>]> {
+        self.partitions.as_deref()
+    }
+}
+
+/// Response to the cache partitions request.
+#[derive(Debug)]
+pub struct CachePartitionsRsp {
+    ver: AffinityTopologyVersion,
+    caches: Vec<CachePartitionMap>,
+}
+
+impl CachePartitionsRsp {
+    /// Get affinity topology version the distribution is actual for.
+    pub fn version(&self) -> AffinityTopologyVersion {
+        self.ver
+    }
+
+    /// Move out distributions of the caches.
+    pub fn into_caches(self) -> Vec<CachePartitionMap> {
+        self.caches
+    }
+}
+
+impl<'a> Request for CachePartitionsReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::CachePartitions;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = CachePartitionsRsp;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i32(self.cache_ids.len() as i32);
+        for id in self.cache_ids {
+            out.write_i32(*id);
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<CachePartitionsRsp> {
+        let ver = AffinityTopologyVersion::read(stream);
+
+        let mut caches = Vec::new();
+
+        let groups = stream.read_i32();
+        for _ in 0..groups {
+            read_group(stream, &mut caches);
+        }
+
+        Ok(CachePartitionsRsp { ver, caches })
+    }
+}
+
+/// Read group of caches, sharing the same partition distribution.
+fn read_group(stream: &InStream, caches: &mut Vec<CachePartitionMap>) {
+    let applicable = stream.read_bool();
+
+    let mut group = Vec::new();
+
+    let count = stream.read_i32();
+    for _ in 0..count {
+        let cache_id = stream.read_i32();
+
+        let mut key_configs = HashMap::new();
+        if applicable {
+            let configs = stream.read_i32();
+            for _ in 0..configs {
+                let type_id = stream.read_i32();
+                let field_id = stream.read_i32();
+
+                key_configs.insert(type_id, field_id);
+            }
+        }
+
+        group.push(CachePartitionMap {
+            cache_id,
+            key_configs,
+            partitions: None,
+        });
+    }
+
+    if applicable {
+        let partitions = read_partitions(stream);
+
+        for cache in &mut group {
+            cache.partitions = Some(partitions.clone());
+        }
+    }
+
+    caches.append(&mut group);
+}
+
+/// Read primary nodes of the partitions and index them by partition.
+fn read_partitions(stream: &InStream) -> Vec<Option<Uuid>> {
+    let mut partitions = Vec::new();
+
+    let nodes = stream.read_i32();
+    for _ in 0..nodes {
+        let node_id = read_full::<Uuid, Uuid>(stream);
+
+        let count = stream.read_i32();
+        for _ in 0..count {
+            let part = stream.read_i32() as usize;
+
+            if partitions.len() <= part {
+                partitions.resize(part + 1, None);
+            }
+            partitions[part] = node_id;
+        }
+    }
+
+    partitions
+}
@@ -0,0 +1,63 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{write_full, IgniteHash, ProtocolType};
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to put value by key.
+pub struct CachePutReq<'a, K, V: ?Sized> {
+    cache_id: i32,
+    tx_id: Option<i32>,
+    key: &'a K,
+    value: &'a V,
+}
+
+impl<'a, K, V: ?Sized> CachePutReq<'a, K, V> {
+    /// Create new instance of the request.
+    pub fn new(cache_id: i32, key: &'a K, value: &'a V) -> Self {
+        Self {
+            cache_id,
+            tx_id: None,
+            key,
+            value,
+        }
+    }
+
+    /// Make the operation within the transaction.
+    pub fn with_tx(self, tx_id: Option<i32>) -> Self {
+        Self { tx_id, ..self }
+    }
+}
+
+impl<'a, K, V> Request for CachePutReq<'a, K, V>
+where
+    K: ProtocolType + IgniteHash + Sync,
+    V: ProtocolType + ?Sized,
+{
+    /// Request type.
+    const TYPE: RequestType = RequestType::CachePut;
+
+    /// Putting the same value again gives the same result.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        write_tx_cache_header(out, self.cache_id, self.tx_id);
+        write_full(self.key, out);
+        write_full(self.value, out);
+    }
+
+    /// The request can be sent to the node storing the key.
+    fn affinity(&self) -> Option<KeyAffinity<'_>> {
+        Some(KeyAffinity::new(self.cache_id, self.key))
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::client_cluster::ClusterState;
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Request sent to change state of the cluster.
+pub struct ClusterChangeStateReq {
+    state: ClusterState,
+}
+
+impl ClusterChangeStateReq {
+    /// Create new instance of the request.
+    pub fn new(state: ClusterState) -> Self {
+        Self { state }
+    }
+}
+
+impl Request for ClusterChangeStateReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterChangeState;
+
+    /// Changing to the same state again gives the same result.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterStates);
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i8(self.state as i8);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Request sent to enable or disable write-ahead logging for the cache.
+pub struct ClusterChangeWalStateReq<'a> {
+    cache_name: &'a str,
+    enable: bool,
+}
+
+impl<'a> ClusterChangeWalStateReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(cache_name: &'a str, enable: bool) -> Self {
+        Self { cache_name, enable }
+    }
+}
+
+impl<'a> Request for ClusterChangeWalStateReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterChangeWalState;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterStates);
+
+    /// Response type. Whether the state was changed.
+    type Response = bool;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_str(self.cache_name);
+        out.write_bool(self.enable);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<bool> {
+        Ok(stream.read_bool())
+    }
+}
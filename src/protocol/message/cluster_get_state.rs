@@ -0,0 +1,46 @@
+use crate::client_cluster::ClusterState;
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+use crate::IgniteError;
+
+use super::common::*;
+
+/// Request sent to get state of the cluster.
+pub struct ClusterGetStateReq;
+
+impl ClusterGetStateReq {
+    /// Create new instance of the request.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for ClusterGetStateReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterGetState;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterStates);
+
+    /// Response type.
+    type Response = ClusterState;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, _out: &OutStream, _ver: &ProtocolVersion) {}
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<ClusterState> {
+        let code = stream.read_i8();
+
+        ClusterState::from_code(code)
+            .ok_or_else(|| IgniteError::new(format!("Unknown cluster state: {}", code)))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Request sent to check if write-ahead logging is enabled for the cache.
+pub struct ClusterGetWalStateReq<'a> {
+    cache_name: &'a str,
+}
+
+impl<'a> ClusterGetWalStateReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(cache_name: &'a str) -> Self {
+        Self { cache_name }
+    }
+}
+
+impl<'a> Request for ClusterGetWalStateReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterGetWalState;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterStates);
+
+    /// Response type.
+    type Response = bool;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_str(self.cache_name);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<bool> {
+        Ok(stream.read_bool())
+    }
+}
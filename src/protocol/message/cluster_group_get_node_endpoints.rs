@@ -0,0 +1,100 @@
+use std::net::{IpAddr, SocketAddr};
+
+use uuid::Uuid;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream, ProtocolType};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Request sent to get endpoints of the server nodes, the client can connect to.
+pub struct ClusterGroupGetNodeEndpointsReq;
+
+impl ClusterGroupGetNodeEndpointsReq {
+    /// Create new instance of the request.
+    /// All the server nodes of the current topology are requested.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Endpoints of a single server node.
+#[derive(Debug, Clone)]
+pub struct NodeEndpoints {
+    id: Uuid,
+    port: u16,
+    addresses: Vec<String>,
+}
+
+impl NodeEndpoints {
+    /// Get ID of the node.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Get socket addresses of the node. Addresses which are not IPs are skipped.
+    pub fn socket_addrs(&self) -> Vec<SocketAddr> {
+        self.addresses
+            .iter()
+            .filter_map(|addr| addr.parse::<IpAddr>().ok())
+            .map(|ip| SocketAddr::new(ip, self.port))
+            .collect()
+    }
+}
+
+impl Request for ClusterGroupGetNodeEndpointsReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterGroupGetNodeEndpoints;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterGroupGetNodesEndpoints);
+
+    /// Response type.
+    type Response = Vec<NodeEndpoints>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i64(UNKNOWN_TOPOLOGY_VERSION);
+        out.write_i64(UNKNOWN_TOPOLOGY_VERSION);
+    }
+
+    /// Read payload of the response message.
+    /// As the whole topology is requested, there are no removed nodes.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<Vec<NodeEndpoints>> {
+        let _top_ver = stream.read_i64();
+
+        let count = stream.read_i32();
+
+        let mut nodes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let id = Uuid::read_payload(stream);
+            let port = stream.read_i32() as u16;
+
+            let addrs = stream.read_i32();
+            let addresses = (0..addrs)
+                .filter_map(|_| stream.read_str().map(|s| s.into_owned()))
+                .collect();
+
+            nodes.push(NodeEndpoints {
+                id,
+                port,
+                addresses,
+            });
+        }
+
+        let removed = stream.read_i32();
+        for _ in 0..removed {
+            Uuid::read_payload(stream);
+        }
+
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,91 @@
+use uuid::Uuid;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream, ProtocolType};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Filter of the cluster nodes, applied on the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClusterGroupFilter {
+    /// Nodes with the attribute of the given value.
+    Attribute(String, String),
+    /// Server nodes only.
+    Servers,
+}
+
+impl ClusterGroupFilter {
+    /// Code of the filter in the protocol.
+    fn code(&self) -> i16 {
+        match self {
+            ClusterGroupFilter::Attribute(..) => 1,
+            ClusterGroupFilter::Servers => 2,
+        }
+    }
+}
+
+/// Request sent to get IDs of the cluster nodes.
+pub struct ClusterGroupGetNodeIdsReq<'a> {
+    filters: &'a [ClusterGroupFilter],
+}
+
+impl<'a> ClusterGroupGetNodeIdsReq<'a> {
+    /// Create new instance of the request.
+    /// IDs of the nodes of the current topology, matching all the filters, are requested.
+    pub fn new(filters: &'a [ClusterGroupFilter]) -> Self {
+        Self { filters }
+    }
+}
+
+impl<'a> Request for ClusterGroupGetNodeIdsReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterGroupGetNodeIds;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterGroups);
+
+    /// Response type.
+    type Response = Vec<Uuid>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        // IDs are only returned if the topology differs from the known one.
+        out.write_i64(UNKNOWN_TOPOLOGY_VERSION);
+
+        // Without filters all the nodes are requested.
+        out.write_bool(!self.filters.is_empty());
+        if self.filters.is_empty() {
+            return;
+        }
+
+        out.write_i32(self.filters.len() as i32);
+        for filter in self.filters {
+            out.write_i16(filter.code());
+            match filter {
+                ClusterGroupFilter::Attribute(name, value) => {
+                    out.write_str(name);
+                    out.write_str(value);
+                }
+                ClusterGroupFilter::Servers => out.write_bool(true),
+            }
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<Vec<Uuid>> {
+        let changed = stream.read_bool();
+        if !changed {
+            return Ok(Vec::new());
+        }
+
+        let _top_ver = stream.read_i64();
+
+        let count = stream.read_i32();
+
+        Ok((0..count).map(|_| Uuid::read_payload(stream)).collect())
+    }
+}
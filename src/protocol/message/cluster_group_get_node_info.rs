@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::cluster_node::ClusterNode;
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{header, try_read_full, InStream, OutStream, ProtocolType};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+use crate::{IgniteError, IgniteValue};
+
+use super::common::*;
+
+/// Request sent to get details of the cluster nodes.
+pub struct ClusterGroupGetNodeInfoReq<'a> {
+    ids: &'a [Uuid],
+}
+
+impl<'a> ClusterGroupGetNodeInfoReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(ids: &'a [Uuid]) -> Self {
+        Self { ids }
+    }
+}
+
+impl<'a> Request for ClusterGroupGetNodeInfoReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ClusterGroupGetNodeInfo;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ClusterGroups);
+
+    /// Response type. Nodes which left the cluster are not returned.
+    type Response = Vec<ClusterNode>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i32(self.ids.len() as i32);
+        for id in self.ids {
+            id.write_payload(out);
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<Vec<ClusterNode>> {
+        let count = stream.read_i32();
+
+        (0..count).map(|_| read_node(stream)).collect()
+    }
+}
+
+/// Read details of a single node, written by the server's
+/// `ClientClusterGroupGetNodesDetailsResponse`.
+fn read_node(stream: &InStream) -> IgniteResult<ClusterNode> {
+    let id = try_read_full::<Uuid, _>(stream)?
+        .ok_or_else(|| IgniteError::new("Node details do not contain the node ID"))?;
+
+    let mut attributes = HashMap::new();
+    let count = stream.read_i32();
+    for _ in 0..count {
+        let name = stream.read_str().unwrap_or_default().into_owned();
+        if let Some(value) = read_value(stream)? {
+            attributes.insert(name, value);
+        }
+    }
+
+    let addresses = read_strings(stream)?;
+    let host_names = read_strings(stream)?;
+    let order = stream.read_i64();
+    let _is_local = stream.read_bool();
+    let _is_daemon = stream.read_bool();
+    let is_client = stream.read_bool();
+    let consistent_id = read_value(stream)?;
+    let version = read_version(stream)?;
+
+    Ok(ClusterNode::new(
+        id,
+        consistent_id,
+        addresses,
+        host_names,
+        attributes,
+        order,
+        is_client,
+        version,
+    ))
+}
+
+/// Read collection of strings, skipping the elements of other types.
+fn read_strings(stream: &InStream) -> IgniteResult<Vec<String>> {
+    let hdr = stream.read_i8();
+
+    match hdr {
+        header::NULL => return Ok(Vec::new()),
+        header::OBJECT_COLLECTION => {}
+        _ => {
+            return Err(IgniteError::new(format!(
+                "Collection is expected, got value with type header {}",
+                hdr
+            )))
+        }
+    }
+
+    let count = stream.read_i32();
+    let _collection_type = stream.read_i8();
+
+    let mut strings = Vec::new();
+    for _ in 0..count {
+        if let Some(value) = read_value(stream)? {
+            strings.push(value);
+        }
+    }
+
+    Ok(strings)
+}
+
+/// Read product version of the node, e.g. `2.9.0` or `2.10.0-SNAPSHOT`.
+/// Revision timestamp and hash are not needed.
+fn read_version(stream: &InStream) -> IgniteResult<String> {
+    let major = stream.read_i8();
+    let minor = stream.read_i8();
+    let maintenance = stream.read_i8();
+    let stage = stream.read_str().unwrap_or_default();
+    let _revision_timestamp = stream.read_i64();
+    IgniteValue::skip(stream)?;
+
+    let mut version = format!("{}.{}.{}", major, minor, maintenance);
+    if !stage.is_empty() {
+        version.push('-');
+        version.push_str(&stage);
+    }
+
+    Ok(version)
+}
+
+/// Read value of a simple type, converting it to string.
+/// Values of other types, e.g. arrays and collections, are skipped.
+fn read_value(stream: &InStream) -> IgniteResult<Option<String>> {
+    let hdr = stream.read_i8();
+
+    let value = match hdr {
+        header::NULL => return Ok(None),
+        header::BYTE => stream.read_i8().to_string(),
+        header::SHORT => stream.read_i16().to_string(),
+        header::INT => stream.read_i32().to_string(),
+        header::LONG => stream.read_i64().to_string(),
+        header::FLOAT => stream.read_f32().to_string(),
+        header::DOUBLE => stream.read_f64().to_string(),
+        header::BOOL => stream.read_bool().to_string(),
+        header::STRING => stream.read_str_raw().into_owned(),
+        header::UUID => Uuid::read_payload(stream).to_string(),
+        header::CHAR => std::char::from_u32(stream.read_i16() as u16 as u32)
+            .unwrap_or_default()
+            .to_string(),
+        _ => {
+            IgniteValue::skip_payload(hdr, stream)?;
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(value))
+}
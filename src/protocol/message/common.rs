@@ -1,12 +1,69 @@
-use crate::protocol::OutStream;
-use crate::protocol_version::ProtocolVersion;
+use crate::ignite_error::{ErrorKind, IgniteResult};
+use crate::protocol::{IgniteHash, InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion, VERSION_1_4_0};
 
 use crate::IgniteError;
 
 /// Type of request message
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum RequestType {
+    ResourceClose = 0,
     Handshake = 1,
+    Heartbeat = 4,
+    GetIdleTimeout = 5,
+    CacheGet = 1000,
+    CachePut = 1001,
+    CacheContainsKey = 1011,
+    CacheRemoveKey = 1016,
+    CacheGetNames = 1050,
     CacheCreateWithName = 1051,
+    CacheGetOrCreateWithName = 1052,
+    CachePartitions = 1101,
+    QueryScan = 2000,
+    QueryScanCursorGetPage = 2001,
+    QuerySqlFields = 2004,
+    QuerySqlFieldsCursorGetPage = 2005,
+    QueryContinuous = 2006,
+    TxStart = 4000,
+    TxEnd = 4001,
+    ClusterGetState = 5000,
+    ClusterChangeState = 5001,
+    ClusterChangeWalState = 5002,
+    ClusterGetWalState = 5003,
+    ClusterGroupGetNodeIds = 5100,
+    ClusterGroupGetNodeInfo = 5101,
+    ClusterGroupGetNodeEndpoints = 5102,
+    ComputeTaskExecute = 6000,
+    ServiceInvoke = 7000,
+    ServiceGetDescriptors = 7001,
+    ServiceGetDescriptor = 7002,
+}
+
+/// Topology version, meaning that the current topology is requested.
+pub const UNKNOWN_TOPOLOGY_VERSION: i64 = -1;
+
+/// Key of the cache operation, which can be used to find the node storing it.
+#[derive(Copy, Clone)]
+pub struct KeyAffinity<'a> {
+    cache_id: i32,
+    key: &'a (dyn IgniteHash + Sync),
+}
+
+impl<'a> KeyAffinity<'a> {
+    /// Make new instance.
+    pub fn new(cache_id: i32, key: &'a (dyn IgniteHash + Sync)) -> Self {
+        Self { cache_id, key }
+    }
+
+    /// Get ID of the cache.
+    pub fn cache_id(&self) -> i32 {
+        self.cache_id
+    }
+
+    /// Get the key.
+    pub fn key(&self) -> &'a (dyn IgniteHash + Sync) {
+        self.key
+    }
 }
 
 /// Trait for a type representing protocol request message
@@ -14,11 +71,34 @@ pub trait Request {
     /// Type of the request.
     const TYPE: RequestType;
 
+    /// Whether the request can be safely sent again if the connection was
+    /// lost before the response was received.
+    const IDEMPOTENT: bool = false;
+
+    /// Feature, which has to be negotiated with the node for the request to be sent.
+    const FEATURE: Option<ProtocolFeature> = None;
+
+    /// Minimal protocol version the node has to accept for the request to be sent.
+    const MIN_VERSION: Option<ProtocolVersion> = None;
+
     /// Type of response if the request was accepted.
     type Response;
 
     /// Write payload of the request message.
-    fn write_payload(&self, out: &mut OutStream, ver: &ProtocolVersion);
+    fn write_payload(&self, out: &OutStream, ver: &ProtocolVersion);
+
+    /// Key of the request, if it is a single key operation, which can be sent
+    /// directly to the node storing the key.
+    fn affinity(&self) -> Option<KeyAffinity<'_>> {
+        None
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        ver: &ProtocolVersion,
+    ) -> IgniteResult<Self::Response>;
 }
 
 /// Response enum.
@@ -31,13 +111,18 @@ pub enum Response<A, R> {
 pub type SimpleResponse<A> = Response<A, GeneralResponseReject>;
 
 /// General response reject.
-#[allow(dead_code)]
+#[derive(Debug)]
 pub struct GeneralResponseReject {
     status: i32,
     error: String,
 }
 
 impl GeneralResponseReject {
+    /// Make new instance.
+    pub fn new(status: i32, error: String) -> Self {
+        Self { status, error }
+    }
+
     /// Get status
     #[allow(dead_code)]
     pub fn status(&self) -> i32 {
@@ -50,15 +135,193 @@ impl GeneralResponseReject {
         &self.error
     }
 
-    /// Move error message out of 
+    /// Move error message out of
     #[allow(dead_code)]
     pub fn decompose(self) -> (i32, String) {
         (self.status, self.error)
     }
 }
 
-impl Into<IgniteError> for GeneralResponseReject {
-    fn into(self) -> IgniteError {
-        IgniteError::new(self.error)
+impl From<GeneralResponseReject> for IgniteError {
+    fn from(rej: GeneralResponseReject) -> Self {
+        IgniteError::new_with_kind(ErrorKind::Server(rej.status), rej.error)
+    }
+}
+
+/// Version of the affinity topology, which changes every time partitions are
+/// redistributed between the nodes of the cluster.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AffinityTopologyVersion {
+    major: i64,
+    minor: i32,
+}
+
+impl AffinityTopologyVersion {
+    /// Make new instance.
+    #[cfg(test)]
+    pub fn new(major: i64, minor: i32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Read version from the stream.
+    pub fn read(stream: &InStream) -> Self {
+        let major = stream.read_i64();
+        let minor = stream.read_i32();
+
+        Self { major, minor }
+    }
+}
+
+/// Response flag: the request has failed.
+const FLAG_ERROR: i16 = 1;
+
+/// Response flag: affinity topology has changed and its new version follows.
+const FLAG_AFFINITY_TOPOLOGY_CHANGED: i16 = 2;
+
+/// Response flag: the message is a notification pushed by the server.
+const FLAG_NOTIFICATION: i16 = 4;
+
+/// Header of every response message, sent before the payload.
+#[derive(Debug)]
+pub struct ResponseHeader {
+    affinity_ver: Option<AffinityTopologyVersion>,
+    reject: Option<GeneralResponseReject>,
+}
+
+impl ResponseHeader {
+    /// Read the header of the response.
+    /// The payload of accepted response can be read from the stream after the call.
+    pub fn read(stream: &InStream, ver: &ProtocolVersion) -> ResponseHeader {
+        // Request ID is only needed to route the raw message, see peek_req_id().
+        let _req_id = stream.read_i64();
+
+        let (failed, affinity_ver) = if *ver >= VERSION_1_4_0 {
+            let flags = stream.read_i16();
+
+            let affinity_ver = if flags & FLAG_AFFINITY_TOPOLOGY_CHANGED != 0 {
+                Some(AffinityTopologyVersion::read(stream))
+            } else {
+                None
+            };
+
+            // Notifications carry the resource ID instead of the request ID,
+            // and the operation code. The operation is known from the resource.
+            if flags & FLAG_NOTIFICATION != 0 {
+                let _op_code = stream.read_i16();
+            }
+
+            (flags & FLAG_ERROR != 0, affinity_ver)
+        } else {
+            (true, None)
+        };
+
+        let reject = if failed {
+            match stream.read_i32() {
+                0 => None,
+                status => {
+                    let error = stream.read_str().unwrap_or_default().into_owned();
+
+                    Some(GeneralResponseReject::new(status, error))
+                }
+            }
+        } else {
+            None
+        };
+
+        ResponseHeader {
+            affinity_ver,
+            reject,
+        }
+    }
+
+    /// Read ID of the request, the response is sent for, from the raw message.
+    pub fn peek_req_id(data: &[u8]) -> i64 {
+        InStream::new(data).read_i64()
+    }
+
+    /// Check if the raw message is a notification pushed by the server.
+    pub fn peek_notification(data: &[u8], ver: &ProtocolVersion) -> bool {
+        if *ver < VERSION_1_4_0 || data.len() < 10 {
+            return false;
+        }
+
+        let flags = InStream::new(&data[8..]).read_i16();
+
+        flags & FLAG_NOTIFICATION != 0
+    }
+
+    /// Get new affinity topology version, if it has changed.
+    pub fn affinity_version(&self) -> Option<AffinityTopologyVersion> {
+        self.affinity_ver
+    }
+
+    /// Convert into response, which is rejected if the request has failed.
+    pub fn into_response(self) -> SimpleResponse<()> {
+        match self.reject {
+            Some(reject) => Response::Reject(reject),
+            None => Response::Accept(()),
+        }
+    }
+}
+
+/// Page of the rows of the query cursor.
+#[derive(Debug)]
+pub struct CursorPage<T> {
+    rows: Vec<T>,
+    has_more: bool,
+}
+
+impl<T> CursorPage<T> {
+    /// Read page of the rows, reading every row with the given function.
+    pub fn read<F>(stream: &InStream, mut read_row: F) -> IgniteResult<Self>
+    where
+        F: FnMut(&InStream) -> IgniteResult<T>,
+    {
+        let row_count = stream.read_i32();
+
+        let mut rows = Vec::with_capacity(row_count.max(0) as usize);
+        for _ in 0..row_count {
+            rows.push(read_row(stream)?);
+        }
+
+        let has_more = stream.read_bool();
+
+        Ok(Self { rows, has_more })
+    }
+
+    /// Get the rows.
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Check if there are more pages on the server.
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Take the rows.
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows
+    }
+}
+
+/// Cache operation flag: the operation is made within the transaction, which ID follows.
+const FLAG_TRANSACTIONAL: i8 = 2;
+
+/// Write header of the cache operation request.
+pub fn write_cache_header(out: &OutStream, cache_id: i32) {
+    write_tx_cache_header(out, cache_id, None);
+}
+
+/// Write header of the cache operation request, which may be made within the transaction.
+pub fn write_tx_cache_header(out: &OutStream, cache_id: i32, tx_id: Option<i32>) {
+    out.write_i32(cache_id);
+
+    match tx_id {
+        Some(tx_id) => {
+            out.write_i8(FLAG_TRANSACTIONAL);
+            out.write_i32(tx_id);
+        }
+        None => out.write_i8(0),
     }
 }
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::protocol::{InStream, OutStream, ProtocolType};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Flag disabling failover of the task jobs to the other nodes.
+pub const FLAG_NO_FAILOVER: i8 = 1;
+
+/// Flag disabling caching of the job results on the nodes.
+pub const FLAG_NO_RESULT_CACHE: i8 = 2;
+
+/// Request sent to execute the compute task deployed on the server.
+/// The response is the ID of the task, which the result is pushed for.
+pub struct ComputeTaskExecuteReq<'a> {
+    node_ids: Option<&'a [Uuid]>,
+    flags: i8,
+    timeout: Option<Duration>,
+    task_name: &'a str,
+    arg: &'a IgniteValue,
+}
+
+impl<'a> ComputeTaskExecuteReq<'a> {
+    /// Create new instance of the request.
+    /// None means the task can be executed on all the nodes of the cluster.
+    pub fn new(
+        node_ids: Option<&'a [Uuid]>,
+        flags: i8,
+        timeout: Option<Duration>,
+        task_name: &'a str,
+        arg: &'a IgniteValue,
+    ) -> Self {
+        Self {
+            node_ids,
+            flags,
+            timeout,
+            task_name,
+            arg,
+        }
+    }
+}
+
+impl<'a> Request for ComputeTaskExecuteReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ComputeTaskExecute;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ExecuteTaskByName);
+
+    /// Response type.
+    type Response = i64;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        let node_ids = self.node_ids.unwrap_or(&[]);
+
+        // Zero nodes means all the nodes.
+        out.write_i32(node_ids.len() as i32);
+        for id in node_ids {
+            id.write_payload(out);
+        }
+
+        out.write_i8(self.flags);
+        // Zero means no timeout.
+        out.write_i64(self.timeout.map_or(0, |t| t.as_millis() as i64));
+        out.write_str(self.task_name);
+
+        self.arg.write(out);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<i64> {
+        Ok(stream.read_i64())
+    }
+}
+
+/// Read payload of the notification, pushed by the server once the task is finished.
+pub fn read_task_result(stream: &InStream) -> IgniteResult<IgniteValue> {
+    IgniteValue::read(stream)
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to get the timeout, after which the node closes idle connection.
+pub struct GetIdleTimeoutReq;
+
+impl GetIdleTimeoutReq {
+    /// Create new instance of the request.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for GetIdleTimeoutReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::GetIdleTimeout;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type. None if idle connections are not closed.
+    type Response = Option<Duration>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, _out: &OutStream, _ver: &ProtocolVersion) {}
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<Option<Duration>> {
+        let millis = stream.read_i64();
+
+        if millis <= 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(Duration::from_millis(millis as u64)))
+    }
+}
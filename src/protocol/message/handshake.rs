@@ -1,9 +1,18 @@
-use crate::protocol::{InStream, Readable};
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::protocol::{header, read_full, write_full, InStream, Readable};
 use crate::protocol::{OutStream, Writable};
-use crate::protocol_version::ProtocolVersion;
+use crate::protocol_version::SUPPORTED_FEATURES;
+use crate::protocol_version::{features_mask, ProtocolVersion};
+use crate::protocol_version::{VERSION_1_4_0, VERSION_1_7_0};
 
 use super::{RequestType, Response};
 
+/// Status of the handshake rejected because of the invalid credentials.
+const STATUS_AUTH_FAILED: i32 = 2000;
+
 /// Type of client. There is only one type of client
 /// we are interested in - Thin.
 enum ClientType {
@@ -17,12 +26,23 @@ pub struct HandshakeReq<'a> {
     ver: ProtocolVersion,
     user: &'a str,
     pass: &'a str,
+    attributes: &'a HashMap<String, String>,
 }
 
 impl<'a> HandshakeReq<'a> {
     /// Make new instance
-    pub fn new(ver: ProtocolVersion, user: &'a str, pass: &'a str) -> Self {
-        HandshakeReq { ver, user, pass }
+    pub fn new(
+        ver: ProtocolVersion,
+        user: &'a str,
+        pass: &'a str,
+        attributes: &'a HashMap<String, String>,
+    ) -> Self {
+        HandshakeReq {
+            ver,
+            user,
+            pass,
+            attributes,
+        }
     }
 }
 
@@ -34,6 +54,13 @@ impl<'a> Writable for HandshakeReq<'a> {
 
         out.write_i8(ClientType::Thin as i8);
 
+        if self.ver >= VERSION_1_7_0 {
+            out.write_i8(header::BYTE_ARRAY);
+            out.write_u8_array_raw(features_mask(&SUPPORTED_FEATURES));
+
+            write_full(self.attributes, out);
+        }
+
         out.write_str(self.user);
         out.write_str(self.pass);
     }
@@ -45,36 +72,82 @@ impl<'a> Writable for HandshakeReq<'a> {
 pub struct HandshakeReject {
     ver: ProtocolVersion,
     error: String,
+    status: Option<i32>,
 }
 
 impl HandshakeReject {
     /// Make new instance.
-    fn new(ver: ProtocolVersion, error: String) -> Self {
-        HandshakeReject { ver, error }
+    fn new(ver: ProtocolVersion, error: String, status: Option<i32>) -> Self {
+        HandshakeReject { ver, error, status }
     }
 
     /// Get error
     pub fn get_error(&self) -> &str {
         &self.error
     }
+
+    /// Check if the handshake was rejected because of the invalid credentials.
+    pub fn is_auth_failure(&self) -> bool {
+        self.status == Some(STATUS_AUTH_FAILED)
+    }
 }
 
-/// Handshake response.
-pub type HandshakeRsp = Response<(), HandshakeReject>;
+/// Handshake accept.
+#[derive(Debug)]
+pub struct HandshakeAccept {
+    node_id: Option<Uuid>,
+    features: Vec<u8>,
+}
 
-impl Readable for HandshakeRsp {
-    type Item = HandshakeRsp;
+impl HandshakeAccept {
+    /// Get bitmask of the features, supported by both the node and the client.
+    /// Empty before version 1.7.0.
+    pub fn features(&self) -> &[u8] {
+        &self.features
+    }
 
-    fn read(stream: &InStream) -> HandshakeRsp {
+    /// Get ID of the node the connection is established with.
+    /// Only known since version 1.4.0.
+    pub fn node_id(&self) -> Option<Uuid> {
+        self.node_id
+    }
+}
+
+/// Handshake response.
+pub type HandshakeRsp = Response<HandshakeAccept, HandshakeReject>;
+
+impl HandshakeRsp {
+    /// Read the response to the handshake request of the given version.
+    pub fn read(stream: &InStream, ver: &ProtocolVersion) -> HandshakeRsp {
         let accepted = stream.read_bool();
 
         if accepted {
-            return Response::Accept(());
+            let mut features = Vec::new();
+            if *ver >= VERSION_1_7_0 {
+                let _header = stream.read_i8();
+                let len = stream.read_i32();
+                features.extend_from_slice(stream.read_bytes(len as usize));
+            }
+
+            let node_id = if *ver >= VERSION_1_4_0 {
+                read_full::<Uuid, Uuid>(stream)
+            } else {
+                None
+            };
+
+            return Response::Accept(HandshakeAccept { node_id, features });
         }
 
         let ver = ProtocolVersion::read(stream);
         let err = stream.read_str().unwrap_or_default();
 
-        Response::Reject(HandshakeReject::new(ver, err.into_owned()))
+        // Status is only sent by the newer nodes.
+        let status = if stream.remaining() >= 4 {
+            Some(stream.read_i32())
+        } else {
+            None
+        };
+
+        Response::Reject(HandshakeReject::new(ver, err.into_owned(), status))
     }
 }
@@ -0,0 +1,34 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to keep the idle connection alive.
+pub struct HeartbeatReq;
+
+impl HeartbeatReq {
+    /// Create new instance of the request.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for HeartbeatReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::Heartbeat;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, _out: &OutStream, _ver: &ProtocolVersion) {}
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
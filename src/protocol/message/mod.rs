@@ -1,7 +1,63 @@
+mod cache_contains_key;
+mod cache_create_with_name;
+mod cache_get;
+mod cache_get_names;
+mod cache_get_or_create_with_name;
+mod cache_partitions;
+mod cache_put;
+mod cache_remove_key;
+mod cluster_change_state;
+mod cluster_change_wal_state;
+mod cluster_get_state;
+mod cluster_get_wal_state;
+mod cluster_group_get_node_endpoints;
+mod cluster_group_get_node_ids;
+mod cluster_group_get_node_info;
 mod common;
+mod compute_task_execute;
+mod get_idle_timeout;
 mod handshake;
-mod cache_create_with_name;
+mod heartbeat;
+mod query_continuous;
+mod query_scan;
+mod query_scan_cursor_get_page;
+mod query_sql_fields;
+mod query_sql_fields_cursor_get_page;
+mod resource_close;
+mod service_get_descriptors;
+mod service_invoke;
+mod tx_end;
+mod tx_start;
 
-pub use common::{Request, RequestType, Response, SimpleResponse, GeneralResponseReject};
-pub use handshake::{HandshakeReq, HandshakeRsp};
-pub use cache_create_with_name::CacheCreateWithNameReq;
\ No newline at end of file
+pub use cache_contains_key::CacheContainsKeyReq;
+pub use cache_create_with_name::CacheCreateWithNameReq;
+pub use cache_get::CacheGetReq;
+pub use cache_get_names::CacheGetNamesReq;
+pub use cache_get_or_create_with_name::CacheGetOrCreateWithNameReq;
+pub use cache_partitions::{CachePartitionMap, CachePartitionsReq};
+pub use cache_put::CachePutReq;
+pub use cache_remove_key::CacheRemoveKeyReq;
+pub use cluster_change_state::ClusterChangeStateReq;
+pub use cluster_change_wal_state::ClusterChangeWalStateReq;
+pub use cluster_get_state::ClusterGetStateReq;
+pub use cluster_get_wal_state::ClusterGetWalStateReq;
+pub use cluster_group_get_node_endpoints::ClusterGroupGetNodeEndpointsReq;
+pub use cluster_group_get_node_ids::{ClusterGroupFilter, ClusterGroupGetNodeIdsReq};
+pub use cluster_group_get_node_info::ClusterGroupGetNodeInfoReq;
+pub use common::{AffinityTopologyVersion, CursorPage, KeyAffinity};
+pub use common::{Request, RequestType, Response, ResponseHeader};
+pub use compute_task_execute::{read_task_result, ComputeTaskExecuteReq};
+pub use compute_task_execute::{FLAG_NO_FAILOVER, FLAG_NO_RESULT_CACHE};
+pub use get_idle_timeout::GetIdleTimeoutReq;
+pub use handshake::{HandshakeAccept, HandshakeReq, HandshakeRsp};
+pub use heartbeat::HeartbeatReq;
+pub use query_continuous::{read_entry_events, QueryContinuousReq};
+pub use query_scan::{QueryScanReq, QueryScanRsp};
+pub use query_scan_cursor_get_page::QueryScanCursorGetPageReq;
+pub use query_sql_fields::{QuerySqlFieldsReq, QuerySqlFieldsRsp};
+pub use query_sql_fields_cursor_get_page::QuerySqlFieldsCursorGetPageReq;
+pub use resource_close::ResourceCloseReq;
+pub use service_get_descriptors::{ServiceGetDescriptorReq, ServiceGetDescriptorsReq};
+pub use service_invoke::ServiceInvokeReq;
+pub use tx_end::TxEndReq;
+pub use tx_start::TxStartReq;
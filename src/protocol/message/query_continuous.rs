@@ -0,0 +1,82 @@
+use crate::cache_entry_event::{CacheEntryEvent, CacheEntryEventType};
+use crate::continuous_query::ContinuousQuery;
+use crate::ignite_error::{IgniteError, IgniteResult};
+use crate::protocol::{header, try_read_full, write_full, InStream, OutStream, ProtocolType};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Platform of the filter object, telling the server it is a Java class.
+const FILTER_PLATFORM_JAVA: i8 = 1;
+
+/// Request sent to start continuous query over the cache.
+/// The response is the ID of the query, which the events are pushed for.
+pub struct QueryContinuousReq<'a> {
+    cache_id: i32,
+    query: &'a ContinuousQuery,
+}
+
+impl<'a> QueryContinuousReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(cache_id: i32, query: &'a ContinuousQuery) -> Self {
+        Self { cache_id, query }
+    }
+}
+
+impl<'a> Request for QueryContinuousReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::QueryContinuous;
+
+    /// Response type.
+    type Response = i64;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        let query = self.query;
+
+        write_cache_header(out, self.cache_id);
+
+        out.write_i32(query.get_buffer_size());
+        out.write_i64(query.get_time_interval().as_millis() as i64);
+        out.write_bool(query.get_include_expired());
+
+        match query.get_filter() {
+            Some(filter) => {
+                write_full(filter, out);
+                out.write_i8(FILTER_PLATFORM_JAVA);
+            }
+            None => out.write_i8(header::NULL),
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<i64> {
+        Ok(stream.read_i64())
+    }
+}
+
+/// Read payload of the event notification, pushed by the server for the continuous query.
+pub fn read_entry_events<K, V>(stream: &InStream) -> IgniteResult<Vec<CacheEntryEvent<K, V>>>
+where
+    K: ProtocolType<Item = K>,
+    V: ProtocolType<Item = V>,
+{
+    let count = stream.read_i32();
+
+    let mut events = Vec::with_capacity(count.max(0) as usize);
+    for _ in 0..count {
+        let key = try_read_full::<K, K>(stream)?
+            .ok_or_else(|| IgniteError::new("Continuous query returned null key"))?;
+
+        let old_value = try_read_full::<V, V>(stream)?;
+        let new_value = try_read_full::<V, V>(stream)?;
+
+        let code = stream.read_i8();
+        let event_type = CacheEntryEventType::from_code(code)
+            .ok_or_else(|| IgniteError::new(format!("Unknown event type: {}", code)))?;
+
+        events.push(CacheEntryEvent::new(key, old_value, new_value, event_type));
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{header, write_full, InStream, OutStream, ProtocolType};
+use crate::protocol_version::ProtocolVersion;
+use crate::scan_query::ScanQuery;
+
+use super::common::*;
+use super::query_scan_cursor_get_page::{read_scan_page, ScanPage};
+
+/// Platform of the filter object, telling the server it is a Java class.
+const FILTER_PLATFORM_JAVA: i8 = 1;
+
+/// Partition number meaning that all the partitions are scanned.
+const ALL_PARTITIONS: i32 = -1;
+
+/// Request sent to execute scan query over the cache.
+pub struct QueryScanReq<'a, K, V> {
+    cache_id: i32,
+    query: &'a ScanQuery,
+    _e: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, K, V> QueryScanReq<'a, K, V> {
+    /// Create new instance of the request.
+    pub fn new(cache_id: i32, query: &'a ScanQuery) -> Self {
+        Self {
+            cache_id,
+            query,
+            _e: PhantomData,
+        }
+    }
+}
+
+/// Response to the scan query: cursor and the first page of the entries.
+#[derive(Debug)]
+pub struct QueryScanRsp<K, V> {
+    cursor_id: i64,
+    page: ScanPage<K, V>,
+}
+
+impl<K, V> QueryScanRsp<K, V> {
+    /// Get ID of the cursor on the server.
+    pub fn cursor_id(&self) -> i64 {
+        self.cursor_id
+    }
+
+    /// Take the first page.
+    pub fn into_page(self) -> ScanPage<K, V> {
+        self.page
+    }
+}
+
+impl<'a, K, V> Request for QueryScanReq<'a, K, V>
+where
+    K: ProtocolType<Item = K>,
+    V: ProtocolType<Item = V>,
+{
+    /// Request type.
+    const TYPE: RequestType = RequestType::QueryScan;
+
+    /// Response type.
+    type Response = QueryScanRsp<K, V>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        let query = self.query;
+
+        write_cache_header(out, self.cache_id);
+
+        match query.get_filter() {
+            Some(filter) => {
+                write_full(filter, out);
+                out.write_i8(FILTER_PLATFORM_JAVA);
+            }
+            None => out.write_i8(header::NULL),
+        }
+
+        out.write_i32(query.get_page_size());
+        out.write_i32(query.get_partition().unwrap_or(ALL_PARTITIONS));
+        out.write_bool(query.get_local());
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<QueryScanRsp<K, V>> {
+        let cursor_id = stream.read_i64();
+        let page = read_scan_page(stream)?;
+
+        Ok(QueryScanRsp { cursor_id, page })
+    }
+}
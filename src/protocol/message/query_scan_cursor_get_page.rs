@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+use crate::ignite_error::{IgniteError, IgniteResult};
+use crate::protocol::{try_read_full, InStream, OutStream, ProtocolType};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Page of the entries of the scan query.
+pub type ScanPage<K, V> = CursorPage<(K, V)>;
+
+/// Read page of the cache entries.
+pub fn read_scan_page<K, V>(stream: &InStream) -> IgniteResult<ScanPage<K, V>>
+where
+    K: ProtocolType<Item = K>,
+    V: ProtocolType<Item = V>,
+{
+    CursorPage::read(stream, |stream| {
+        let key = try_read_full::<K, K>(stream)?;
+        let value = try_read_full::<V, V>(stream)?;
+
+        match (key, value) {
+            (Some(key), Some(value)) => Ok((key, value)),
+            _ => Err(IgniteError::new("Scan query returned null entry")),
+        }
+    })
+}
+
+/// Request sent to get the next page of the scan query.
+pub struct QueryScanCursorGetPageReq<K, V> {
+    cursor_id: i64,
+    _e: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> QueryScanCursorGetPageReq<K, V> {
+    /// Create new instance of the request.
+    pub fn new(cursor_id: i64) -> Self {
+        Self {
+            cursor_id,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Request for QueryScanCursorGetPageReq<K, V>
+where
+    K: ProtocolType<Item = K>,
+    V: ProtocolType<Item = V>,
+{
+    /// Request type.
+    const TYPE: RequestType = RequestType::QueryScanCursorGetPage;
+
+    /// Response type.
+    type Response = ScanPage<K, V>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i64(self.cursor_id);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<ScanPage<K, V>> {
+        read_scan_page(stream)
+    }
+}
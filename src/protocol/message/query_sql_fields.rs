@@ -0,0 +1,114 @@
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+use crate::sql_fields_query::SqlFieldsQuery;
+
+use super::common::*;
+use super::query_sql_fields_cursor_get_page::{read_fields_page, FieldsPage};
+
+/// Request sent to execute SQL fields query.
+pub struct QuerySqlFieldsReq<'a> {
+    query: &'a SqlFieldsQuery,
+}
+
+impl<'a> QuerySqlFieldsReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(query: &'a SqlFieldsQuery) -> Self {
+        Self { query }
+    }
+}
+
+/// Response to the SQL fields query: cursor and the first page of the rows.
+#[derive(Debug)]
+pub struct QuerySqlFieldsRsp {
+    cursor_id: i64,
+    columns: Vec<String>,
+    column_count: usize,
+    page: FieldsPage,
+}
+
+impl QuerySqlFieldsRsp {
+    /// Get ID of the cursor on the server.
+    pub fn cursor_id(&self) -> i64 {
+        self.cursor_id
+    }
+
+    /// Get number of columns.
+    pub fn column_count(&self) -> usize {
+        self.column_count
+    }
+
+    /// Split into column names and the first page.
+    pub fn into_parts(self) -> (Vec<String>, FieldsPage) {
+        (self.columns, self.page)
+    }
+}
+
+impl<'a> Request for QuerySqlFieldsReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::QuerySqlFields;
+
+    /// Response type.
+    type Response = QuerySqlFieldsRsp;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        let query = self.query;
+
+        // Query is not bound to a cache, the schema is used instead.
+        write_cache_header(out, 0);
+
+        match query.get_schema() {
+            Some(schema) => out.write_str(schema),
+            None => IgniteValue::Null.write(out),
+        }
+
+        out.write_i32(query.get_page_size());
+        out.write_i32(query.get_max_rows());
+        out.write_str(query.get_sql());
+
+        out.write_i32(query.get_args().len() as i32);
+        for arg in query.get_args() {
+            arg.write(out);
+        }
+
+        out.write_i8(query.get_statement_type() as i8);
+        out.write_bool(query.get_distributed_joins());
+        out.write_bool(query.get_local());
+        // Replicated only, enforce join order and collocated flags.
+        out.write_bool(false);
+        out.write_bool(false);
+        out.write_bool(false);
+        out.write_bool(query.get_lazy());
+        out.write_i64(query.get_timeout().map_or(0, |t| t.as_millis() as i64));
+        out.write_bool(query.get_include_field_names());
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<QuerySqlFieldsRsp> {
+        let cursor_id = stream.read_i64();
+        let column_count = stream.read_i32() as usize;
+
+        let columns = if self.query.get_include_field_names() {
+            (0..column_count)
+                .map(|_| stream.read_str().unwrap_or_default().into_owned())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let page = read_fields_page(stream, column_count)?;
+
+        Ok(QuerySqlFieldsRsp {
+            cursor_id,
+            columns,
+            column_count,
+            page,
+        })
+    }
+}
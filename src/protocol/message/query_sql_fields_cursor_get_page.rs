@@ -0,0 +1,52 @@
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Page of the rows of the SQL fields query.
+pub type FieldsPage = CursorPage<Vec<IgniteValue>>;
+
+/// Read page of the rows with the given number of columns.
+pub fn read_fields_page(stream: &InStream, column_count: usize) -> IgniteResult<FieldsPage> {
+    CursorPage::read(stream, |stream| {
+        (0..column_count)
+            .map(|_| IgniteValue::read(stream))
+            .collect()
+    })
+}
+
+/// Request sent to get the next page of the SQL fields query.
+pub struct QuerySqlFieldsCursorGetPageReq {
+    cursor_id: i64,
+    column_count: usize,
+}
+
+impl QuerySqlFieldsCursorGetPageReq {
+    /// Create new instance of the request.
+    pub fn new(cursor_id: i64, column_count: usize) -> Self {
+        Self {
+            cursor_id,
+            column_count,
+        }
+    }
+}
+
+impl Request for QuerySqlFieldsCursorGetPageReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::QuerySqlFieldsCursorGetPage;
+
+    /// Response type.
+    type Response = FieldsPage;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i64(self.cursor_id);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<FieldsPage> {
+        read_fields_page(stream, self.column_count)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::ProtocolVersion;
+
+use super::common::*;
+
+/// Request sent to release the resource held by the server, e.g. query cursor.
+pub struct ResourceCloseReq {
+    resource_id: i64,
+}
+
+impl ResourceCloseReq {
+    /// Create new instance of the request.
+    pub fn new(resource_id: i64) -> Self {
+        Self { resource_id }
+    }
+}
+
+impl Request for ResourceCloseReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ResourceClose;
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i64(self.resource_id);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use uuid::Uuid;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{read_full, InStream, OutStream};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+use crate::service_descriptor::{ServiceDescriptor, ServicePlatform};
+use crate::IgniteError;
+
+use super::common::*;
+
+/// Request sent to get descriptors of all the services deployed in the cluster.
+pub struct ServiceGetDescriptorsReq;
+
+impl ServiceGetDescriptorsReq {
+    /// Create new instance of the request.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for ServiceGetDescriptorsReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ServiceGetDescriptors;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::GetServiceDescriptors);
+
+    /// Response type.
+    type Response = Vec<ServiceDescriptor>;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, _out: &OutStream, _ver: &ProtocolVersion) {}
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<Vec<ServiceDescriptor>> {
+        let count = stream.read_i32();
+
+        (0..count).map(|_| read_descriptor(stream)).collect()
+    }
+}
+
+/// Request sent to get descriptor of the service by name.
+pub struct ServiceGetDescriptorReq<'a> {
+    name: &'a str,
+}
+
+impl<'a> ServiceGetDescriptorReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(name: &'a str) -> Self {
+        Self { name }
+    }
+}
+
+impl<'a> Request for ServiceGetDescriptorReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ServiceGetDescriptor;
+
+    /// The request does not change anything on the server.
+    const IDEMPOTENT: bool = true;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::GetServiceDescriptors);
+
+    /// Response type.
+    type Response = ServiceDescriptor;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_str(self.name);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<ServiceDescriptor> {
+        read_descriptor(stream)
+    }
+}
+
+/// Read descriptor of a single service.
+fn read_descriptor(stream: &InStream) -> IgniteResult<ServiceDescriptor> {
+    let name = stream.read_str().unwrap_or_default().into_owned();
+    let service_class = stream.read_str().unwrap_or_default().into_owned();
+    let total_count = stream.read_i32();
+    let max_per_node_count = stream.read_i32();
+    let cache_name = stream.read_str().map(|s| s.into_owned());
+    let origin_node_id = read_full::<Uuid, Uuid>(stream);
+
+    let code = stream.read_i8();
+    let platform = ServicePlatform::from_code(code)
+        .ok_or_else(|| IgniteError::new(format!("Unknown service platform: {}", code)))?;
+
+    Ok(ServiceDescriptor::new(
+        name,
+        service_class,
+        total_count,
+        max_per_node_count,
+        cache_name,
+        origin_node_id,
+        platform,
+    ))
+}
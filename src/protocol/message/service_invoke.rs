@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::binary_object::BinaryObject;
+use crate::ignite_error::IgniteResult;
+use crate::ignite_value::IgniteValue;
+use crate::protocol::{header, InStream, OutStream, ProtocolType};
+use crate::protocol_version::{ProtocolFeature, ProtocolVersion};
+
+use super::common::*;
+
+/// Flag telling the server to pass binary objects to the service as they are.
+const FLAG_KEEP_BINARY: i8 = 1;
+
+/// Flag telling the server the types of the parameters are sent with the arguments.
+const FLAG_HAS_PARAMETER_TYPES: i8 = 2;
+
+/// Request sent to invoke method of the service deployed in the cluster.
+/// The response is the value returned by the method.
+pub struct ServiceInvokeReq<'a> {
+    name: &'a str,
+    method: &'a str,
+    args: &'a [IgniteValue],
+    param_types: Option<&'a [&'a str]>,
+    node_ids: Option<&'a [Uuid]>,
+    timeout: Option<Duration>,
+}
+
+impl<'a> ServiceInvokeReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(name: &'a str, method: &'a str, args: &'a [IgniteValue]) -> Self {
+        Self {
+            name,
+            method,
+            args,
+            param_types: None,
+            node_ids: None,
+            timeout: None,
+        }
+    }
+
+    /// Set Java types of the parameters, one per argument.
+    pub fn with_param_types(self, param_types: Option<&'a [&'a str]>) -> Self {
+        Self {
+            param_types,
+            ..self
+        }
+    }
+
+    /// Set nodes the service can be invoked on. None means all the nodes.
+    pub fn with_node_ids(self, node_ids: Option<&'a [Uuid]>) -> Self {
+        Self { node_ids, ..self }
+    }
+
+    /// Set timeout of the invocation on the server.
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+}
+
+impl<'a> Request for ServiceInvokeReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::ServiceInvoke;
+
+    /// Feature the node has to support.
+    const FEATURE: Option<ProtocolFeature> = Some(ProtocolFeature::ServiceInvoke);
+
+    /// Response type.
+    type Response = IgniteValue;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_str(self.name);
+
+        let mut flags = FLAG_KEEP_BINARY;
+        if self.param_types.is_some() {
+            flags |= FLAG_HAS_PARAMETER_TYPES;
+        }
+        out.write_i8(flags);
+
+        // Zero means no timeout.
+        out.write_i64(self.timeout.map_or(0, |t| t.as_millis() as i64));
+
+        // Zero nodes means all the nodes.
+        let node_ids = self.node_ids.unwrap_or(&[]);
+        out.write_i32(node_ids.len() as i32);
+        for id in node_ids {
+            id.write_payload(out);
+        }
+
+        out.write_str(self.method);
+
+        out.write_i32(self.args.len() as i32);
+        for (i, arg) in self.args.iter().enumerate() {
+            if let Some(param_types) = self.param_types {
+                out.write_i32(param_types.get(i).map_or(0, |name| param_type_id(name)));
+            }
+            arg.write(out);
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(
+        &self,
+        stream: &InStream,
+        _ver: &ProtocolVersion,
+    ) -> IgniteResult<IgniteValue> {
+        IgniteValue::read(stream)
+    }
+}
+
+/// Get ID of the Java type, the way the server resolves the method parameters.
+/// Primitive types and their wrappers share the ID of the type header.
+fn param_type_id(name: &str) -> i32 {
+    let hdr = match name {
+        "byte" | "java.lang.Byte" => header::BYTE,
+        "short" | "java.lang.Short" => header::SHORT,
+        "int" | "java.lang.Integer" => header::INT,
+        "long" | "java.lang.Long" => header::LONG,
+        "float" | "java.lang.Float" => header::FLOAT,
+        "double" | "java.lang.Double" => header::DOUBLE,
+        "char" | "java.lang.Character" => header::CHAR,
+        "boolean" | "java.lang.Boolean" => header::BOOL,
+        "java.lang.String" => header::STRING,
+        "java.util.UUID" => header::UUID,
+        "java.util.Date" => header::DATE,
+        "byte[]" => header::BYTE_ARRAY,
+        "java.math.BigDecimal" => header::DECIMAL,
+        "java.sql.Timestamp" => header::TIMESTAMP,
+        "java.sql.Time" => header::TIME,
+        _ => return BinaryObject::type_id_of(name),
+    };
+
+    i32::from(hdr)
+}
@@ -0,0 +1,40 @@
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{InStream, OutStream};
+use crate::protocol_version::{ProtocolVersion, VERSION_1_5_0};
+
+use super::common::*;
+
+/// Request sent to commit or roll back the transaction.
+pub struct TxEndReq {
+    tx_id: i32,
+    committed: bool,
+}
+
+impl TxEndReq {
+    /// Create new instance of the request.
+    pub fn new(tx_id: i32, committed: bool) -> Self {
+        Self { tx_id, committed }
+    }
+}
+
+impl Request for TxEndReq {
+    /// Request type.
+    const TYPE: RequestType = RequestType::TxEnd;
+
+    /// Version the node has to accept.
+    const MIN_VERSION: Option<ProtocolVersion> = Some(VERSION_1_5_0);
+
+    /// Response type.
+    type Response = ();
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i32(self.tx_id);
+        out.write_bool(self.committed);
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, _stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<()> {
+        Ok(())
+    }
+}
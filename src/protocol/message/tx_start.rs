@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use crate::ignite_error::IgniteResult;
+use crate::protocol::{header, InStream, OutStream};
+use crate::protocol_version::{ProtocolVersion, VERSION_1_5_0};
+use crate::transactions::{TransactionConcurrency, TransactionIsolation};
+
+use super::common::*;
+
+/// Request sent to start the transaction, bound to the connection.
+/// The response is the ID of the transaction.
+pub struct TxStartReq<'a> {
+    concurrency: TransactionConcurrency,
+    isolation: TransactionIsolation,
+    timeout: Option<Duration>,
+    label: Option<&'a str>,
+}
+
+impl<'a> TxStartReq<'a> {
+    /// Create new instance of the request.
+    pub fn new(
+        concurrency: TransactionConcurrency,
+        isolation: TransactionIsolation,
+        timeout: Option<Duration>,
+        label: Option<&'a str>,
+    ) -> Self {
+        Self {
+            concurrency,
+            isolation,
+            timeout,
+            label,
+        }
+    }
+}
+
+impl<'a> Request for TxStartReq<'a> {
+    /// Request type.
+    const TYPE: RequestType = RequestType::TxStart;
+
+    /// Version the node has to accept.
+    const MIN_VERSION: Option<ProtocolVersion> = Some(VERSION_1_5_0);
+
+    /// Response type.
+    type Response = i32;
+
+    /// Write payload of the request message.
+    fn write_payload(&self, out: &OutStream, _ver: &ProtocolVersion) {
+        out.write_i8(self.concurrency as i8);
+        out.write_i8(self.isolation as i8);
+        // Zero means no timeout.
+        out.write_i64(self.timeout.map_or(0, |t| t.as_millis() as i64));
+
+        match self.label {
+            Some(label) => out.write_str(label),
+            None => out.write_i8(header::NULL),
+        }
+    }
+
+    /// Read payload of the response message.
+    fn read_response(&self, stream: &InStream, _ver: &ProtocolVersion) -> IgniteResult<i32> {
+        Ok(stream.read_i32())
+    }
+}
@@ -1,4 +1,5 @@
 mod growing_buffer;
+mod ignite_hash;
 mod in_stream;
 mod out_stream;
 mod protocol_type;
@@ -9,7 +10,9 @@ pub mod header;
 pub mod message;
 pub mod utils;
 
+pub use self::ignite_hash::IgniteHash;
 pub use self::in_stream::{InStream, Readable};
 pub use self::out_stream::{OutStream, Writable};
+pub use self::protocol_type::{read_full, try_read_full, write_full, ProtocolType};
 pub use self::request_encoder::RequestEncoder;
 pub use self::response_decoder::ResponseDecoder;
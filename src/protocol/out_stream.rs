@@ -91,6 +91,31 @@ impl OutStream {
         }
     }
 
+    /// Write bool value to a stream
+    pub fn write_bool(&self, value: bool) {
+        self.write_i8(value as i8);
+    }
+
+    /// Write f32 value to a stream
+    pub fn write_f32(&self, value: f32) {
+        self.write_i32(value.to_bits() as i32);
+    }
+
+    /// Write f64 value to a stream
+    pub fn write_f64(&self, value: f64) {
+        self.write_i64(value.to_bits() as i64);
+    }
+
+    /// Write bytes to a stream as is, without the length
+    pub fn write_bytes(&self, value: &[u8]) {
+        self.ensure_capacity(value.len());
+
+        // It is safe as safety check was performed before
+        unsafe {
+            self.unsafe_write_bytes(value);
+        }
+    }
+
     /// Write string value to a stream
     pub fn write_str<S: AsRef<str>>(&self, value: S) {
         let value0 = value.as_ref().as_bytes();
@@ -125,7 +150,7 @@ impl OutStream {
 
     /// Reserve a space in a stream for a i32 value.
     #[allow(dead_code)]
-    pub fn reserve_i32(&self) -> ReservedI32 {
+    pub fn reserve_i32(&self) -> ReservedI32<'_> {
         self.ensure_capacity(4);
 
         let reserved = ReservedI32::new(self);
@@ -137,7 +162,7 @@ impl OutStream {
 
     /// Reserve a space in a stream for a i32 value which will be lately set to
     /// a length of the block of data.
-    pub fn reserve_len(&self) -> ReservedLen {
+    pub fn reserve_len(&self) -> ReservedLen<'_> {
         self.ensure_capacity(4);
 
         let reserved = ReservedLen::new(self);
@@ -148,6 +173,7 @@ impl OutStream {
     }
 
     /// Write i8 value without capacity checks
+    #[allow(clippy::identity_op)]
     unsafe fn unsafe_write_i8(&self, value: i8) {
         let dst = self.mut_ptr_to_free_space();
 
@@ -360,6 +386,7 @@ fn test_write_i64() {
 }
 
 #[test]
+#[allow(clippy::char_lit_as_u8)]
 fn test_write_str_raw() {
     let out = OutStream::new();
 
@@ -391,6 +418,7 @@ fn test_write_str_raw() {
 }
 
 #[test]
+#[allow(clippy::char_lit_as_u8)]
 fn test_write_str() {
     let out = OutStream::new();
 
@@ -1,5 +1,13 @@
 extern crate paste;
 
+use std::any;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use uuid::Uuid;
+
+use crate::ignite_error::{IgniteError, IgniteResult};
+
 use super::header;
 use super::{InStream, OutStream};
 
@@ -10,20 +18,23 @@ pub trait ProtocolType {
 
     fn write_payload(&self, stream: &OutStream);
     fn read_payload(stream: &InStream) -> Self::Item;
+
+    /// Read payload, failing instead of panicking if the value can not be read.
+    fn try_read_payload(stream: &InStream) -> IgniteResult<Self::Item> {
+        Ok(Self::read_payload(stream))
+    }
 }
 
 /// Write full value
-#[allow(dead_code)]
-pub fn write_full<T, I>(val: T, stream: &OutStream)
+pub fn write_full<T, I>(val: &T, stream: &OutStream)
 where
-    T: ProtocolType<Item = I>,
+    T: ProtocolType<Item = I> + ?Sized,
 {
     stream.write_i8(T::HEADER);
     val.write_payload(stream);
 }
 
 /// Read full value
-#[allow(dead_code)]
 pub fn read_full<T, I>(stream: &InStream) -> Option<I>
 where
     T: ProtocolType<Item = I>,
@@ -39,6 +50,27 @@ where
     }
 }
 
+/// Read full value, failing if the value of another type is read,
+/// e.g. the one stored in the cache by other client.
+pub fn try_read_full<T, I>(stream: &InStream) -> IgniteResult<Option<I>>
+where
+    T: ProtocolType<Item = I>,
+{
+    let header = stream.read_i8();
+
+    if header == T::HEADER {
+        Ok(Some(T::try_read_payload(stream)?))
+    } else if header == header::NULL {
+        Ok(None)
+    } else {
+        Err(IgniteError::new(format!(
+            "Value of type {} is expected, got value with type header {}",
+            any::type_name::<T>(),
+            header
+        )))
+    }
+}
+
 macro_rules! impl_proto_for_primitive {
     ($ttype:ty, $header:expr) => {
         impl ProtocolType for $ttype {
@@ -64,8 +96,11 @@ impl_proto_for_primitive!(i8, header::BYTE);
 impl_proto_for_primitive!(i16, header::SHORT);
 impl_proto_for_primitive!(i32, header::INT);
 impl_proto_for_primitive!(i64, header::LONG);
+impl_proto_for_primitive!(f32, header::FLOAT);
+impl_proto_for_primitive!(f64, header::DOUBLE);
+impl_proto_for_primitive!(bool, header::BOOL);
 
-impl<'a> ProtocolType for &'a str {
+impl ProtocolType for &str {
     type Item = String;
     const HEADER: i8 = header::STRING;
 
@@ -77,3 +112,130 @@ impl<'a> ProtocolType for &'a str {
         stream.read_str_raw().into()
     }
 }
+
+impl ProtocolType for String {
+    type Item = String;
+    const HEADER: i8 = header::STRING;
+
+    fn write_payload(&self, stream: &OutStream) {
+        stream.write_str_raw(self);
+    }
+
+    fn read_payload(stream: &InStream) -> Self::Item {
+        stream.read_str_raw().into()
+    }
+}
+
+impl ProtocolType for Uuid {
+    type Item = Uuid;
+    const HEADER: i8 = header::UUID;
+
+    fn write_payload(&self, stream: &OutStream) {
+        let bits = self.as_u128();
+
+        stream.write_i64((bits >> 64) as i64);
+        stream.write_i64(bits as i64);
+    }
+
+    fn read_payload(stream: &InStream) -> Self::Item {
+        let most = stream.read_i64() as u64;
+        let least = stream.read_i64() as u64;
+
+        Uuid::from_u128((u128::from(most) << 64) | u128::from(least))
+    }
+}
+
+/// Kind of the map, telling the server which Java class to deserialize it into.
+const MAP_KIND_HASH_MAP: i8 = 1;
+
+impl<K, V> ProtocolType for HashMap<K, V>
+where
+    K: ProtocolType<Item = K> + Eq + Hash,
+    V: ProtocolType<Item = V>,
+{
+    type Item = HashMap<K, V>;
+    const HEADER: i8 = header::OBJECT_MAP;
+
+    fn write_payload(&self, stream: &OutStream) {
+        stream.write_i32(self.len() as i32);
+        stream.write_i8(MAP_KIND_HASH_MAP);
+
+        for (key, value) in self {
+            write_full(key, stream);
+            write_full(value, stream);
+        }
+    }
+
+    /// Entries with null keys or values are skipped.
+    fn read_payload(stream: &InStream) -> Self::Item {
+        let len = stream.read_i32();
+        let _kind = stream.read_i8();
+
+        (0..len)
+            .filter_map(|_| {
+                let key = read_full::<K, K>(stream);
+                let value = read_full::<V, V>(stream);
+
+                Some((key?, value?))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_map_round_trip() {
+    let mut map = HashMap::new();
+    map.insert("tenant".to_owned(), "acme".to_owned());
+
+    let out = OutStream::new();
+    write_full(&map, &out);
+
+    let mem = out.into_memory();
+
+    assert_eq!(mem.len(), 1 + 4 + 1 + (1 + 4 + 6) + (1 + 4 + 4));
+    assert_eq!(mem[0], header::OBJECT_MAP as u8);
+    assert_eq!(mem[5], MAP_KIND_HASH_MAP as u8);
+
+    let stream = InStream::new(&mem);
+    assert_eq!(read_full::<HashMap<String, String>, _>(&stream), Some(map));
+}
+
+#[test]
+fn test_uuid_round_trip() {
+    let uuid = Uuid::from_u128(0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF);
+
+    let out = OutStream::new();
+    write_full(&uuid, &out);
+
+    let mem = out.into_memory();
+
+    assert_eq!(mem.len(), 1 + 16);
+    assert_eq!(mem[0], header::UUID as u8);
+    assert_eq!(mem[1], 0x77);
+    assert_eq!(mem[8], 0x00);
+    assert_eq!(mem[9], 0xFF);
+    assert_eq!(mem[16], 0x88);
+
+    let stream = InStream::new(&mem);
+    assert_eq!(read_full::<Uuid, Uuid>(&stream), Some(uuid));
+}
+
+#[test]
+fn test_try_read_full_mismatch() {
+    let out = OutStream::new();
+    write_full(&42i32, &out);
+    out.write_i8(header::NULL);
+    write_full(&"acme", &out);
+
+    let mem = out.into_memory();
+    let stream = InStream::new(&mem);
+
+    assert_eq!(try_read_full::<i32, i32>(&stream).unwrap(), Some(42));
+    assert_eq!(try_read_full::<i32, i32>(&stream).unwrap(), None);
+
+    let err = try_read_full::<i64, i64>(&stream).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Value of type i64 is expected, got value with type header 9"
+    );
+}
@@ -9,7 +9,6 @@ pub struct RequestEncoder(());
 
 impl RequestEncoder {
     /// Creates a new `RequestEncoder` instance.
-    #[allow(dead_code)]
     pub fn new() -> Self {
         Self(())
     }
@@ -1,15 +1,20 @@
-use bytes::BytesMut;
+use bytes::{Buf, BytesMut};
 use tokio_util::codec::Decoder;
 
 use crate::IgniteError;
 
+/// Size of the length prefix of every message.
+const LEN_SIZE: usize = 4;
+
 /// A simple `Decoder` implementation for protocol messages.
+///
+/// Splits the incoming data into messages using length prefix. Produced
+/// items do not include the prefix itself.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ResponseDecoder(());
 
 impl ResponseDecoder {
     /// Creates a new `ResponseDecoder` instance.
-    #[allow(dead_code)]
     pub fn new() -> Self {
         Self(())
     }
@@ -21,11 +26,53 @@ impl Decoder for ResponseDecoder {
 
     /// Decode response
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if buf.len() > 0 {
-            let len = buf.len();
-            Ok(Some(buf.split_to(len)))
-        } else {
-            Ok(None)
+        if buf.len() < LEN_SIZE {
+            return Ok(None);
+        }
+
+        let mut len_buf = [0u8; LEN_SIZE];
+        len_buf.copy_from_slice(&buf[..LEN_SIZE]);
+
+        let len = super::utils::deserialize_i32(&len_buf);
+        if len < 0 {
+            return Err(IgniteError::new(format!(
+                "Invalid length of the message: {}",
+                len
+            )));
         }
+
+        let len = len as usize;
+        if buf.len() < LEN_SIZE + len {
+            buf.reserve(LEN_SIZE + len - buf.len());
+            return Ok(None);
+        }
+
+        buf.advance(LEN_SIZE);
+        Ok(Some(buf.split_to(len)))
     }
 }
+
+#[test]
+fn test_decode_partial() {
+    let mut decoder = ResponseDecoder::new();
+    let mut buf = BytesMut::from(&[3u8, 0, 0][..]);
+
+    assert!(decoder.decode(&mut buf).unwrap().is_none());
+
+    buf.extend_from_slice(&[0u8, 1, 2]);
+    assert!(decoder.decode(&mut buf).unwrap().is_none());
+
+    buf.extend_from_slice(&[3u8, 2, 0]);
+    let msg = decoder.decode(&mut buf).unwrap().unwrap();
+
+    assert_eq!(&msg[..], &[1u8, 2, 3]);
+    assert_eq!(&buf[..], &[2u8, 0]);
+}
+
+#[test]
+fn test_decode_negative_len() {
+    let mut decoder = ResponseDecoder::new();
+    let mut buf = BytesMut::from(&[0xFFu8, 0xFF, 0xFF, 0xFF][..]);
+
+    decoder.decode(&mut buf).unwrap_err();
+}
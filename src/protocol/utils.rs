@@ -6,6 +6,22 @@ pub fn deserialize_i32(data: &[u8; 4]) -> i32 {
         | ((data[3] as i32 & 0xFFi32) << 24)
 }
 
+/// Calculate hash code of the string the same way Java does it.
+/// Used to calculate IDs of caches and types.
+pub fn java_string_hash(value: &str) -> i32 {
+    value
+        .encode_utf16()
+        .fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(i32::from(c)))
+}
+
+/// Calculate hash code of the bytes the same way as Java's `Arrays.hashCode(byte[])`.
+/// Used to calculate hash codes of binary objects.
+pub fn java_bytes_hash<'a, I: IntoIterator<Item = &'a u8>>(bytes: I) -> i32 {
+    bytes.into_iter().fold(1i32, |h, b| {
+        h.wrapping_mul(31).wrapping_add(i32::from(*b as i8))
+    })
+}
+
 /// Calculate the value fast which is the power of two and is greater or equals to the provided
 /// value. See https://graphics.stanford.edu/~seander/bithacks.html#RoundUpPowerOf2 for details.
 pub fn round_to_pow2_u32(val: u32) -> u32 {
@@ -41,6 +57,15 @@ fn test_deserialize_i32_x11378caa() {
     assert_eq!(res, 0x11378CAA);
 }
 
+#[test]
+fn test_java_string_hash() {
+    assert_eq!(0, java_string_hash(""));
+    assert_eq!(97, java_string_hash("a"));
+    assert_eq!(1_544_803_905, java_string_hash("default"));
+    assert_eq!(-969_099_747, java_string_hash("Hello World!"));
+    assert_eq!(1_177_014_952, java_string_hash("Привет"));
+}
+
 #[test]
 fn test_round_to_pow2_exact() {
     assert_eq!(1, round_to_pow2_u32(1));
@@ -68,9 +93,18 @@ fn test_round_to_pow2_any() {
     assert_eq!(256, round_to_pow2_u32(211));
     assert_eq!(1024, round_to_pow2_u32(618));
 
-    assert_eq!(1 << 31, round_to_pow2_u32(::std::i32::MAX as u32));
+    assert_eq!(1 << 31, round_to_pow2_u32(i32::MAX as u32));
 
     for i in 2..32 {
         assert_eq!(1 << i, round_to_pow2_u32((1 << i) - 1));
     }
 }
+
+#[test]
+fn test_java_bytes_hash() {
+    // Reference values are produced by Java's Arrays.hashCode().
+    let bytes = [3u8, 42, 0, 0, 0, 9, 4, 0, 0, 0, b'A', b'c', b'm', b'e'];
+    assert_eq!(java_bytes_hash(&bytes), -151_027_673);
+    assert_eq!(java_bytes_hash(&[0xFFu8, 0x80]), 802);
+    assert_eq!(java_bytes_hash(&[]), 1);
+}
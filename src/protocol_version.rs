@@ -2,10 +2,79 @@ use crate::protocol::{InStream, OutStream, Readable, Writable};
 use std::fmt;
 
 /// Version 1.2.0
-pub const VERSION_1_2_0: ProtocolVersion = ProtocolVersion::new(1,2,0);
+pub const VERSION_1_2_0: ProtocolVersion = ProtocolVersion::new(1, 2, 0);
+
+/// Version 1.4.0. Adds partition awareness: node ID in the handshake response,
+/// flags and affinity topology version in the response header.
+pub const VERSION_1_4_0: ProtocolVersion = ProtocolVersion::new(1, 4, 0);
+
+/// Version 1.5.0. Adds transactions.
+pub const VERSION_1_5_0: ProtocolVersion = ProtocolVersion::new(1, 5, 0);
+
+/// Version 1.7.0. Adds bitmask of the features, supported by both sides,
+/// to the handshake.
+pub const VERSION_1_7_0: ProtocolVersion = ProtocolVersion::new(1, 7, 0);
+
+/// Optional feature, negotiated in the handshake since version 1.7.0.
+/// Value is the index of the bit in the feature mask.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProtocolFeature {
+    /// User attributes are sent in the handshake request.
+    UserAttributes = 0,
+    /// Compute tasks can be executed by the name of their class.
+    ExecuteTaskByName = 1,
+    /// Cluster state and write-ahead logging can be managed.
+    ClusterStates = 2,
+    /// Endpoints of the server nodes can be requested.
+    ClusterGroupGetNodesEndpoints = 3,
+    /// Node IDs and details of the cluster groups can be requested.
+    ClusterGroups = 4,
+    /// Methods of the services can be invoked.
+    ServiceInvoke = 5,
+    /// Descriptors of the services can be requested.
+    GetServiceDescriptors = 9,
+    /// Heartbeat requests and idle timeout of the node.
+    Heartbeat = 11,
+}
+
+/// Features, supported by the client.
+pub const SUPPORTED_FEATURES: [ProtocolFeature; 8] = [
+    ProtocolFeature::UserAttributes,
+    ProtocolFeature::ExecuteTaskByName,
+    ProtocolFeature::ClusterStates,
+    ProtocolFeature::ClusterGroupGetNodesEndpoints,
+    ProtocolFeature::ClusterGroups,
+    ProtocolFeature::ServiceInvoke,
+    ProtocolFeature::GetServiceDescriptors,
+    ProtocolFeature::Heartbeat,
+];
+
+/// Check if the feature is set in the bitmask.
+pub fn has_feature(mask: &[u8], feature: ProtocolFeature) -> bool {
+    let bit = feature as usize;
+
+    mask.get(bit / 8)
+        .is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+}
+
+/// Make bitmask of the features, as it is sent in the handshake.
+pub fn features_mask(features: &[ProtocolFeature]) -> Vec<u8> {
+    let mut mask = Vec::new();
+
+    for feature in features {
+        let bit = *feature as usize;
+        if mask.len() <= bit / 8 {
+            mask.resize(bit / 8 + 1, 0);
+        }
+
+        mask[bit / 8] |= 1 << (bit % 8);
+    }
+
+    mask
+}
 
 /// Simple abstraction over protocol version.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ProtocolVersion {
     major: i16,
     minor: i16,
@@ -54,3 +123,14 @@ impl Readable for ProtocolVersion {
         }
     }
 }
+
+#[test]
+fn test_features_mask() {
+    assert_eq!(features_mask(&[]), Vec::<u8>::new());
+    assert_eq!(features_mask(&SUPPORTED_FEATURES), vec![0x3F, 0x0A]);
+
+    let mask = features_mask(&[ProtocolFeature::Heartbeat]);
+    assert!(has_feature(&mask, ProtocolFeature::Heartbeat));
+    assert!(!has_feature(&mask, ProtocolFeature::UserAttributes));
+    assert!(!has_feature(&[], ProtocolFeature::Heartbeat));
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio::net;
+
+use crate::ignite_error::{ChainResult, IgniteResult};
+use crate::IgniteError;
+
+/// Resolves host names of the endpoints into IP addresses.
+///
+/// Endpoints are resolved when the client starts and periodically after that,
+/// so the client connects to the addresses appearing behind a host name.
+///
+/// # Examples
+/// ```
+/// use futures::future::{self, BoxFuture, FutureExt};
+/// use ignite_rust::{ClientConfiguration, IgniteResult, Resolver};
+/// use std::net::IpAddr;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct Localhost;
+///
+/// impl Resolver for Localhost {
+///     fn resolve<'a>(&'a self, _host: &'a str) -> BoxFuture<'a, IgniteResult<Vec<IpAddr>>> {
+///         future::ready(Ok(vec!["127.0.0.1".parse().unwrap()])).boxed()
+///     }
+/// }
+///
+/// let mut cfg = ClientConfiguration::new();
+/// cfg.set_resolver(Arc::new(Localhost));
+/// ```
+pub trait Resolver: fmt::Debug + Send + Sync {
+    /// Get all the addresses of the host.
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, IgniteResult<Vec<IpAddr>>>;
+}
+
+/// Resolves host names with the system resolver, without blocking the runtime.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DnsResolver;
+
+impl Resolver for DnsResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, IgniteResult<Vec<IpAddr>>> {
+        async move {
+            // Port is required by the lookup, but is not used.
+            let addrs = net::lookup_host((host, 0))
+                .await
+                .chain_error(format!("Failed to resolve host address: {}", host))?;
+
+            Ok(addrs.map(|addr| addr.ip()).collect())
+        }
+        .boxed()
+    }
+}
+
+/// Resolves host names into the addresses set by the user.
+/// Addresses can be changed at any time, e.g. to test how the client reacts.
+///
+/// Hosts, which are IP addresses, are resolved into themselves.
+#[derive(Debug, Default)]
+pub struct StaticResolver {
+    hosts: Mutex<HashMap<String, Vec<IpAddr>>>,
+}
+
+impl StaticResolver {
+    /// Make new instance without any hosts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set addresses of the host, replacing the previous ones.
+    pub fn set_host<S: Into<String>>(&self, host: S, ips: Vec<IpAddr>) {
+        self.hosts().insert(host.into(), ips);
+    }
+
+    /// Remove the host, so it can not be resolved anymore.
+    pub fn remove_host(&self, host: &str) {
+        self.hosts().remove(host);
+    }
+
+    /// Get the hosts map.
+    fn hosts(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<IpAddr>>> {
+        // Map is always left consistent, so we do not care if it is poisoned.
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Resolver for StaticResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, IgniteResult<Vec<IpAddr>>> {
+        let res = match (self.hosts().get(host), host.parse::<IpAddr>()) {
+            (Some(ips), _) => Ok(ips.clone()),
+            (None, Ok(ip)) => Ok(vec![ip]),
+            (None, Err(_)) => Err(IgniteError::new(format!("Unknown host: {}", host))),
+        };
+
+        futures::future::ready(res).boxed()
+    }
+}
+
+#[test]
+fn static_resolver() {
+    let resolver = StaticResolver::new();
+    let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+    let resolve = |host| futures::executor::block_on(resolver.resolve(host));
+
+    assert_eq!(
+        resolve("::1").unwrap(),
+        vec!["::1".parse::<IpAddr>().unwrap()]
+    );
+    assert!(resolve("ignite.local").is_err());
+
+    resolver.set_host("ignite.local", vec![ip]);
+    assert_eq!(resolve("ignite.local").unwrap(), vec![ip]);
+
+    resolver.remove_host("ignite.local");
+    assert!(resolve("ignite.local").is_err());
+}
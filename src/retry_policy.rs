@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::IgniteError;
+
+/// Context of the operation which failed because of a connection issue.
+/// Passed to the RetryPolicy to decide whether the operation should be retried.
+#[derive(Debug)]
+pub struct RetryContext<'a> {
+    op_code: i16,
+    idempotent: bool,
+    iteration: u32,
+    retry_limit: u32,
+    error: &'a IgniteError,
+}
+
+impl<'a> RetryContext<'a> {
+    /// Make new instance.
+    pub(crate) fn new(
+        op_code: i16,
+        idempotent: bool,
+        iteration: u32,
+        retry_limit: u32,
+        error: &'a IgniteError,
+    ) -> Self {
+        Self {
+            op_code,
+            idempotent,
+            iteration,
+            retry_limit,
+            error,
+        }
+    }
+
+    /// Get protocol code of the operation.
+    pub fn op_code(&self) -> i16 {
+        self.op_code
+    }
+
+    /// Check if the operation can be repeated without any additional effect.
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    /// Get number of the retries, which were already made. Zero for the first failure.
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Get retry limit, set in the client configuration.
+    pub fn retry_limit(&self) -> u32 {
+        self.retry_limit
+    }
+
+    /// Get error the operation failed with.
+    pub fn error(&self) -> &IgniteError {
+        self.error
+    }
+}
+
+/// Policy, deciding whether the operation, which failed because the connection
+/// to the node was lost, should be transparently retried using another connection.
+///
+/// # Examples
+/// ```
+/// use ignite_rust::{ClientConfiguration, RetryContext, RetryPolicy};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct RetryTwice;
+///
+/// impl RetryPolicy for RetryTwice {
+///     fn should_retry(&self, ctx: &RetryContext) -> bool {
+///         ctx.iteration() < 2
+///     }
+/// }
+///
+/// let mut cfg = ClientConfiguration::new();
+/// cfg.set_retry_policy(Arc::new(RetryTwice));
+/// ```
+pub trait RetryPolicy: fmt::Debug + Send + Sync {
+    /// Decide whether the operation should be retried.
+    fn should_retry(&self, ctx: &RetryContext) -> bool;
+}
+
+/// Retries idempotent operations until the retry limit is reached.
+/// This is the default policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryIdempotentPolicy;
+
+impl RetryPolicy for RetryIdempotentPolicy {
+    fn should_retry(&self, ctx: &RetryContext) -> bool {
+        ctx.is_idempotent() && ctx.iteration() < ctx.retry_limit()
+    }
+}
+
+/// Retries any operations until the retry limit is reached.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryAllPolicy;
+
+impl RetryPolicy for RetryAllPolicy {
+    fn should_retry(&self, ctx: &RetryContext) -> bool {
+        ctx.iteration() < ctx.retry_limit()
+    }
+}
+
+/// Never retries operations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryNonePolicy;
+
+impl RetryPolicy for RetryNonePolicy {
+    fn should_retry(&self, _ctx: &RetryContext) -> bool {
+        false
+    }
+}
+
+#[test]
+fn test_retry_idempotent_policy() {
+    let err = IgniteError::new("Test");
+    let policy = RetryIdempotentPolicy;
+
+    assert!(policy.should_retry(&RetryContext::new(1000, true, 0, 2, &err)));
+    assert!(policy.should_retry(&RetryContext::new(1000, true, 1, 2, &err)));
+    assert!(!policy.should_retry(&RetryContext::new(1000, true, 2, 2, &err)));
+    assert!(!policy.should_retry(&RetryContext::new(1001, false, 0, 2, &err)));
+}
+
+#[test]
+fn test_retry_all_policy() {
+    let err = IgniteError::new("Test");
+    let policy = RetryAllPolicy;
+
+    assert!(policy.should_retry(&RetryContext::new(1001, false, 0, 1, &err)));
+    assert!(!policy.should_retry(&RetryContext::new(1001, false, 1, 1, &err)));
+}
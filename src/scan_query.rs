@@ -0,0 +1,82 @@
+use crate::binary_object::BinaryObject;
+
+/// Default number of entries fetched at once.
+const DEFAULT_PAGE_SIZE: i32 = 1024;
+
+/// Query iterating over the entries of the cache.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::{BinaryObject, ScanQuery};
+///
+/// let mut query = ScanQuery::new();
+/// query.set_partition(Some(3));
+/// query.set_filter(Some(BinaryObject::new("com.example.ActivePersonFilter")));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanQuery {
+    page_size: i32,
+    partition: Option<i32>,
+    local: bool,
+    filter: Option<BinaryObject>,
+}
+
+impl ScanQuery {
+    /// Make new query over all the entries of the cache.
+    pub fn new() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            partition: None,
+            local: false,
+            filter: None,
+        }
+    }
+
+    /// Set number of entries fetched from the server at once.
+    pub fn set_page_size(&mut self, page_size: i32) {
+        self.page_size = page_size;
+    }
+
+    /// Get number of entries fetched from the server at once.
+    pub fn get_page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    /// Set partition to scan. None means all the partitions are scanned.
+    pub fn set_partition(&mut self, partition: Option<i32>) {
+        self.partition = partition;
+    }
+
+    /// Get partition to scan.
+    pub fn get_partition(&self) -> Option<i32> {
+        self.partition
+    }
+
+    /// Set whether the query is executed over the data of the node it is sent to only.
+    pub fn set_local(&mut self, local: bool) {
+        self.local = local;
+    }
+
+    /// Get whether the query is executed over the data of the node it is sent to only.
+    pub fn get_local(&self) -> bool {
+        self.local
+    }
+
+    /// Set filter of the entries, applied on the server. The object has to be of
+    /// the Java class implementing `IgniteBiPredicate`, available on the server.
+    pub fn set_filter(&mut self, filter: Option<BinaryObject>) {
+        self.filter = filter;
+    }
+
+    /// Get filter of the entries.
+    pub fn get_filter(&self) -> Option<&BinaryObject> {
+        self.filter.as_ref()
+    }
+}
+
+impl Default for ScanQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
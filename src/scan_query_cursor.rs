@@ -0,0 +1,77 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::{Stream, StreamExt};
+
+use crate::ignite_error::IgniteResult;
+use crate::net::{AsyncDataChannel, MessageRouter};
+use crate::paged_cursor::PagedCursor;
+use crate::protocol::message::{QueryScanCursorGetPageReq, QueryScanRsp};
+use crate::protocol::ProtocolType;
+
+/// Cursor over the entries of the scan query.
+///
+/// Entries are fetched from the server page by page, as the stream is polled.
+/// Like the SQL cursor, it is bound to the connection the query was executed over,
+/// and is closed on the server in the background if dropped before the end.
+///
+/// # Example
+/// ```no_run
+/// # use ignite_rust::*;
+/// use futures::TryStreamExt;
+///
+/// # async fn run(cache: IgniteCache<i32, String>) -> IgniteResult<()> {
+/// let mut cursor = cache.scan(&ScanQuery::new()).await?;
+///
+/// while let Some((key, value)) = cursor.try_next().await? {
+///     println!("{}: {}", key, value);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ScanQueryCursor<K, V> {
+    inner: PagedCursor<(K, V), QueryScanCursorGetPageReq<K, V>>,
+}
+
+impl<K, V> ScanQueryCursor<K, V>
+where
+    K: ProtocolType<Item = K> + Send + 'static,
+    V: ProtocolType<Item = V> + Send + 'static,
+{
+    /// Make new instance out of the query response.
+    pub(crate) fn new(
+        router: Arc<MessageRouter>,
+        channel: Arc<AsyncDataChannel>,
+        rsp: QueryScanRsp<K, V>,
+    ) -> Self {
+        let cursor_id = rsp.cursor_id();
+        let get_page = QueryScanCursorGetPageReq::new(cursor_id);
+
+        Self {
+            inner: PagedCursor::new(router, channel, cursor_id, rsp.into_page(), get_page),
+        }
+    }
+}
+
+impl<K, V> Stream for ScanQueryCursor<K, V>
+where
+    K: ProtocolType<Item = K> + Send + 'static,
+    V: ProtocolType<Item = V> + Send + 'static,
+{
+    type Item = IgniteResult<(K, V)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next_unpin(cx)
+    }
+}
+
+impl<K, V> fmt::Debug for ScanQueryCursor<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScanQueryCursor")
+            .field("cursor_id", &self.inner.cursor_id())
+            .field("has_more", &self.inner.has_more())
+            .finish()
+    }
+}
@@ -0,0 +1,91 @@
+use uuid::Uuid;
+
+/// Platform the service is implemented on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServicePlatform {
+    /// Java service.
+    Java = 0,
+    /// .NET service.
+    DotNet = 1,
+}
+
+impl ServicePlatform {
+    /// Get platform by the protocol code.
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            0 => Some(ServicePlatform::Java),
+            1 => Some(ServicePlatform::DotNet),
+            _ => None,
+        }
+    }
+}
+
+/// Details of the service deployed in the cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceDescriptor {
+    name: String,
+    service_class: String,
+    total_count: i32,
+    max_per_node_count: i32,
+    cache_name: Option<String>,
+    origin_node_id: Option<Uuid>,
+    platform: ServicePlatform,
+}
+
+impl ServiceDescriptor {
+    /// Make new instance.
+    pub(crate) fn new(
+        name: String,
+        service_class: String,
+        total_count: i32,
+        max_per_node_count: i32,
+        cache_name: Option<String>,
+        origin_node_id: Option<Uuid>,
+        platform: ServicePlatform,
+    ) -> Self {
+        Self {
+            name,
+            service_class,
+            total_count,
+            max_per_node_count,
+            cache_name,
+            origin_node_id,
+            platform,
+        }
+    }
+
+    /// Get name of the service.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get name of the class implementing the service.
+    pub fn service_class(&self) -> &str {
+        &self.service_class
+    }
+
+    /// Get max number of the service instances in the cluster. Zero means no limit.
+    pub fn total_count(&self) -> i32 {
+        self.total_count
+    }
+
+    /// Get max number of the service instances per node. Zero means no limit.
+    pub fn max_per_node_count(&self) -> i32 {
+        self.max_per_node_count
+    }
+
+    /// Get name of the cache the service is deployed along with the keys of, if any.
+    pub fn cache_name(&self) -> Option<&str> {
+        self.cache_name.as_deref()
+    }
+
+    /// Get ID of the node which deployed the service, if known.
+    pub fn origin_node_id(&self) -> Option<Uuid> {
+        self.origin_node_id
+    }
+
+    /// Get platform the service is implemented on.
+    pub fn platform(&self) -> ServicePlatform {
+        self.platform
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use crate::ignite_value::IgniteValue;
+
+/// Default number of rows fetched at once.
+const DEFAULT_PAGE_SIZE: i32 = 1024;
+
+/// Type of the statement the query is allowed to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementType {
+    /// Any statement.
+    Any = 0,
+    /// SELECT statement, returning rows.
+    Select = 1,
+    /// DML or DDL statement, returning number of the affected rows.
+    Update = 2,
+}
+
+/// SQL query returning rows of fields.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::SqlFieldsQuery;
+///
+/// let mut query = SqlFieldsQuery::new("SELECT name FROM Person WHERE age > ?");
+/// query.set_args(vec![30i32.into()]);
+/// query.set_page_size(100);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlFieldsQuery {
+    sql: String,
+    schema: Option<String>,
+    args: Vec<IgniteValue>,
+    statement_type: StatementType,
+    page_size: i32,
+    max_rows: i32,
+    distributed_joins: bool,
+    local: bool,
+    lazy: bool,
+    timeout: Option<Duration>,
+    include_field_names: bool,
+}
+
+impl SqlFieldsQuery {
+    /// Make new query with the default parameters.
+    pub fn new<S: Into<String>>(sql: S) -> Self {
+        Self {
+            sql: sql.into(),
+            schema: None,
+            args: Vec::new(),
+            statement_type: StatementType::Any,
+            page_size: DEFAULT_PAGE_SIZE,
+            max_rows: 0,
+            distributed_joins: false,
+            local: false,
+            lazy: false,
+            timeout: None,
+            include_field_names: true,
+        }
+    }
+
+    /// Get SQL text of the query.
+    pub fn get_sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Set schema the query is executed in. The PUBLIC schema is used by default.
+    pub fn set_schema<S: Into<String>>(&mut self, schema: S) {
+        self.schema = Some(schema.into());
+    }
+
+    /// Get schema the query is executed in.
+    pub fn get_schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Set arguments of the query, one per `?` placeholder.
+    pub fn set_args(&mut self, args: Vec<IgniteValue>) {
+        self.args = args;
+    }
+
+    /// Get arguments of the query.
+    pub fn get_args(&self) -> &[IgniteValue] {
+        &self.args
+    }
+
+    /// Set type of the statement. The query fails if the statement is of the other type.
+    pub fn set_statement_type(&mut self, statement_type: StatementType) {
+        self.statement_type = statement_type;
+    }
+
+    /// Get type of the statement.
+    pub fn get_statement_type(&self) -> StatementType {
+        self.statement_type
+    }
+
+    /// Set number of rows fetched from the server at once.
+    pub fn set_page_size(&mut self, page_size: i32) {
+        self.page_size = page_size;
+    }
+
+    /// Get number of rows fetched from the server at once.
+    pub fn get_page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    /// Set maximum number of rows returned by the query. Zero means no limit.
+    pub fn set_max_rows(&mut self, max_rows: i32) {
+        self.max_rows = max_rows;
+    }
+
+    /// Get maximum number of rows returned by the query.
+    pub fn get_max_rows(&self) -> i32 {
+        self.max_rows
+    }
+
+    /// Set whether joins of the non-colocated data are allowed.
+    pub fn set_distributed_joins(&mut self, distributed_joins: bool) {
+        self.distributed_joins = distributed_joins;
+    }
+
+    /// Get whether joins of the non-colocated data are allowed.
+    pub fn get_distributed_joins(&self) -> bool {
+        self.distributed_joins
+    }
+
+    /// Set whether the query is executed over the data of the node it is sent to only.
+    pub fn set_local(&mut self, local: bool) {
+        self.local = local;
+    }
+
+    /// Get whether the query is executed over the data of the node it is sent to only.
+    pub fn get_local(&self) -> bool {
+        self.local
+    }
+
+    /// Set whether the rows are fetched lazily, keeping memory consumption
+    /// of the server low for the large result sets.
+    pub fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    /// Get whether the rows are fetched lazily.
+    pub fn get_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    /// Set timeout of the query execution on the server. None means no timeout.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Get timeout of the query execution on the server.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set whether names of the columns are returned by the query.
+    pub fn set_include_field_names(&mut self, include_field_names: bool) {
+        self.include_field_names = include_field_names;
+    }
+
+    /// Get whether names of the columns are returned by the query.
+    pub fn get_include_field_names(&self) -> bool {
+        self.include_field_names
+    }
+}
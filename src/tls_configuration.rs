@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+/// Verification of the certificate, presented by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVerifyMode {
+    /// Certificate chain is verified against the trusted CA, and the
+    /// certificate has to be issued for the host name.
+    Full,
+    /// Certificate chain is verified against the trusted CA, but the host name
+    /// is not checked. Useful when nodes are accessed by IP addresses.
+    CaOnly,
+    /// Certificate is not verified at all. Connection is encrypted but the node
+    /// is not authenticated, so it should only be used for testing.
+    None,
+}
+
+/// Configuration of the TLS connections to the nodes.
+///
+/// TLS support has to be enabled with one of the cargo features:
+/// `tls-rustls` or `tls-native`. If both are enabled, rustls is used.
+#[derive(Debug, Clone)]
+pub struct TlsConfiguration {
+    ca_file: Option<PathBuf>,
+    cert_file: Option<PathBuf>,
+    key_file: Option<PathBuf>,
+    server_name: Option<String>,
+    verify_mode: TlsVerifyMode,
+}
+
+impl Default for TlsConfiguration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TlsConfiguration {
+    /// Create new configuration with full verification of the node certificate.
+    pub fn new() -> TlsConfiguration {
+        TlsConfiguration {
+            ca_file: None,
+            cert_file: None,
+            key_file: None,
+            server_name: None,
+            verify_mode: TlsVerifyMode::Full,
+        }
+    }
+
+    /// Set path to the PEM file with the certificates of the trusted CAs.
+    ///
+    /// By default only the system trusted CAs are used with `tls-native`, and
+    /// no CAs are trusted with `tls-rustls`.
+    pub fn set_ca_file<P: AsRef<Path>>(&mut self, ca_file: P) {
+        self.ca_file = Some(ca_file.as_ref().to_owned());
+    }
+
+    /// Get path to the PEM file with the certificates of the trusted CAs.
+    pub fn get_ca_file(&self) -> Option<&Path> {
+        self.ca_file.as_deref()
+    }
+
+    /// Set paths to the PEM files with the client certificate chain and its
+    /// private key in PKCS#8 format, which are presented to the node if it
+    /// requires client authentication.
+    pub fn set_client_certificate<P: AsRef<Path>, K: AsRef<Path>>(
+        &mut self,
+        cert_file: P,
+        key_file: K,
+    ) {
+        self.cert_file = Some(cert_file.as_ref().to_owned());
+        self.key_file = Some(key_file.as_ref().to_owned());
+    }
+
+    /// Get paths to the PEM files with the client certificate chain and its private key.
+    pub fn get_client_certificate(&self) -> Option<(&Path, &Path)> {
+        match (&self.cert_file, &self.key_file) {
+            (Some(cert), Some(key)) => Some((cert, key)),
+            _ => None,
+        }
+    }
+
+    /// Set name of the server, the node certificate is verified against,
+    /// overriding the host name of the endpoint.
+    pub fn set_server_name(&mut self, server_name: &str) {
+        self.server_name = Some(server_name.to_owned());
+    }
+
+    /// Get name of the server, the node certificate is verified against.
+    pub fn get_server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Set verification of the node certificate.
+    ///
+    /// Default is TlsVerifyMode::Full.
+    pub fn set_verify_mode(&mut self, verify_mode: TlsVerifyMode) {
+        self.verify_mode = verify_mode;
+    }
+
+    /// Get verification of the node certificate.
+    pub fn get_verify_mode(&self) -> TlsVerifyMode {
+        self.verify_mode
+    }
+}
+
+#[test]
+fn tls_configuration_client_certificate() {
+    let mut cfg = TlsConfiguration::new();
+    assert_eq!(cfg.get_client_certificate(), None);
+
+    cfg.set_client_certificate("client.pem", "client.key");
+
+    assert_eq!(
+        cfg.get_client_certificate(),
+        Some((Path::new("client.pem"), Path::new("client.key")))
+    );
+    assert_eq!(cfg.get_verify_mode(), TlsVerifyMode::Full);
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use crate::transactions::{TransactionConcurrency, TransactionIsolation};
+
+/// Default max number of attempts to run the transaction.
+pub const DEFAULT_TX_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the second attempt to run the transaction.
+pub const DEFAULT_TX_RETRY_DELAY_INITIAL: Duration = Duration::from_millis(50);
+
+/// Default max delay between attempts to run the transaction.
+pub const DEFAULT_TX_RETRY_DELAY_MAX: Duration = Duration::from_secs(1);
+
+/// Options of the transaction run by `IgniteClient::transactional()`.
+///
+/// # Example
+///
+/// ```
+/// use ignite_rust::{TransactionConcurrency, TransactionIsolation, TransactionOptions};
+/// use std::time::Duration;
+///
+/// let mut opts = TransactionOptions::new();
+/// opts.set_concurrency(TransactionConcurrency::Optimistic);
+/// opts.set_isolation(TransactionIsolation::Serializable);
+/// opts.set_max_attempts(5);
+/// opts.set_retry_backoff(Duration::from_millis(10), Duration::from_millis(200));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionOptions {
+    concurrency: TransactionConcurrency,
+    isolation: TransactionIsolation,
+    timeout: Option<Duration>,
+    label: Option<String>,
+    max_attempts: u32,
+    retry_delay_initial: Duration,
+    retry_delay_max: Duration,
+}
+
+impl TransactionOptions {
+    /// Make new options of the pessimistic repeatable read transaction.
+    pub fn new() -> Self {
+        Self {
+            concurrency: TransactionConcurrency::Pessimistic,
+            isolation: TransactionIsolation::RepeatableRead,
+            timeout: None,
+            label: None,
+            max_attempts: DEFAULT_TX_MAX_ATTEMPTS,
+            retry_delay_initial: DEFAULT_TX_RETRY_DELAY_INITIAL,
+            retry_delay_max: DEFAULT_TX_RETRY_DELAY_MAX,
+        }
+    }
+
+    /// Set concurrency mode of the transaction.
+    pub fn set_concurrency(&mut self, concurrency: TransactionConcurrency) {
+        self.concurrency = concurrency;
+    }
+
+    /// Get concurrency mode of the transaction.
+    pub fn get_concurrency(&self) -> TransactionConcurrency {
+        self.concurrency
+    }
+
+    /// Set isolation level of the transaction.
+    pub fn set_isolation(&mut self, isolation: TransactionIsolation) {
+        self.isolation = isolation;
+    }
+
+    /// Get isolation level of the transaction.
+    pub fn get_isolation(&self) -> TransactionIsolation {
+        self.isolation
+    }
+
+    /// Set timeout, after which the server rolls back the transaction.
+    /// None means no timeout.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Get timeout of the transaction.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set label of the transaction.
+    pub fn set_label(&mut self, label: Option<&str>) {
+        self.label = label.map(str::to_owned);
+    }
+
+    /// Get label of the transaction.
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Set max number of attempts to run the transaction. Only optimistic
+    /// serializable transactions, failed because of the conflict with another
+    /// transaction, are run again.
+    ///
+    /// Default is DEFAULT_TX_MAX_ATTEMPTS.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = max_attempts;
+    }
+
+    /// Get max number of attempts to run the transaction.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set backoff of the attempts to run the transaction.
+    ///
+    /// Every subsequent attempt is delayed twice as long as the previous one,
+    /// starting with `initial` and not exceeding `max`.
+    pub fn set_retry_backoff(&mut self, initial: Duration, max: Duration) {
+        self.retry_delay_initial = initial;
+        self.retry_delay_max = max;
+    }
+
+    /// Get backoff of the attempts as a pair of initial and max delays.
+    pub fn get_retry_backoff(&self) -> (Duration, Duration) {
+        (self.retry_delay_initial, self.retry_delay_max)
+    }
+
+    /// Get delay before the attempt, made after the specified number of failed ones.
+    pub(crate) fn retry_delay(&self, failures: u32) -> Duration {
+        if failures == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let factor = 1u32.checked_shl(failures - 1).unwrap_or(u32::MAX);
+
+        self.retry_delay_initial
+            .checked_mul(factor)
+            .map_or(self.retry_delay_max, |delay| {
+                delay.min(self.retry_delay_max)
+            })
+    }
+
+    /// Whether the transaction conflicting with another one can be run again.
+    pub(crate) fn is_retriable(&self) -> bool {
+        self.concurrency == TransactionConcurrency::Optimistic
+            && self.isolation == TransactionIsolation::Serializable
+    }
+}
+
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn transaction_options_retry_delay() {
+    let mut opts = TransactionOptions::new();
+    opts.set_retry_backoff(Duration::from_millis(10), Duration::from_millis(30));
+
+    assert!(!opts.is_retriable());
+    assert_eq!(opts.retry_delay(0), Duration::from_millis(0));
+    assert_eq!(opts.retry_delay(1), Duration::from_millis(10));
+    assert_eq!(opts.retry_delay(2), Duration::from_millis(20));
+    assert_eq!(opts.retry_delay(3), Duration::from_millis(30));
+    assert_eq!(opts.retry_delay(100), Duration::from_millis(30));
+
+    opts.set_concurrency(TransactionConcurrency::Optimistic);
+    opts.set_isolation(TransactionIsolation::Serializable);
+
+    assert!(opts.is_retriable());
+}
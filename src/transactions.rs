@@ -0,0 +1,326 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use tokio::time;
+
+use crate::ignite_error::{ErrorKind, IgniteError, IgniteResult};
+use crate::net::{AsyncDataChannel, MessageRouter};
+use crate::protocol::message::{TxEndReq, TxStartReq};
+use crate::transaction_options::TransactionOptions;
+
+/// Name of the server exception, reported when the optimistic transaction
+/// conflicts with another one.
+const OPTIMISTIC_EXCEPTION: &str = "TransactionOptimisticException";
+
+/// Message of the server exception, reported when the optimistic transaction
+/// conflicts with another one.
+const OPTIMISTIC_CONFLICT: &str = "read/write conflict";
+
+/// Concurrency mode of the transaction, defining when the entries are locked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionConcurrency {
+    /// Entries are locked on commit. The commit fails if they were changed meanwhile.
+    Optimistic = 0,
+    /// Entries are locked on the first access.
+    Pessimistic = 1,
+}
+
+/// Isolation level of the transaction, defining which changes of the other
+/// transactions it can see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionIsolation {
+    /// Value is read again on every access.
+    ReadCommitted = 0,
+    /// Value is read once and the same value is returned on the following accesses.
+    RepeatableRead = 1,
+    /// Transactions are executed as if they were executed one after another.
+    Serializable = 2,
+}
+
+/// Transaction the cache operations can be made within.
+///
+/// The transaction is bound to the connection it was started over, and the
+/// operations within it are always sent over the same connection.
+#[derive(Debug, Clone)]
+pub(crate) struct TxContext {
+    id: i32,
+    channel: Arc<AsyncDataChannel>,
+}
+
+impl TxContext {
+    /// Get ID of the transaction.
+    pub(crate) fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// Get channel the transaction was started over.
+    pub(crate) fn channel(&self) -> &AsyncDataChannel {
+        &self.channel
+    }
+}
+
+/// Transactions of the cluster the client is connected to.
+///
+/// Transactions require protocol version 1.5.0 and fail with the Unsupported
+/// error kind if the node does not support them.
+#[derive(Debug, Clone)]
+pub struct ClientTransactions {
+    router: Arc<MessageRouter>,
+}
+
+impl ClientTransactions {
+    /// Make new instance.
+    pub(crate) fn new(router: Arc<MessageRouter>) -> Self {
+        Self { router }
+    }
+
+    /// Start new transaction.
+    ///
+    /// The transaction is rolled back by the server if it is not finished within
+    /// the timeout. None means no timeout. Label makes the transaction easier to
+    /// find in the server logs and views.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::*;
+    /// # async fn run(client: IgniteClient, cache: IgniteCache<i32, i32>) -> IgniteResult<()> {
+    /// let tx = client
+    ///     .transactions()
+    ///     .tx_start(
+    ///         TransactionConcurrency::Pessimistic,
+    ///         TransactionIsolation::RepeatableRead,
+    ///         None,
+    ///         Some("transfer"),
+    ///     )
+    ///     .await?;
+    ///
+    /// let accounts = cache.with_transaction(&tx);
+    /// let balance = accounts.get(&1).await?.unwrap_or(0);
+    /// accounts.put(&1, &(balance - 10)).await?;
+    /// accounts.put(&2, &10).await?;
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tx_start(
+        &self,
+        concurrency: TransactionConcurrency,
+        isolation: TransactionIsolation,
+        timeout: Option<Duration>,
+        label: Option<&str>,
+    ) -> IgniteResult<Transaction> {
+        let req = TxStartReq::new(concurrency, isolation, timeout, label);
+        let (id, channel) = self.router.send_bound(&req, None).await?;
+
+        Ok(Transaction {
+            router: self.router.clone(),
+            ctx: TxContext { id, channel },
+            finished: false,
+        })
+    }
+
+    /// Run the closure within the new transaction.
+    ///
+    /// The transaction is committed if the closure succeeds, and rolled back
+    /// otherwise. Optimistic serializable transaction, which conflicted with
+    /// another one, is run again with the backoff set by the options, until the
+    /// max number of attempts is reached. The closure has to be safe to run again.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ignite_rust::*;
+    /// use futures::FutureExt;
+    ///
+    /// # async fn run(client: IgniteClient, cache: IgniteCache<i32, i32>) -> IgniteResult<()> {
+    /// let mut opts = TransactionOptions::new();
+    /// opts.set_concurrency(TransactionConcurrency::Optimistic);
+    /// opts.set_isolation(TransactionIsolation::Serializable);
+    ///
+    /// let balance = client
+    ///     .transactional(&opts, |tx| {
+    ///         let accounts = cache.with_transaction(tx);
+    ///         async move {
+    ///             let balance = accounts.get(&1).await?.unwrap_or(0) - 10;
+    ///             accounts.put(&1, &balance).await?;
+    ///             accounts.put(&2, &10).await?;
+    ///             Ok(balance)
+    ///         }
+    ///         .boxed()
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transactional<T, F>(&self, opts: &TransactionOptions, mut f: F) -> IgniteResult<T>
+    where
+        F: for<'t> FnMut(&'t Transaction) -> BoxFuture<'t, IgniteResult<T>>,
+    {
+        let mut failures = 0;
+
+        loop {
+            let res = self.run_once(opts, &mut f).await;
+
+            match res {
+                Err(err) if Self::can_retry(opts, failures, &err) => {
+                    failures += 1;
+
+                    debug!(
+                        "Transaction conflicted, attempt {} of {}: {}",
+                        failures,
+                        opts.get_max_attempts(),
+                        err
+                    );
+
+                    time::delay_for(opts.retry_delay(failures)).await;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Start the transaction, run the closure and finish the transaction.
+    async fn run_once<T, F>(&self, opts: &TransactionOptions, f: &mut F) -> IgniteResult<T>
+    where
+        F: for<'t> FnMut(&'t Transaction) -> BoxFuture<'t, IgniteResult<T>>,
+    {
+        let tx = self
+            .tx_start(
+                opts.get_concurrency(),
+                opts.get_isolation(),
+                opts.get_timeout(),
+                opts.get_label(),
+            )
+            .await?;
+
+        match f(&tx).await {
+            Ok(val) => tx.commit().await.map(|_| val),
+            Err(err) => {
+                let id = tx.id();
+                if let Err(rollback_err) = tx.rollback().await {
+                    warn!("Can not roll back transaction {}: {}", id, rollback_err);
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Whether the failed attempt to run the transaction can be made again.
+    fn can_retry(opts: &TransactionOptions, failures: u32, err: &IgniteError) -> bool {
+        opts.is_retriable() && failures + 1 < opts.get_max_attempts() && is_conflict(err)
+    }
+}
+
+/// Check whether the error, or any of its causes, is reported by the server
+/// because of the conflict of the optimistic transaction with another one.
+fn is_conflict(err: &IgniteError) -> bool {
+    let mut cur: Option<&(dyn Error + 'static)> = Some(err);
+
+    while let Some(err) = cur {
+        if let Some(err) = err.downcast_ref::<IgniteError>() {
+            if let ErrorKind::Server(_) = err.kind() {
+                let msg = err.to_string();
+                if msg.contains(OPTIMISTIC_EXCEPTION) || msg.contains(OPTIMISTIC_CONFLICT) {
+                    return true;
+                }
+            }
+        }
+
+        cur = err.source();
+    }
+
+    false
+}
+
+/// Started transaction.
+///
+/// Cache operations are made within the transaction using the cache returned by
+/// `IgniteCache::with_transaction()`. The transaction is rolled back in the
+/// background if it is dropped without being committed or rolled back.
+pub struct Transaction {
+    router: Arc<MessageRouter>,
+    ctx: TxContext,
+    finished: bool,
+}
+
+impl Transaction {
+    /// Get ID of the transaction.
+    pub fn id(&self) -> i32 {
+        self.ctx.id
+    }
+
+    /// Commit the transaction.
+    pub async fn commit(mut self) -> IgniteResult<()> {
+        self.end(true).await
+    }
+
+    /// Roll back the transaction.
+    pub async fn rollback(mut self) -> IgniteResult<()> {
+        self.end(false).await
+    }
+
+    /// Get context of the transaction.
+    pub(crate) fn context(&self) -> &TxContext {
+        &self.ctx
+    }
+
+    /// Commit or roll back the transaction.
+    async fn end(&mut self, committed: bool) -> IgniteResult<()> {
+        // Server can not finish the transaction if the request is not sent,
+        // but it rolls back all the transactions of the connection once it is lost.
+        self.finished = true;
+
+        let req = TxEndReq::new(self.ctx.id, committed);
+        self.router
+            .send_on(&self.ctx.channel, &req, None)
+            .await
+            .map_err(|err| {
+                let action = if committed { "commit" } else { "roll back" };
+                IgniteError::new_with_kind_and_source(
+                    err.kind(),
+                    format!("Can not {} transaction {}", action, self.ctx.id),
+                    Box::new(err),
+                )
+            })
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if self.finished || self.ctx.channel.is_broken() {
+            return;
+        }
+
+        let runtime = match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => runtime,
+            Err(_) => {
+                warn!("Can not roll back transaction {}: no runtime", self.ctx.id);
+                return;
+            }
+        };
+
+        let router = self.router.clone();
+        let ctx = self.ctx.clone();
+
+        runtime.spawn(async move {
+            let req = TxEndReq::new(ctx.id, false);
+
+            if let Err(err) = router.send_on(&ctx.channel, &req, None).await {
+                warn!("Can not roll back transaction {}: {}", ctx.id, err);
+            }
+        });
+    }
+}
+
+impl fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("id", &self.ctx.id)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
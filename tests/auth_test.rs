@@ -0,0 +1,118 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use ignite_rust::*;
+
+/// Start node, accepting only the given credentials.
+async fn start_node(user: &str, pass: &str) -> FakeNode {
+    let options = FakeNodeOptions {
+        credentials: Some((user.to_owned(), pass.to_owned())),
+        ..FakeNodeOptions::default()
+    };
+
+    FakeNode::start_with(options, |_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Provider, returning a new password every time, as if it was rotated.
+#[derive(Debug, Default)]
+struct RotatingCredentials {
+    calls: AtomicUsize,
+}
+
+impl CredentialsProvider for RotatingCredentials {
+    fn credentials(&self) -> IgniteResult<Credentials> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+
+        Ok(Credentials::new("ignite", format!("secret{}", call)))
+    }
+}
+
+/// Provider, which can not get the credentials.
+#[derive(Debug)]
+struct FailingCredentials;
+
+impl CredentialsProvider for FailingCredentials {
+    fn credentials(&self) -> IgniteResult<Credentials> {
+        Err(IgniteError::new("Vault is sealed"))
+    }
+}
+
+#[test]
+fn auth_user_and_password() {
+    run_async(async {
+        let node = start_node("ignite", "secret").await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+        cfg.set_user("ignite");
+        cfg.set_password("secret");
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+    });
+}
+
+#[test]
+fn auth_invalid_credentials() {
+    run_async(async {
+        let node1 = start_node("ignite", "secret").await;
+        let node2 = start_node("ignite", "secret").await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+        cfg.set_connect_eagerly(false);
+        cfg.set_user("ignite");
+        cfg.set_password("wrong");
+
+        let err = IgniteClient::start(cfg).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Authentication);
+
+        // Other nodes are not tried, and other versions are not negotiated.
+        assert_eq!(node1.connections() + node2.connections(), 1);
+    });
+}
+
+#[test]
+fn auth_credentials_provider() {
+    run_async(async {
+        let node = start_node("ignite", "secret1").await;
+
+        let provider = Arc::new(RotatingCredentials::default());
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+        cfg.set_user("ignored");
+        cfg.set_credentials_provider(provider.clone());
+
+        // The first password is outdated, the second one is accepted.
+        let err = IgniteClient::start(cfg.clone()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Authentication);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn auth_credentials_provider_fails() {
+    run_async(async {
+        let node = start_node("ignite", "secret").await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+        cfg.set_credentials_provider(Arc::new(FailingCredentials));
+
+        let err = IgniteClient::start(cfg).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Authentication);
+    });
+}
@@ -0,0 +1,85 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use ignite_rust::*;
+
+/// Op code of the request putting value by key.
+const OP_CACHE_PUT: i16 = 1001;
+
+/// Op code of the request checking if the key is in cache.
+const OP_CACHE_CONTAINS_KEY: i16 = 1011;
+
+/// Op code of the request removing the key.
+const OP_CACHE_REMOVE_KEY: i16 = 1016;
+
+/// Position of the key in the key operation payload: after the cache ID,
+/// flags and the type header of the key.
+const KEY_POS: usize = 6;
+
+/// Position of the value in the put request payload.
+const VALUE_POS: usize = 11;
+
+/// Start node storing integer keys and values in memory.
+async fn start_node() -> FakeNode {
+    let data = Arc::new(Mutex::new(HashMap::new()));
+
+    FakeNode::start(move |req| {
+        let mut data = data.lock().unwrap();
+        let key = || read_i32(&req.payload, KEY_POS);
+
+        let payload = match req.op_code {
+            OP_CACHE_GET => match data.get(&key()) {
+                Some(value) => {
+                    let mut payload = vec![3];
+                    put_i32(&mut payload, *value);
+                    payload
+                }
+                None => vec![101],
+            },
+            OP_CACHE_PUT => {
+                data.insert(key(), read_i32(&req.payload, VALUE_POS));
+                Vec::new()
+            }
+            OP_CACHE_CONTAINS_KEY => vec![data.contains_key(&key()) as u8],
+            OP_CACHE_REMOVE_KEY => vec![data.remove(&key()).is_some() as u8],
+            _ => Vec::new(),
+        };
+
+        FakeReply::Ok(payload)
+    })
+    .await
+}
+
+#[test]
+fn cache_key_operations() {
+    run_async(async {
+        let node = start_node().await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get(&1).await.unwrap(), None);
+        assert!(!cache.contains_key(&1).await.unwrap());
+
+        cache.put(&1, &42).await.unwrap();
+
+        assert_eq!(cache.get(&1).await.unwrap(), Some(42));
+        assert!(cache.contains_key(&1).await.unwrap());
+
+        assert!(cache.remove(&1).await.unwrap());
+        assert!(!cache.remove(&1).await.unwrap());
+
+        assert_eq!(cache.get(&1).await.unwrap(), None);
+    });
+}
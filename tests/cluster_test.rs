@@ -0,0 +1,498 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Op code of the request getting IDs of the cluster nodes.
+const OP_CLUSTER_GROUP_GET_NODE_IDS: i16 = 5100;
+
+/// Op code of the request getting details of the cluster nodes.
+const OP_CLUSTER_GROUP_GET_NODE_INFO: i16 = 5101;
+
+/// Op code of the request getting endpoints of the server nodes.
+const OP_CLUSTER_GROUP_GET_NODE_ENDPOINTS: i16 = 5102;
+
+/// Op code of the request getting state of the cluster.
+const OP_CLUSTER_GET_STATE: i16 = 5000;
+
+/// Op code of the request changing state of the cluster.
+const OP_CLUSTER_CHANGE_STATE: i16 = 5001;
+
+/// Op code of the request enabling or disabling WAL for the cache.
+const OP_CLUSTER_CHANGE_WAL_STATE: i16 = 5002;
+
+/// Op code of the request getting WAL state of the cache.
+const OP_CLUSTER_GET_WAL_STATE: i16 = 5003;
+
+/// Feature mask with cluster state management supported.
+const FEATURES_STATE: [u8; 1] = [0x04];
+
+/// Feature mask with cluster groups and node endpoints supported.
+const FEATURES_CLUSTER: [u8; 1] = [0x18];
+
+/// Server nodes of the cluster: ID and port.
+type Topology = Arc<Mutex<Vec<(u128, u16)>>>;
+
+/// Write node ID without the type header.
+fn put_id(buf: &mut Vec<u8>, id: u128) {
+    put_i64(buf, (id >> 64) as i64);
+    put_i64(buf, id as i64);
+}
+
+/// Reply to the cluster requests according to the topology.
+fn reply(topology: &Topology, req: &FakeRequest) -> FakeReply {
+    let nodes = topology.lock().unwrap().clone();
+    let mut payload = Vec::new();
+
+    match req.op_code {
+        OP_CLUSTER_GROUP_GET_NODE_ENDPOINTS => {
+            put_i64(&mut payload, 1);
+            put_i32(&mut payload, nodes.len() as i32);
+            for (id, port) in nodes {
+                put_id(&mut payload, id);
+                put_i32(&mut payload, i32::from(port));
+                put_i32(&mut payload, 1);
+                put_str(&mut payload, "127.0.0.1");
+            }
+            put_i32(&mut payload, 0);
+        }
+        OP_CLUSTER_GROUP_GET_NODE_IDS => {
+            // Client node is the last one.
+            let mut ids: Vec<u128> = nodes.iter().map(|(id, _)| *id).collect();
+            ids.push(3);
+            ids.retain(|id| node_matches(*id, &req.payload));
+
+            payload.push(1);
+            put_i64(&mut payload, 1);
+            put_i32(&mut payload, ids.len() as i32);
+            for id in ids {
+                put_id(&mut payload, id);
+            }
+        }
+        OP_CLUSTER_GROUP_GET_NODE_INFO => {
+            let count = read_i32(&req.payload, 0);
+            put_i32(&mut payload, count);
+            for i in 0..count as usize {
+                let id = read_i64(&req.payload, 4 + i * 16 + 8) as u128;
+                put_node(&mut payload, id);
+            }
+        }
+        _ => put_i32(&mut payload, 0),
+    }
+
+    FakeReply::Ok(payload)
+}
+
+/// Check if the node matches the filters of the node IDs request.
+fn node_matches(id: u128, payload: &[u8]) -> bool {
+    // Topology version goes first.
+    if payload[8] == 0 {
+        return true;
+    }
+
+    let count = read_i32(payload, 9);
+    let mut pos = 13;
+    let mut matches = true;
+
+    for _ in 0..count {
+        let code = read_i16(payload, pos);
+        pos += 2;
+
+        match code {
+            1 => {
+                let (name, next) = read_str(payload, pos);
+                let (value, next) = read_str(payload, next);
+                pos = next;
+
+                matches &=
+                    name.as_deref() == Some("region") && value == Some(region(id).to_owned());
+            }
+            2 => {
+                matches &= payload[pos] == 0 || id != 3;
+                pos += 1;
+            }
+            _ => panic!("Unknown filter: {}", code),
+        }
+    }
+
+    matches
+}
+
+/// Get region attribute of the node.
+fn region(id: u128) -> &'static str {
+    if id == 1 {
+        "eu"
+    } else {
+        "us"
+    }
+}
+
+/// Write collection of strings the way `BinaryRawWriter.writeCollection` does it.
+fn put_strings(buf: &mut Vec<u8>, strings: &[&str]) {
+    buf.push(24);
+    put_i32(buf, strings.len() as i32);
+    buf.push(1);
+    for s in strings {
+        put_str(buf, s);
+    }
+}
+
+/// Write details of the node the way `ClientClusterGroupGetNodesDetailsResponse` does it.
+/// Node 3 is a client node.
+fn put_node(buf: &mut Vec<u8>, id: u128) {
+    put_uuid(buf, id);
+
+    // Attributes, including the ones of complex types.
+    put_i32(buf, 4);
+    put_str(buf, "region");
+    put_str(buf, region(id));
+    put_str(buf, "org.apache.ignite.ignite.features");
+    buf.push(12);
+    put_i32(buf, 2);
+    buf.extend_from_slice(&[0xFF, 0x07]);
+    put_str(buf, "org.apache.ignite.rest.tcp.addrs");
+    put_strings(buf, &["127.0.0.1"]);
+    put_str(buf, "cores");
+    buf.push(3);
+    put_i32(buf, 4);
+
+    // Addresses and host names.
+    put_strings(buf, &["127.0.0.1", "10.0.0.1"]);
+    put_strings(buf, &["localhost"]);
+
+    // Order, local, daemon and client flags.
+    put_i64(buf, id as i64);
+    buf.push(0);
+    buf.push(0);
+    buf.push((id == 3) as u8);
+
+    // Consistent ID.
+    put_str(buf, &format!("node-{}", id));
+
+    // Product version: major, minor, maintenance, stage, revision timestamp and hash.
+    buf.extend_from_slice(&[2, 9, id as u8]);
+    put_str(buf, if id == 2 { "SNAPSHOT" } else { "" });
+    put_i64(buf, 1_600_000_000_000);
+    buf.push(12);
+    put_i32(buf, 20);
+    buf.extend_from_slice(&[0xAB; 20]);
+}
+
+/// Start two server nodes, both of them knowing the whole topology.
+async fn start_cluster(features: &[u8]) -> (FakeNode, FakeNode, Topology) {
+    let topology: Topology = Arc::new(Mutex::new(Vec::new()));
+
+    let mut nodes = Vec::new();
+    for id in 1..=2 {
+        let options = FakeNodeOptions {
+            version: FAKE_NODE_VERSION_1_7,
+            node_id: id,
+            features: features.to_vec(),
+            ..FakeNodeOptions::default()
+        };
+
+        let topology0 = topology.clone();
+        let node = FakeNode::start_with(options, move |req| reply(&topology0, req)).await;
+
+        let port = node.endpoint().parse::<SocketAddr>().unwrap().port();
+        topology.lock().unwrap().push((id, port));
+
+        nodes.push(node);
+    }
+
+    let node2 = nodes.pop().unwrap();
+    let node1 = nodes.pop().unwrap();
+
+    (node1, node2, topology)
+}
+
+/// Make configuration with the single seed endpoint.
+fn make_cfg(seed: &FakeNode) -> ClientConfiguration {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&seed.endpoint()).unwrap();
+    cfg
+}
+
+#[test]
+fn cluster_discovery_connects_to_all_nodes() {
+    run_async(async {
+        let (node1, node2, _) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let client = IgniteClient::start(make_cfg(&node1)).await.unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 1);
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert!(node2.requests() >= 2);
+    });
+}
+
+#[test]
+fn cluster_discovery_node_left() {
+    run_async(async {
+        let (node1, node2, topology) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let mut cfg = make_cfg(&node1);
+        cfg.set_resolve_interval(Some(Duration::from_millis(100)));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        assert_eq!(node2.connections(), 1);
+
+        topology.lock().unwrap().retain(|(id, _)| *id == 1);
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        let before = node2.requests();
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert_eq!(node2.requests(), before);
+    });
+}
+
+#[test]
+fn cluster_discovery_disabled() {
+    run_async(async {
+        let (node1, node2, _) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let mut cfg = make_cfg(&node1);
+        cfg.set_cluster_discovery(false);
+
+        let _client = IgniteClient::start(cfg).await.unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 0);
+    });
+}
+
+#[test]
+fn cluster_discovery_not_supported() {
+    run_async(async {
+        let (node1, node2, _) = start_cluster(&[]).await;
+
+        let client = IgniteClient::start(make_cfg(&node1)).await.unwrap();
+
+        assert_eq!(node2.connections(), 0);
+        assert_eq!(node1.requests(), 0);
+
+        let err = client.cluster().nodes().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    });
+}
+
+#[test]
+fn cluster_nodes() {
+    run_async(async {
+        let (node1, _node2, _) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let mut cfg = make_cfg(&node1);
+        cfg.set_cluster_discovery(false);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let nodes = client.cluster().nodes().await.unwrap();
+
+        assert_eq!(nodes.len(), 3);
+
+        let first = &nodes[0];
+        assert_eq!(first.id().as_u128(), 1);
+        assert_eq!(first.consistent_id(), Some("node-1"));
+        assert_eq!(first.version(), "2.9.1");
+        assert_eq!(first.addresses(), &["127.0.0.1", "10.0.0.1"]);
+        assert_eq!(first.host_names(), &["localhost"]);
+        assert_eq!(first.attribute("region"), Some("eu"));
+        assert_eq!(first.attribute("cores"), Some("4"));
+        assert_eq!(first.attribute("missing"), None);
+        assert_eq!(first.attribute("org.apache.ignite.ignite.features"), None);
+        assert_eq!(first.attribute("org.apache.ignite.rest.tcp.addrs"), None);
+        assert_eq!(first.order(), 1);
+        assert!(!first.is_client());
+
+        assert_eq!(nodes[1].attribute("region"), Some("us"));
+        assert_eq!(nodes[1].version(), "2.9.2-SNAPSHOT");
+        assert!(nodes[2].is_client());
+    });
+}
+
+/// State of the fake cluster: cluster state and caches with disabled WAL.
+type ClusterStates = Arc<Mutex<(i8, HashMap<String, bool>)>>;
+
+/// Start node, keeping state of the cluster and WAL of the caches.
+async fn start_state_node(features: &[u8]) -> FakeNode {
+    let states: ClusterStates = Arc::new(Mutex::new((1, HashMap::new())));
+
+    let options = FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_7,
+        node_id: 1,
+        features: features.to_vec(),
+        ..FakeNodeOptions::default()
+    };
+
+    FakeNode::start_with(options, move |req| {
+        let mut states = states.lock().unwrap();
+        let mut payload = Vec::new();
+
+        match req.op_code {
+            OP_CLUSTER_GET_STATE => payload.push(states.0 as u8),
+            OP_CLUSTER_CHANGE_STATE => states.0 = req.payload[0] as i8,
+            OP_CLUSTER_CHANGE_WAL_STATE => {
+                let (cache, pos) = read_str(&req.payload, 0);
+                let enable = req.payload[pos] != 0;
+
+                let old = states.1.insert(cache.unwrap(), enable).unwrap_or(true);
+                payload.push((old != enable) as u8);
+            }
+            OP_CLUSTER_GET_WAL_STATE => {
+                let (cache, _) = read_str(&req.payload, 0);
+                let enabled = states.1.get(&cache.unwrap()).cloned().unwrap_or(true);
+                payload.push(enabled as u8);
+            }
+            _ => return FakeReply::Err(1, "Unexpected request".to_owned()),
+        }
+
+        FakeReply::Ok(payload)
+    })
+    .await
+}
+
+#[test]
+fn cluster_state() {
+    run_async(async {
+        let node = start_state_node(&FEATURES_STATE).await;
+
+        let client = IgniteClient::start(make_cfg(&node)).await.unwrap();
+        let cluster = client.cluster();
+
+        assert_eq!(cluster.state().await.unwrap(), ClusterState::Active);
+
+        cluster.set_state(ClusterState::ReadOnly).await.unwrap();
+        assert_eq!(cluster.state().await.unwrap(), ClusterState::ReadOnly);
+
+        cluster.set_state(ClusterState::Inactive).await.unwrap();
+        assert_eq!(cluster.state().await.unwrap(), ClusterState::Inactive);
+    });
+}
+
+#[test]
+fn cluster_wal_state() {
+    run_async(async {
+        let node = start_state_node(&FEATURES_STATE).await;
+
+        let client = IgniteClient::start(make_cfg(&node)).await.unwrap();
+        let cluster = client.cluster();
+
+        assert!(cluster.is_wal_enabled("cache1").await.unwrap());
+
+        assert!(cluster.disable_wal("cache1").await.unwrap());
+        assert!(!cluster.disable_wal("cache1").await.unwrap());
+        assert!(!cluster.is_wal_enabled("cache1").await.unwrap());
+        assert!(cluster.is_wal_enabled("cache2").await.unwrap());
+
+        assert!(cluster.enable_wal("cache1").await.unwrap());
+        assert!(cluster.is_wal_enabled("cache1").await.unwrap());
+    });
+}
+
+#[test]
+fn cluster_state_not_supported() {
+    run_async(async {
+        let node = start_state_node(&[]).await;
+
+        let client = IgniteClient::start(make_cfg(&node)).await.unwrap();
+        let cluster = client.cluster();
+
+        let err = cluster.state().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = cluster.disable_wal("cache1").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        assert_eq!(node.requests(), 0);
+    });
+}
+
+/// Get sorted IDs of the nodes.
+fn ids(nodes: &[ClusterNode]) -> Vec<u128> {
+    let mut ids: Vec<u128> = nodes.iter().map(|node| node.id().as_u128()).collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn cluster_group_server_filters() {
+    run_async(async {
+        let (node1, _node2, _) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let mut cfg = make_cfg(&node1);
+        cfg.set_cluster_discovery(false);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cluster = client.cluster();
+
+        assert_eq!(ids(&cluster.group().nodes().await.unwrap()), vec![1, 2, 3]);
+        assert_eq!(
+            ids(&cluster.for_servers().nodes().await.unwrap()),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(&cluster.for_attribute("region", "us").nodes().await.unwrap()),
+            vec![2, 3]
+        );
+
+        let group = cluster.for_servers().for_attribute("region", "us");
+        assert_eq!(ids(&group.nodes().await.unwrap()), vec![2]);
+
+        let group = cluster.for_attribute("region", "asia");
+        assert!(group.nodes().await.unwrap().is_empty());
+        assert!(group.node_ids().await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn cluster_group_client_filters() {
+    run_async(async {
+        let (node1, _node2, _) = start_cluster(&FEATURES_CLUSTER).await;
+
+        let mut cfg = make_cfg(&node1);
+        cfg.set_cluster_discovery(false);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cluster = client.cluster();
+
+        let id = |id: u128| uuid::Uuid::from_u128(id);
+
+        let group = cluster.for_node_ids(vec![id(2), id(3), id(4)]);
+        assert_eq!(ids(&group.nodes().await.unwrap()), vec![2, 3]);
+
+        let mut node_ids = group.node_ids().await.unwrap();
+        node_ids.sort();
+        assert_eq!(node_ids, vec![id(2), id(3)]);
+
+        let group = cluster.for_predicate(|node| node.attribute("cores") == Some("4"));
+        assert_eq!(ids(&group.nodes().await.unwrap()), vec![1, 2, 3]);
+
+        let group = cluster.for_predicate(|node| node.is_client());
+        assert_eq!(group.node_ids().await.unwrap(), vec![id(3)]);
+
+        assert_eq!(ids(&cluster.for_oldest().nodes().await.unwrap()), vec![1]);
+        assert_eq!(ids(&cluster.for_youngest().nodes().await.unwrap()), vec![3]);
+        assert_eq!(
+            ids(&cluster.for_servers().for_youngest().nodes().await.unwrap()),
+            vec![2]
+        );
+
+        // Filters after the oldest node are applied to the oldest node only.
+        let group = cluster.for_oldest().for_attribute("region", "us");
+        assert!(group.nodes().await.unwrap().is_empty());
+    });
+}
@@ -0,0 +1,302 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::FutureExt;
+use ignite_rust::*;
+use uuid::Uuid;
+
+/// Op code of the request getting IDs of the cluster nodes.
+const OP_CLUSTER_GROUP_GET_NODE_IDS: i16 = 5100;
+
+/// Op code of the request executing the compute task.
+const OP_COMPUTE_TASK_EXECUTE: i16 = 6000;
+
+/// Op code of the notification with the result of the task.
+const OP_COMPUTE_TASK_FINISHED: i16 = 6001;
+
+/// Feature mask with task execution and cluster groups supported.
+const FEATURES_COMPUTE: [u8; 1] = [0x12];
+
+/// Task doubling the integer argument.
+const DOUBLE_TASK: &str = "org.apache.ignite.test.DoubleTask";
+
+/// Task returning null.
+const NULL_TASK: &str = "org.apache.ignite.test.NullTask";
+
+/// Task failing on the server.
+const FAILING_TASK: &str = "org.apache.ignite.test.FailingTask";
+
+/// Task which never finishes.
+const ENDLESS_TASK: &str = "org.apache.ignite.test.EndlessTask";
+
+/// Status of the failed request.
+const STATUS_FAILED: i32 = 1;
+
+/// Task execution received by the fake node.
+#[derive(Debug, Clone, PartialEq)]
+struct TaskLog {
+    node_ids: Vec<u128>,
+    flags: u8,
+    timeout: i64,
+    name: String,
+    arg: Option<i32>,
+}
+
+/// Requests received by the fake node.
+#[derive(Debug, Default, Clone)]
+struct ComputeLog {
+    tasks: Vec<TaskLog>,
+    closed: Vec<i64>,
+}
+
+/// Read the task execution request.
+fn read_task(payload: &[u8]) -> TaskLog {
+    let count = read_i32(payload, 0) as usize;
+
+    let node_ids = (0..count)
+        .map(|i| {
+            let pos = 4 + i * 16;
+            let hi = read_i64(payload, pos) as u64 as u128;
+            let lo = read_i64(payload, pos + 8) as u64 as u128;
+            (hi << 64) | lo
+        })
+        .collect();
+
+    let pos = 4 + count * 16;
+    let (name, pos) = read_str(payload, pos + 9);
+
+    let arg = match payload[pos] {
+        101 => None,
+        _ => Some(read_i32(payload, pos + 1)),
+    };
+
+    TaskLog {
+        node_ids,
+        flags: payload[4 + count * 16],
+        timeout: read_i64(payload, 4 + count * 16 + 1),
+        name: name.unwrap(),
+        arg,
+    }
+}
+
+/// Start node executing the test tasks, logging the requests.
+async fn start_node() -> (FakeNode, Arc<Mutex<ComputeLog>>) {
+    let log = Arc::new(Mutex::new(ComputeLog::default()));
+    let log0 = log.clone();
+
+    let options = FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_7,
+        features: FEATURES_COMPUTE.to_vec(),
+        ..Default::default()
+    };
+
+    let node = FakeNode::start_with(options, move |req| {
+        let mut log = log0.lock().unwrap();
+        let mut payload = Vec::new();
+
+        match req.op_code {
+            OP_COMPUTE_TASK_EXECUTE => {
+                let task = read_task(&req.payload);
+                log.tasks.push(task.clone());
+
+                let task_id = log.tasks.len() as i64;
+                put_i64(&mut payload, task_id);
+
+                let mut result = Vec::new();
+                let mut error = None;
+
+                match task.name.as_str() {
+                    DOUBLE_TASK => {
+                        result.push(3);
+                        put_i32(&mut result, task.arg.unwrap() * 2);
+                    }
+                    NULL_TASK => result.push(101),
+                    FAILING_TASK => error = Some((STATUS_FAILED, "Task failed".to_owned())),
+                    _ => return FakeReply::Ok(payload),
+                }
+
+                let finished = FakeNotification {
+                    resource_id: task_id,
+                    op_code: OP_COMPUTE_TASK_FINISHED,
+                    payload: result,
+                    error,
+                };
+
+                return FakeReply::Notify(payload, vec![finished]);
+            }
+            OP_CLUSTER_GROUP_GET_NODE_IDS => {
+                payload.push(1);
+                put_i64(&mut payload, 1);
+                put_i32(&mut payload, 2);
+                for id in 1..=2 {
+                    put_i64(&mut payload, 0);
+                    put_i64(&mut payload, id);
+                }
+            }
+            OP_RESOURCE_CLOSE => log.closed.push(read_i64(&req.payload, 0)),
+            _ => {}
+        }
+
+        FakeReply::Ok(payload)
+    })
+    .await;
+
+    (node, log)
+}
+
+#[test]
+fn compute_execute() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+
+        let res: i32 = client.compute().execute(DOUBLE_TASK, 21).await.unwrap();
+
+        assert_eq!(res, 42);
+
+        let res: Option<i32> = client
+            .compute()
+            .execute(NULL_TASK, None::<i32>)
+            .await
+            .unwrap();
+
+        assert_eq!(res, None);
+
+        // Result of another type is not converted.
+        let err = client
+            .compute()
+            .execute::<_, String>(DOUBLE_TASK, 1)
+            .await
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Int value can not be converted into"));
+
+        let tasks = log.lock().unwrap().tasks.clone();
+        assert_eq!(
+            tasks[0],
+            TaskLog {
+                node_ids: Vec::new(),
+                flags: 0,
+                timeout: 0,
+                name: DOUBLE_TASK.to_owned(),
+                arg: Some(21),
+            }
+        );
+        assert_eq!(tasks[1].arg, None);
+        assert_eq!(tasks[2].arg, Some(1));
+    });
+}
+
+#[test]
+fn compute_execute_options() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+
+        let group = client
+            .cluster()
+            .for_node_ids(vec![Uuid::from_u128(2), Uuid::from_u128(7)]);
+
+        let compute = client
+            .compute()
+            .with_cluster_group(&group)
+            .with_timeout(Duration::from_secs(3))
+            .with_no_failover()
+            .with_no_result_cache();
+
+        assert_eq!(compute.timeout(), Some(Duration::from_secs(3)));
+
+        let res: i32 = compute.execute(DOUBLE_TASK, 1).await.unwrap();
+        assert_eq!(res, 2);
+
+        let task = log.lock().unwrap().tasks[0].clone();
+        assert_eq!(task.node_ids, vec![2]);
+        assert_eq!(task.flags, 3);
+        assert_eq!(task.timeout, 3000);
+
+        // No request is sent if there are no nodes to execute the task on.
+        let group = client.cluster().for_node_ids(vec![Uuid::from_u128(7)]);
+        let res = client
+            .compute()
+            .with_cluster_group(&group)
+            .execute::<_, i32>(DOUBLE_TASK, 1)
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(log.lock().unwrap().tasks.len(), 1);
+    });
+}
+
+#[test]
+fn compute_execute_failed() {
+    run_async(async {
+        let (node, _) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+
+        let err = client
+            .compute()
+            .execute::<_, i32>(FAILING_TASK, IgniteValue::Null)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(STATUS_FAILED));
+        assert_eq!(err.to_string(), "Task failed");
+
+        // Node without the feature.
+        let node = FakeNode::start_with(
+            FakeNodeOptions {
+                version: FAKE_NODE_VERSION_1_7,
+                ..Default::default()
+            },
+            |_| FakeReply::Ok(Vec::new()),
+        )
+        .await;
+        let client = start_client_without_pa(&node).await;
+
+        let err = client
+            .compute()
+            .execute::<_, i32>(DOUBLE_TASK, 1)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    });
+}
+
+#[test]
+fn compute_cancel_on_drop() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+
+        let compute = client.compute();
+        let mut task = compute
+            .execute::<_, i32>(ENDLESS_TASK, IgniteValue::Null)
+            .boxed();
+
+        // Let the task start, then give up waiting for it.
+        let res = tokio::time::timeout(Duration::from_millis(100), &mut task).await;
+        assert!(res.is_err());
+        drop(task);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert_eq!(log.lock().unwrap().tasks.len(), 1);
+        assert_eq!(log.lock().unwrap().closed, vec![1]);
+
+        // Finished tasks are not cancelled.
+        let res: i32 = client.compute().execute(DOUBLE_TASK, 2).await.unwrap();
+
+        assert_eq!(res, 4);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+        assert_eq!(log.lock().unwrap().closed, vec![1]);
+    });
+}
@@ -0,0 +1,326 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{StreamExt, TryStreamExt};
+use ignite_rust::*;
+
+/// Op code of the request putting value by key.
+const OP_CACHE_PUT: i16 = 1001;
+
+/// Op code of the request removing the key.
+const OP_CACHE_REMOVE_KEY: i16 = 1016;
+
+/// Op code of the request executing scan query.
+const OP_QUERY_SCAN: i16 = 2000;
+
+/// Op code of the request starting continuous query.
+const OP_QUERY_CONTINUOUS: i16 = 2006;
+
+/// Op code of the notification with the continuous query events.
+const OP_QUERY_CONTINUOUS_EVENT_NOTIFICATION: i16 = 2007;
+
+/// ID of the continuous query started by the fake node.
+const QUERY_ID: i64 = 11;
+
+/// Key, which is reported as created right after the query is started.
+const WELCOME_KEY: i32 = -1;
+
+/// Key, getting which drops the connection.
+const DROP_KEY: i32 = 13;
+
+/// Position of the key in the key operation payload: after the cache ID,
+/// flags and the type header of the key.
+const KEY_POS: usize = 6;
+
+/// Position of the value in the put request payload.
+const VALUE_POS: usize = 10;
+
+/// Continuous query received by the fake node.
+#[derive(Debug, Default, Clone)]
+struct QueryLog {
+    buffer_size: i32,
+    time_interval: i64,
+    include_expired: bool,
+    filter_type_id: Option<i32>,
+    closed: Vec<i64>,
+}
+
+/// Write the event of the integer key with the string values.
+fn put_event(buf: &mut Vec<u8>, key: i32, old: Option<&str>, new: Option<&str>, event_type: u8) {
+    put_i32(buf, 1);
+
+    buf.push(3);
+    put_i32(buf, key);
+    for value in &[old, new] {
+        match value {
+            Some(value) => put_str(buf, value),
+            None => buf.push(101),
+        }
+    }
+    buf.push(event_type);
+}
+
+/// Make notification with the single event.
+fn notification(
+    key: i32,
+    old: Option<&str>,
+    new: Option<&str>,
+    event_type: u8,
+) -> FakeNotification {
+    let mut payload = Vec::new();
+    put_event(&mut payload, key, old, new, event_type);
+
+    FakeNotification {
+        resource_id: QUERY_ID,
+        op_code: OP_QUERY_CONTINUOUS_EVENT_NOTIFICATION,
+        payload,
+        error: None,
+    }
+}
+
+/// Start node storing integer keys and string values in memory, pushing the
+/// changes to the continuous query once it is started.
+async fn start_node() -> (FakeNode, Arc<Mutex<QueryLog>>) {
+    let log = Arc::new(Mutex::new(QueryLog::default()));
+    let log0 = log.clone();
+
+    let data = Mutex::new(BTreeMap::<i32, String>::new());
+    let started = Mutex::new(false);
+
+    let options = FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_4,
+        ..Default::default()
+    };
+
+    let node = FakeNode::start_with(options, move |req| {
+        let mut data = data.lock().unwrap();
+        let mut started = started.lock().unwrap();
+        let mut log = log0.lock().unwrap();
+
+        let key = || read_i32(&req.payload, KEY_POS);
+        let mut notifications = Vec::new();
+
+        match req.op_code {
+            OP_QUERY_CONTINUOUS => {
+                // Cache ID and flags go first.
+                log.buffer_size = read_i32(&req.payload, 5);
+                log.time_interval = read_i64(&req.payload, 9);
+                log.include_expired = req.payload[17] != 0;
+                log.filter_type_id = match req.payload[18] {
+                    101 => None,
+                    _ => Some(read_i32(&req.payload, 18 + 4)),
+                };
+
+                *started = true;
+
+                let mut payload = Vec::new();
+                put_i64(&mut payload, QUERY_ID);
+
+                let welcome = notification(WELCOME_KEY, None, Some("welcome"), 0);
+                return FakeReply::Notify(payload, vec![welcome]);
+            }
+            OP_QUERY_SCAN => {
+                let mut payload = Vec::new();
+                put_i64(&mut payload, QUERY_ID + 1);
+                put_i32(&mut payload, data.len() as i32);
+                for (key, value) in data.iter() {
+                    payload.push(3);
+                    put_i32(&mut payload, *key);
+                    put_str(&mut payload, value);
+                }
+                payload.push(0);
+
+                return FakeReply::Ok(payload);
+            }
+            OP_CACHE_GET if key() == DROP_KEY => return FakeReply::Drop,
+            OP_CACHE_PUT => {
+                let (value, _) = read_str(&req.payload, VALUE_POS);
+                let value = value.unwrap();
+
+                let old = data.insert(key(), value.clone());
+                let event_type = if old.is_some() { 1 } else { 0 };
+
+                if *started {
+                    notifications.push(notification(
+                        key(),
+                        old.as_deref(),
+                        Some(&value),
+                        event_type,
+                    ));
+                }
+            }
+            OP_CACHE_REMOVE_KEY => {
+                let old = data.remove(&key());
+
+                if *started {
+                    notifications.push(notification(key(), old.as_deref(), None, 2));
+                }
+            }
+            OP_RESOURCE_CLOSE => {
+                let id = read_i64(&req.payload, 0);
+                if id == QUERY_ID {
+                    *started = false;
+                }
+                log.closed.push(id);
+            }
+            _ => {}
+        }
+
+        FakeReply::Notify(vec![1], notifications)
+    })
+    .await;
+
+    (node, log)
+}
+
+/// Start client connected to the node and get the cache.
+async fn start_cache(node: &FakeNode) -> IgniteCache<i32, String> {
+    let client = start_client_without_pa(node).await;
+    client
+        .get_or_create_cache("events".to_owned())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn continuous_query_events() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let cache = start_cache(&node).await;
+
+        let mut query = ContinuousQuery::new();
+        query.set_buffer_size(10);
+        query.set_time_interval(Duration::from_millis(100));
+        query.set_include_expired(true);
+
+        let mut events = cache.continuous_query(&query).await.unwrap();
+
+        // Pushed right after the response, before the listener could be registered.
+        let event = events.try_next().await.unwrap().unwrap();
+        assert_eq!(*event.key(), WELCOME_KEY);
+        assert_eq!(event.event_type(), CacheEntryEventType::Created);
+
+        cache.put(&1, &"a".to_owned()).await.unwrap();
+        cache.put(&1, &"b".to_owned()).await.unwrap();
+        cache.remove(&1).await.unwrap();
+
+        let events: Vec<_> = events.take(3).try_collect().await.unwrap();
+
+        assert_eq!(events[0].event_type(), CacheEntryEventType::Created);
+        assert_eq!(events[0].old_value(), None);
+        assert_eq!(events[0].new_value().map(String::as_str), Some("a"));
+
+        assert_eq!(events[1].event_type(), CacheEntryEventType::Updated);
+        assert_eq!(events[1].old_value().map(String::as_str), Some("a"));
+
+        assert_eq!(
+            events[2].clone().into_parts(),
+            (1, Some("b".to_owned()), None)
+        );
+        assert_eq!(events[2].event_type(), CacheEntryEventType::Removed);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.buffer_size, 10);
+        assert_eq!(log.time_interval, 100);
+        assert!(log.include_expired);
+        assert_eq!(log.filter_type_id, None);
+        assert_eq!(log.closed, vec![QUERY_ID]);
+    });
+}
+
+#[test]
+fn continuous_query_initial_and_filter() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let cache = start_cache(&node).await;
+
+        cache.put(&1, &"a".to_owned()).await.unwrap();
+        cache.put(&2, &"b".to_owned()).await.unwrap();
+
+        let filter = BinaryObject::new("org.apache.ignite.test.EventFilter");
+
+        let mut query = ContinuousQuery::new();
+        query.set_initial_query(Some(ScanQuery::new()));
+        query.set_filter(Some(filter.clone()));
+
+        let mut events = cache.continuous_query(&query).await.unwrap();
+
+        let initial: Vec<(i32, String)> = events
+            .initial_entries()
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(initial, vec![(1, "a".to_owned()), (2, "b".to_owned())]);
+        assert!(events.initial_entries().is_none());
+
+        assert_eq!(log.lock().unwrap().filter_type_id, Some(filter.type_id()));
+    });
+}
+
+#[test]
+fn continuous_query_connection_lost() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let cache = start_cache(&node).await;
+
+        let mut events = cache
+            .continuous_query(&ContinuousQuery::new())
+            .await
+            .unwrap();
+
+        events.try_next().await.unwrap().unwrap();
+
+        assert!(cache.get(&DROP_KEY).await.is_err());
+
+        let err = events.try_next().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Connection);
+        assert!(events.next().await.is_none());
+
+        drop(events);
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert_eq!(log.lock().unwrap().closed, Vec::<i64>::new());
+    });
+}
+
+#[test]
+fn continuous_query_unexpected_type() {
+    run_async(async {
+        let (node, _) = start_node().await;
+
+        let client = start_client_without_pa(&node).await;
+        let cache: IgniteCache<i32, i64> = client
+            .get_or_create_cache("events".to_owned())
+            .await
+            .unwrap();
+
+        let mut events = cache
+            .continuous_query(&ContinuousQuery::new())
+            .await
+            .unwrap();
+
+        // Value of the welcome event is a string.
+        let err = events.try_next().await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value of type i64 is expected, got value with type header 9"
+        );
+
+        // Events of the removed keys have no values, so they are still read.
+        cache.remove(&1).await.unwrap();
+
+        let event = events.try_next().await.unwrap().unwrap();
+        assert_eq!(*event.key(), 1);
+        assert_eq!(event.event_type(), CacheEntryEventType::Removed);
+    });
+}
@@ -0,0 +1,103 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Start node, replying to every request with an empty list.
+async fn start_node() -> FakeNode {
+    FakeNode::start(|_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Make path of the endpoints file, unique for the test.
+fn endpoints_file(test: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ignite-rust-{}-{}", test, std::process::id()))
+}
+
+/// Make configuration, discovering endpoints from the file.
+fn make_cfg(path: &Path) -> ClientConfiguration {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoint_discovery(Arc::new(FileEndpoints::new(path)));
+    cfg.set_resolve_interval(Some(Duration::from_millis(100)));
+    cfg
+}
+
+#[test]
+fn discovery_endpoints_changed() {
+    run_async(async {
+        let node1 = start_node().await;
+        let node2 = start_node().await;
+
+        let path = endpoints_file("discovery-changed");
+        std::fs::write(&path, node1.endpoint()).unwrap();
+
+        let client = IgniteClient::start(make_cfg(&path)).await.unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 0);
+
+        std::fs::write(
+            &path,
+            format!("{}\n{}\n", node1.endpoint(), node2.endpoint()),
+        )
+        .unwrap();
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 1);
+
+        // Endpoint which is not listed anymore is not used.
+        std::fs::write(&path, node2.endpoint()).unwrap();
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert_eq!(node1.requests(), 0);
+        assert_eq!(node2.requests(), 4);
+
+        std::fs::remove_file(&path).unwrap();
+    });
+}
+
+#[test]
+fn discovery_failure_keeps_endpoints() {
+    run_async(async {
+        let node = start_node().await;
+
+        let path = endpoints_file("discovery-failure");
+        std::fs::write(&path, node.endpoint()).unwrap();
+
+        let client = IgniteClient::start(make_cfg(&path)).await.unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node.connections(), 1);
+        assert_eq!(node.requests(), 1);
+    });
+}
+
+#[test]
+fn discovery_overrides_endpoints() {
+    run_async(async {
+        let node = start_node().await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints("127.0.0.1:1").unwrap();
+        cfg.set_endpoint_discovery(Arc::new(StaticEndpoints::new(&node.endpoint()).unwrap()));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node.requests(), 1);
+    });
+}
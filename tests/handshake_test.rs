@@ -0,0 +1,58 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::collections::HashMap;
+
+use ignite_rust::*;
+
+/// Start node of the given version, replying to every request with an empty list.
+async fn start_node(version: (i16, i16, i16)) -> FakeNode {
+    let options = FakeNodeOptions {
+        version,
+        node_id: 1,
+        ..FakeNodeOptions::default()
+    };
+
+    FakeNode::start_with(options, |_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Make configuration with the tenant attribute.
+fn make_cfg(node: &FakeNode) -> ClientConfiguration {
+    let mut attributes = HashMap::new();
+    attributes.insert("tenant".to_owned(), "acme".to_owned());
+
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&node.endpoint()).unwrap();
+    cfg.set_user_attributes(attributes);
+    cfg
+}
+
+#[test]
+fn handshake_sends_user_attributes() {
+    run_async(async {
+        let node = start_node(FAKE_NODE_VERSION_1_7).await;
+
+        let cfg = make_cfg(&node);
+        let client = IgniteClient::start(cfg.clone()).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        assert_eq!(
+            node.user_attributes(),
+            vec![cfg.get_user_attributes().clone()]
+        );
+    });
+}
+
+#[test]
+fn handshake_user_attributes_not_supported() {
+    run_async(async {
+        let node = start_node(FAKE_NODE_VERSION_1_4).await;
+
+        let client = IgniteClient::start(make_cfg(&node)).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node.user_attributes(), Vec::new());
+    });
+}
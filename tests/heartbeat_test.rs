@@ -0,0 +1,166 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Op code of the heartbeat request.
+const OP_HEARTBEAT: i16 = 4;
+
+/// Op code of the request getting idle timeout of the node.
+const OP_GET_IDLE_TIMEOUT: i16 = 5;
+
+/// Op code of the request getting cache names.
+const OP_CACHE_GET_NAMES: i16 = 1050;
+
+/// Feature mask with heartbeats supported.
+const FEATURES_HEARTBEAT: [u8; 2] = [0x00, 0x08];
+
+/// Counters of the requests, received by the node.
+#[derive(Debug, Default)]
+struct Counters {
+    heartbeats: AtomicUsize,
+    cache_names: AtomicUsize,
+}
+
+/// Start node with the given options, counting the requests and reporting the idle timeout.
+/// The first heartbeat is delayed for a second if asked.
+async fn start_node(
+    options: FakeNodeOptions,
+    idle_timeout: i64,
+    hang_first: bool,
+) -> (FakeNode, Arc<Counters>) {
+    let counters = Arc::new(Counters::default());
+    let counters0 = counters.clone();
+
+    let node = FakeNode::start_with(options, move |req| match req.op_code {
+        OP_HEARTBEAT => {
+            if counters0.heartbeats.fetch_add(1, Ordering::SeqCst) == 0 && hang_first {
+                return FakeReply::Delayed(Duration::from_secs(1), Vec::new());
+            }
+            FakeReply::Ok(Vec::new())
+        }
+        OP_GET_IDLE_TIMEOUT => {
+            let mut payload = Vec::new();
+            put_i64(&mut payload, idle_timeout);
+            FakeReply::Ok(payload)
+        }
+        OP_CACHE_GET_NAMES => {
+            counters0.cache_names.fetch_add(1, Ordering::SeqCst);
+            FakeReply::Ok(empty_names_payload())
+        }
+        _ => FakeReply::Ok(Vec::new()),
+    })
+    .await;
+
+    (node, counters)
+}
+
+/// Options of the node supporting heartbeats.
+fn heartbeat_options() -> FakeNodeOptions {
+    FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_7,
+        features: FEATURES_HEARTBEAT.to_vec(),
+        ..FakeNodeOptions::default()
+    }
+}
+
+/// Make configuration for the client connecting to the node.
+fn make_cfg(node: &FakeNode, interval: Option<Duration>) -> ClientConfiguration {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&node.endpoint()).unwrap();
+    cfg.set_heartbeat_interval(interval);
+    cfg
+}
+
+#[test]
+fn heartbeat_interval_from_idle_timeout() {
+    run_async(async {
+        let (node, counters) = start_node(heartbeat_options(), 300, false).await;
+
+        // Heartbeats are sent every 100 ms, as the idle timeout is lower than the interval.
+        let cfg = make_cfg(&node, Some(Duration::from_secs(10)));
+        let _client = IgniteClient::start(cfg).await.unwrap();
+
+        tokio::time::delay_for(Duration::from_millis(550)).await;
+
+        let heartbeats = counters.heartbeats.load(Ordering::SeqCst);
+        assert!(heartbeats >= 3, "heartbeats: {}", heartbeats);
+        assert_eq!(counters.cache_names.load(Ordering::SeqCst), 0);
+        assert_eq!(node.connections(), 1);
+    });
+}
+
+#[test]
+fn heartbeat_not_sent_while_busy() {
+    run_async(async {
+        let (node, counters) = start_node(heartbeat_options(), 0, false).await;
+
+        let cfg = make_cfg(&node, Some(Duration::from_millis(200)));
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        for _ in 0..10 {
+            client.cache_names().await.unwrap();
+            tokio::time::delay_for(Duration::from_millis(50)).await;
+        }
+
+        assert_eq!(counters.heartbeats.load(Ordering::SeqCst), 0);
+    });
+}
+
+#[test]
+fn heartbeat_fallback_request() {
+    run_async(async {
+        let (node, counters) = start_node(FakeNodeOptions::default(), 0, false).await;
+
+        let cfg = make_cfg(&node, Some(Duration::from_millis(100)));
+        let _client = IgniteClient::start(cfg).await.unwrap();
+
+        tokio::time::delay_for(Duration::from_millis(350)).await;
+
+        assert_eq!(counters.heartbeats.load(Ordering::SeqCst), 0);
+        assert!(counters.cache_names.load(Ordering::SeqCst) >= 2);
+    });
+}
+
+#[test]
+fn heartbeat_reconnects_dead_channel() {
+    run_async(async {
+        let (node, counters) = start_node(heartbeat_options(), 0, true).await;
+
+        let cfg = make_cfg(&node, Some(Duration::from_millis(100)));
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        // The first heartbeat hangs, so the channel is replaced before the node responds.
+        tokio::time::delay_for(Duration::from_millis(500)).await;
+
+        assert_eq!(node.connections(), 2);
+        assert!(counters.heartbeats.load(Ordering::SeqCst) >= 2);
+
+        client.cache_names().await.unwrap();
+        assert_eq!(node.connections(), 2);
+    });
+}
+
+#[test]
+fn heartbeat_disabled() {
+    run_async(async {
+        let (node, counters) = start_node(heartbeat_options(), 300, false).await;
+
+        let mut cfg = make_cfg(&node, None);
+        cfg.set_tcp_nodelay(false);
+        cfg.set_tcp_keepalive(Some(Duration::from_secs(60)));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        assert_eq!(counters.heartbeats.load(Ordering::SeqCst), 0);
+        client.cache_names().await.unwrap();
+    });
+}
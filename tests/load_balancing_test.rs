@@ -0,0 +1,158 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Op code of the request creating a cache.
+const OP_CACHE_CREATE_WITH_NAME: i16 = 1051;
+
+/// Start node, responding to every request immediately.
+async fn start_node() -> FakeNode {
+    FakeNode::start(|_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Make configuration for the client connecting to the specified nodes.
+fn make_cfg(nodes: &[&FakeNode], load_balancing: LoadBalancing) -> ClientConfiguration {
+    let end_points: Vec<String> = nodes.iter().map(|n| n.endpoint()).collect();
+
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&end_points.join(",")).unwrap();
+    cfg.set_load_balancing(load_balancing);
+    cfg
+}
+
+#[test]
+fn load_balancing_round_robin() {
+    run_async(async {
+        let nodes = vec![start_node().await, start_node().await, start_node().await];
+        let refs: Vec<&FakeNode> = nodes.iter().collect();
+
+        let client = IgniteClient::start(make_cfg(&refs, LoadBalancing::RoundRobin))
+            .await
+            .unwrap();
+
+        for node in &nodes {
+            assert_eq!(node.connections(), 1);
+        }
+
+        for _ in 0..6 {
+            client.cache_names().await.unwrap();
+        }
+
+        for node in &nodes {
+            assert_eq!(node.requests(), 2);
+            assert_eq!(node.connections(), 1);
+        }
+    });
+}
+
+#[test]
+fn load_balancing_lazy_connections() {
+    run_async(async {
+        let nodes = vec![start_node().await, start_node().await, start_node().await];
+        let refs: Vec<&FakeNode> = nodes.iter().collect();
+
+        let mut cfg = make_cfg(&refs, LoadBalancing::RoundRobin);
+        cfg.set_connect_eagerly(false);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        let connected: usize = nodes.iter().map(FakeNode::connections).sum();
+        assert_eq!(connected, 1);
+
+        for _ in 0..3 {
+            client.cache_names().await.unwrap();
+        }
+
+        for node in &nodes {
+            assert_eq!(node.connections(), 1);
+            assert_eq!(node.requests(), 1);
+        }
+    });
+}
+
+#[test]
+fn load_balancing_random() {
+    run_async(async {
+        let nodes = vec![start_node().await, start_node().await];
+        let refs: Vec<&FakeNode> = nodes.iter().collect();
+
+        let client = IgniteClient::start(make_cfg(&refs, LoadBalancing::Random))
+            .await
+            .unwrap();
+
+        for _ in 0..100 {
+            client.cache_names().await.unwrap();
+        }
+
+        for node in &nodes {
+            assert!(node.requests() > 0);
+        }
+    });
+}
+
+#[test]
+fn load_balancing_least_in_flight() {
+    run_async(async {
+        let handler = |req: &FakeRequest| match req.op_code {
+            OP_CACHE_CREATE_WITH_NAME => FakeReply::Delayed(Duration::from_millis(500), Vec::new()),
+            _ => FakeReply::Ok(empty_names_payload()),
+        };
+
+        let nodes = [
+            FakeNode::start(handler).await,
+            FakeNode::start(handler).await,
+        ];
+        let refs: Vec<&FakeNode> = nodes.iter().collect();
+
+        let client = IgniteClient::start(make_cfg(&refs, LoadBalancing::LeastInFlight))
+            .await
+            .unwrap();
+        let client = Arc::new(client);
+
+        let client0 = client.clone();
+        let slow = tokio::spawn(async move {
+            client0
+                .create_cache::<i32, i32>("cache1".to_owned())
+                .await
+                .unwrap();
+        });
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        slow.await.unwrap();
+
+        let mut requests: Vec<usize> = nodes.iter().map(FakeNode::requests).collect();
+        requests.sort();
+
+        assert_eq!(requests, vec![1, 4]);
+    });
+}
+
+#[test]
+fn load_balancing_skips_unavailable_node() {
+    run_async(async {
+        let node = start_node().await;
+
+        let mut cfg = make_cfg(&[&node], LoadBalancing::RoundRobin);
+        cfg.set_endpoints(&format!("{},127.0.0.1:1", node.endpoint()))
+            .unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert_eq!(node.requests(), 4);
+    });
+}
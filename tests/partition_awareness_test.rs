@@ -0,0 +1,321 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ignite_rust::*;
+
+/// Op code of the request getting partitions of the caches.
+const OP_CACHE_PARTITIONS: i16 = 1101;
+
+/// State of the fake cluster, shared between its nodes.
+#[derive(Default)]
+struct Cluster {
+    topology: AtomicI64,
+    swapped: AtomicBool,
+    failing: AtomicBool,
+    partition_requests: AtomicUsize,
+    key_configs: Mutex<Vec<(i32, i32)>>,
+}
+
+impl Cluster {
+    /// Make payload of the partitions response. The cache has 4 partitions:
+    /// node 1 is primary for partitions 0 and 1, node 2 for partitions 2 and 3.
+    /// The nodes change their roles when swapped.
+    fn partitions_payload(&self, req: &FakeRequest) -> Vec<u8> {
+        let cache_id = read_i32(&req.payload, 4);
+        let (first, second) = if self.swapped.load(Ordering::SeqCst) {
+            (2, 1)
+        } else {
+            (1, 2)
+        };
+
+        let mut payload = Vec::new();
+        put_i64(&mut payload, self.topology.load(Ordering::SeqCst));
+        put_i32(&mut payload, 0);
+
+        put_i32(&mut payload, 1);
+        payload.push(1);
+
+        put_i32(&mut payload, 1);
+        put_i32(&mut payload, cache_id);
+
+        let key_configs = self.key_configs.lock().unwrap();
+        put_i32(&mut payload, key_configs.len() as i32);
+        for (type_id, field_id) in key_configs.iter() {
+            put_i32(&mut payload, *type_id);
+            put_i32(&mut payload, *field_id);
+        }
+
+        put_i32(&mut payload, 2);
+        for (node_id, parts) in [(first, [0, 1]), (second, [2, 3])].iter() {
+            put_uuid(&mut payload, *node_id);
+            put_i32(&mut payload, parts.len() as i32);
+            for part in parts {
+                put_i32(&mut payload, *part);
+            }
+        }
+
+        payload
+    }
+}
+
+/// Start node with the given ID, recording payloads of the get requests it receives.
+async fn start_node(cluster: Arc<Cluster>, node_id: u128) -> (FakeNode, Arc<Mutex<Vec<Vec<u8>>>>) {
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let keys0 = keys.clone();
+
+    let options = FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_4,
+        node_id,
+        ..FakeNodeOptions::default()
+    };
+
+    let node = FakeNode::start_with(options, move |req| match req.op_code {
+        OP_CACHE_PARTITIONS => {
+            cluster.partition_requests.fetch_add(1, Ordering::SeqCst);
+            if cluster.failing.load(Ordering::SeqCst) {
+                FakeReply::Err(1, "Partitions are not available".to_owned())
+            } else {
+                FakeReply::Ok(cluster.partitions_payload(req))
+            }
+        }
+        OP_CACHE_GET => {
+            keys0.lock().unwrap().push(req.payload.clone());
+            FakeReply::Ok(vec![101])
+        }
+        _ => FakeReply::Ok(empty_names_payload()),
+    })
+    .await;
+
+    node.set_topology_version(1);
+
+    (node, keys)
+}
+
+/// Set topology version on all the nodes of the cluster.
+fn set_topology_version(cluster: &Cluster, nodes: &[&FakeNode], ver: i64) {
+    cluster.topology.store(ver, Ordering::SeqCst);
+    for node in nodes {
+        node.set_topology_version(ver);
+    }
+}
+
+/// Take integer keys, received by the node.
+fn take_keys(payloads: &Mutex<Vec<Vec<u8>>>) -> Vec<i32> {
+    payloads
+        .lock()
+        .unwrap()
+        .drain(..)
+        // Cache ID and flags, followed by the key with the type header.
+        .map(|payload| read_i32(&payload, 6))
+        .collect()
+}
+
+/// Calculate partition of the key the same way as the fake cluster does it.
+fn partition(hash: i32) -> i32 {
+    (hash ^ ((hash as u32) >> 16) as i32) & 3
+}
+
+#[test]
+fn partition_awareness_routes_to_primary_node() {
+    run_async(async {
+        let cluster = Arc::new(Cluster::default());
+
+        let (node1, keys1) = start_node(cluster.clone(), 1).await;
+        let (node2, keys2) = start_node(cluster.clone(), 2).await;
+        set_topology_version(&cluster, &[&node1, &node2], 1);
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        // Topology version is learned from the first response.
+        client.cache_names().await.unwrap();
+
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        for key in 0..8 {
+            assert_eq!(cache.get(&key).await.unwrap(), None);
+        }
+
+        assert_eq!(take_keys(&keys1), vec![0, 1, 4, 5]);
+        assert_eq!(take_keys(&keys2), vec![2, 3, 6, 7]);
+        assert_eq!(cluster.partition_requests.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn partition_awareness_refreshes_on_topology_change() {
+    run_async(async {
+        let cluster = Arc::new(Cluster::default());
+
+        let (node1, keys1) = start_node(cluster.clone(), 1).await;
+        let (node2, keys2) = start_node(cluster.clone(), 2).await;
+        set_topology_version(&cluster, &[&node1, &node2], 1);
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        for key in 0..4 {
+            cache.get(&key).await.unwrap();
+        }
+
+        assert_eq!(take_keys(&keys1), vec![0, 1]);
+        assert_eq!(take_keys(&keys2), vec![2, 3]);
+
+        cluster.swapped.store(true, Ordering::SeqCst);
+        set_topology_version(&cluster, &[&node1, &node2], 2);
+
+        // Any response carries the new topology version.
+        client.cache_names().await.unwrap();
+
+        for key in 0..4 {
+            cache.get(&key).await.unwrap();
+        }
+
+        assert_eq!(take_keys(&keys1), vec![2, 3]);
+        assert_eq!(take_keys(&keys2), vec![0, 1]);
+        assert_eq!(cluster.partition_requests.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn partition_awareness_disabled() {
+    run_async(async {
+        let cluster = Arc::new(Cluster::default());
+
+        let (node1, keys1) = start_node(cluster.clone(), 1).await;
+        let (node2, keys2) = start_node(cluster.clone(), 2).await;
+        set_topology_version(&cluster, &[&node1, &node2], 1);
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+        cfg.set_partition_awareness(false);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        for key in 0..4 {
+            cache.get(&key).await.unwrap();
+        }
+
+        assert_eq!(take_keys(&keys1).len(), 2);
+        assert_eq!(take_keys(&keys2).len(), 2);
+        assert_eq!(cluster.partition_requests.load(Ordering::SeqCst), 0);
+    });
+}
+
+#[test]
+fn partition_awareness_uses_affinity_key_field() {
+    run_async(async {
+        let cluster = Arc::new(Cluster::default());
+        cluster.key_configs.lock().unwrap().push((
+            BinaryObject::type_id_of("PersonKey"),
+            BinaryObject::field_id_of("companyId"),
+        ));
+
+        let (node1, keys1) = start_node(cluster.clone(), 1).await;
+        let (node2, keys2) = start_node(cluster.clone(), 2).await;
+        set_topology_version(&cluster, &[&node1, &node2], 1);
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        let cache = client
+            .get_or_create_cache::<BinaryObject, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        // Company 2 is stored in the partition of node 2.
+        let keys: Vec<BinaryObject> = (0..8)
+            .map(|id| {
+                let mut key = BinaryObject::new("PersonKey");
+                key.set_field("id", &id);
+                key.set_field("companyId", &2);
+                key
+            })
+            .collect();
+
+        // Without the affinity key field some of the keys would go to node 1.
+        assert!(keys.iter().any(|key| partition(key.ignite_hash()) < 2));
+
+        for key in &keys {
+            cache.get(key).await.unwrap();
+        }
+
+        assert_eq!(keys1.lock().unwrap().len(), 0);
+        assert_eq!(keys2.lock().unwrap().len(), 8);
+    });
+}
+
+#[test]
+fn partition_awareness_failed_refresh() {
+    run_async(async {
+        let cluster = Arc::new(Cluster::default());
+        cluster.failing.store(true, Ordering::SeqCst);
+
+        let (node1, keys1) = start_node(cluster.clone(), 1).await;
+        let (node2, keys2) = start_node(cluster.clone(), 2).await;
+        set_topology_version(&cluster, &[&node1, &node2], 1);
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&format!("{},{}", node1.endpoint(), node2.endpoint()))
+            .unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        for key in 0..4 {
+            cache.get(&key).await.unwrap();
+        }
+
+        // Partitions are not requested again until the topology changes.
+        assert_eq!(take_keys(&keys1).len() + take_keys(&keys2).len(), 4);
+        assert_eq!(cluster.partition_requests.load(Ordering::SeqCst), 1);
+
+        cluster.failing.store(false, Ordering::SeqCst);
+        set_topology_version(&cluster, &[&node1, &node2], 2);
+        client.cache_names().await.unwrap();
+
+        for key in 0..4 {
+            cache.get(&key).await.unwrap();
+        }
+
+        assert_eq!(take_keys(&keys1), vec![0, 1]);
+        assert_eq!(take_keys(&keys2), vec![2, 3]);
+        assert_eq!(cluster.partition_requests.load(Ordering::SeqCst), 2);
+    });
+}
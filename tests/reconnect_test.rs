@@ -0,0 +1,173 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Op code of the request getting cache names.
+const OP_CACHE_GET_NAMES: i16 = 1050;
+
+/// Op code of the request creating a cache.
+const OP_CACHE_CREATE_WITH_NAME: i16 = 1051;
+
+/// Make payload of the cache names response.
+fn cache_names_payload(names: &[&str]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    put_i32(&mut payload, names.len() as i32);
+    for name in names {
+        put_str(&mut payload, name);
+    }
+    payload
+}
+
+/// Make configuration for the client connecting to the specified nodes.
+fn make_cfg(nodes: &[&FakeNode]) -> ClientConfiguration {
+    let end_points: Vec<String> = nodes.iter().map(|n| n.endpoint()).collect();
+
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&end_points.join(",")).unwrap();
+    cfg.set_reconnect_backoff(Duration::from_millis(10), Duration::from_millis(50));
+    cfg
+}
+
+#[test]
+fn reconnect_retries_idempotent_operation() {
+    run_async(async {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let dropped0 = dropped.clone();
+
+        let node = FakeNode::start(move |_| {
+            if dropped0.fetch_add(1, Ordering::SeqCst) == 0 {
+                return FakeReply::Drop;
+            }
+            FakeReply::Ok(cache_names_payload(&["cache1"]))
+        })
+        .await;
+
+        let client = IgniteClient::start(make_cfg(&[&node])).await.unwrap();
+        let names = client.cache_names().await.unwrap();
+
+        assert_eq!(names, vec!["cache1".to_owned()]);
+        assert_eq!(node.connections(), 2);
+        assert_eq!(node.requests(), 2);
+    });
+}
+
+#[test]
+fn reconnect_does_not_retry_non_idempotent_operation() {
+    run_async(async {
+        let node = FakeNode::start(|req| match req.op_code {
+            OP_CACHE_CREATE_WITH_NAME => FakeReply::Drop,
+            _ => FakeReply::Ok(cache_names_payload(&[])),
+        })
+        .await;
+
+        let client = IgniteClient::start(make_cfg(&[&node])).await.unwrap();
+
+        let err = client
+            .create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Connection);
+        assert_eq!(node.requests(), 1);
+
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node.connections(), 2);
+    });
+}
+
+#[test]
+fn reconnect_to_another_node() {
+    run_async(async {
+        let broken = FakeNode::start(|_| FakeReply::Drop).await;
+        let healthy = FakeNode::start(|_| FakeReply::Ok(cache_names_payload(&["cache1"]))).await;
+
+        // Failed node should not be tried again for the duration of the test.
+        let mut cfg = make_cfg(&[&broken, &healthy]);
+        cfg.set_reconnect_backoff(Duration::from_secs(10), Duration::from_secs(10));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        for _ in 0..5 {
+            let names = client.cache_names().await.unwrap();
+            assert_eq!(names, vec!["cache1".to_owned()]);
+        }
+
+        assert!(broken.requests() <= 1);
+    });
+}
+
+#[test]
+fn reconnect_respects_retry_limit() {
+    run_async(async {
+        let node = FakeNode::start(|_| FakeReply::Drop).await;
+
+        let mut cfg = make_cfg(&[&node]);
+        cfg.set_retry_limit(2);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let err = client.cache_names().await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Connection);
+        assert_eq!(node.requests(), 3);
+    });
+}
+
+#[test]
+fn reconnect_uses_custom_retry_policy() {
+    run_async(async {
+        let created = Arc::new(AtomicUsize::new(0));
+        let created0 = created.clone();
+
+        let node = FakeNode::start(move |req| match req.op_code {
+            OP_CACHE_GET_NAMES => FakeReply::Drop,
+            OP_CACHE_CREATE_WITH_NAME if created0.fetch_add(1, Ordering::SeqCst) == 0 => {
+                FakeReply::Drop
+            }
+            _ => FakeReply::Ok(Vec::new()),
+        })
+        .await;
+
+        let mut cfg = make_cfg(&[&node]);
+        cfg.set_retry_policy(Arc::new(RetryNonePolicy));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client.cache_names().await.unwrap_err();
+
+        assert_eq!(node.requests(), 1);
+
+        let mut cfg = make_cfg(&[&node]);
+        cfg.set_retry_policy(Arc::new(RetryAllPolicy));
+        cfg.set_retry_limit(1);
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        client
+            .create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn reconnect_reports_server_errors_without_retry() {
+    run_async(async {
+        let node =
+            FakeNode::start(|_| FakeReply::Err(1001, "Cache already exists".to_owned())).await;
+
+        let client = IgniteClient::start(make_cfg(&[&node])).await.unwrap();
+        let err = client.cache_names().await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(1001));
+        assert_eq!(err.to_string(), "Cache already exists");
+        assert_eq!(node.requests(), 1);
+    });
+}
@@ -0,0 +1,136 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignite_rust::*;
+
+/// Host name, resolved by the static resolver.
+const HOST: &str = "ignite.local";
+
+/// Start node, listening on the given address and replying to every request with an empty list.
+async fn start_node(addr: SocketAddr) -> FakeNode {
+    let options = FakeNodeOptions {
+        addr: Some(addr),
+        ..FakeNodeOptions::default()
+    };
+
+    FakeNode::start_with(options, |_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Start two nodes on the same port of different loopback addresses.
+async fn start_nodes() -> (FakeNode, FakeNode, u16) {
+    let node1 = start_node("127.0.0.1:0".parse().unwrap()).await;
+    let port = node1.endpoint().parse::<SocketAddr>().unwrap().port();
+
+    let node2 = start_node(SocketAddr::new(ip("127.0.0.2"), port)).await;
+
+    (node1, node2, port)
+}
+
+/// Parse IP address.
+fn ip(ip: &str) -> IpAddr {
+    ip.parse().unwrap()
+}
+
+/// Make configuration, resolving the host with the given resolver.
+fn make_cfg(resolver: Arc<StaticResolver>, port: u16) -> ClientConfiguration {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&format!("{}:{}", HOST, port)).unwrap();
+    cfg.set_resolver(resolver);
+    cfg.set_resolve_interval(Some(Duration::from_millis(100)));
+    cfg
+}
+
+#[test]
+fn resolver_connects_to_all_addresses() {
+    run_async(async {
+        let (node1, node2, port) = start_nodes().await;
+
+        let resolver = Arc::new(StaticResolver::new());
+        resolver.set_host(HOST, vec![ip("127.0.0.1"), ip("127.0.0.2")]);
+
+        let client = IgniteClient::start(make_cfg(resolver, port)).await.unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 1);
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert_eq!(node1.requests(), 2);
+        assert_eq!(node2.requests(), 2);
+    });
+}
+
+#[test]
+fn resolver_new_address_connected() {
+    run_async(async {
+        let (node1, node2, port) = start_nodes().await;
+
+        let resolver = Arc::new(StaticResolver::new());
+        resolver.set_host(HOST, vec![ip("127.0.0.1")]);
+
+        let client = IgniteClient::start(make_cfg(resolver.clone(), port))
+            .await
+            .unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 0);
+
+        resolver.set_host(HOST, vec![ip("127.0.0.1"), ip("127.0.0.2")]);
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node2.connections(), 1);
+
+        // Address which is not resolved anymore is not used.
+        resolver.set_host(HOST, vec![ip("127.0.0.2")]);
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        for _ in 0..4 {
+            client.cache_names().await.unwrap();
+        }
+
+        assert_eq!(node1.requests(), 0);
+        assert_eq!(node2.requests(), 4);
+    });
+}
+
+#[test]
+fn resolver_failure_keeps_addresses() {
+    run_async(async {
+        let (node1, _node2, port) = start_nodes().await;
+
+        let resolver = Arc::new(StaticResolver::new());
+        resolver.set_host(HOST, vec![ip("127.0.0.1")]);
+
+        let client = IgniteClient::start(make_cfg(resolver.clone(), port))
+            .await
+            .unwrap();
+
+        resolver.remove_host(HOST);
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node1.connections(), 1);
+        assert_eq!(node1.requests(), 1);
+    });
+}
+
+#[test]
+fn resolver_unknown_host() {
+    run_async(async {
+        let resolver = Arc::new(StaticResolver::new());
+
+        let res = IgniteClient::start(make_cfg(resolver, 10800)).await;
+
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Connection);
+    });
+}
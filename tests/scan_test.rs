@@ -0,0 +1,242 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{StreamExt, TryStreamExt};
+use ignite_rust::*;
+
+/// Op code of the request executing scan query.
+const OP_QUERY_SCAN: i16 = 2000;
+
+/// Op code of the request getting the next page of scan query.
+const OP_QUERY_SCAN_CURSOR_GET_PAGE: i16 = 2001;
+
+/// ID of the cursor returned by the fake node.
+const CURSOR_ID: i64 = 7;
+
+/// Number of entries in the fake cache.
+const ENTRY_COUNT: i32 = 7;
+
+/// Number of partitions of the fake cache.
+const PARTITION_COUNT: i32 = 2;
+
+/// Name of the filter class, which only keeps entries with the even keys.
+const EVEN_FILTER: &str = "org.apache.ignite.test.EvenKeyFilter";
+
+/// Scan query received by the fake node.
+#[derive(Debug, Default, Clone)]
+struct ScanLog {
+    filter_type_id: Option<i32>,
+    filter_platform: i8,
+    partition: i32,
+    local: bool,
+    pages: usize,
+    closed: Vec<i64>,
+}
+
+/// Write page of the entries, starting with the given one.
+/// Every value is the string made out of the key.
+fn put_page(buf: &mut Vec<u8>, keys: &[i32], from: usize, page_size: usize) -> usize {
+    let to = keys.len().min(from + page_size);
+
+    put_i32(buf, (to - from) as i32);
+    for key in &keys[from..to] {
+        buf.push(3);
+        put_i32(buf, *key);
+        put_str(buf, &format!("value-{}", key));
+    }
+    buf.push((to < keys.len()) as u8);
+
+    to
+}
+
+/// Start node serving the fake cache, logging the queries.
+async fn start_scan_node() -> (FakeNode, Arc<Mutex<ScanLog>>) {
+    let log = Arc::new(Mutex::new(ScanLog::default()));
+    let log0 = log.clone();
+
+    // Keys, page size and the next entry of the open cursor.
+    let cursor = Mutex::new((Vec::new(), 0, 0));
+
+    let node = FakeNode::start(move |req| {
+        let mut log = log0.lock().unwrap();
+        let mut payload = Vec::new();
+
+        match req.op_code {
+            OP_QUERY_SCAN => {
+                // Cache ID and flags go first, then the filter object.
+                let mut pos = 5;
+                log.filter_type_id = None;
+                if req.payload[pos] == 101 {
+                    pos += 1;
+                } else {
+                    log.filter_type_id = Some(read_i32(&req.payload, pos + 4));
+                    pos += read_i32(&req.payload, pos + 12) as usize;
+                    log.filter_platform = req.payload[pos] as i8;
+                    pos += 1;
+                }
+
+                let page_size = read_i32(&req.payload, pos) as usize;
+                log.partition = read_i32(&req.payload, pos + 4);
+                log.local = req.payload[pos + 8] != 0;
+
+                let partition = log.partition;
+                let filtered = log.filter_type_id.is_some();
+                let keys: Vec<i32> = (0..ENTRY_COUNT)
+                    .filter(|key| partition < 0 || key % PARTITION_COUNT == partition)
+                    .filter(|key| !filtered || key % 2 == 0)
+                    .collect();
+
+                put_i64(&mut payload, CURSOR_ID);
+                let next = put_page(&mut payload, &keys, 0, page_size);
+                *cursor.lock().unwrap() = (keys, page_size, next);
+            }
+            OP_QUERY_SCAN_CURSOR_GET_PAGE => {
+                assert_eq!(read_i64(&req.payload, 0), CURSOR_ID);
+                log.pages += 1;
+
+                let mut cursor = cursor.lock().unwrap();
+                let (keys, page_size, next) = &mut *cursor;
+                *next = put_page(&mut payload, keys, *next, *page_size);
+            }
+            OP_RESOURCE_CLOSE => log.closed.push(read_i64(&req.payload, 0)),
+            _ => {}
+        }
+
+        FakeReply::Ok(payload)
+    })
+    .await;
+
+    (node, log)
+}
+
+/// Start client connected to the node and get the fake cache.
+async fn start_cache(node: &FakeNode) -> IgniteCache<i32, String> {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&node.endpoint()).unwrap();
+
+    let client = IgniteClient::start(cfg).await.unwrap();
+    client.get_or_create_cache("scan".to_owned()).await.unwrap()
+}
+
+#[test]
+fn scan_query_pages() {
+    run_async(async {
+        let (node, log) = start_scan_node().await;
+        let cache = start_cache(&node).await;
+
+        let mut query = ScanQuery::new();
+        query.set_page_size(3);
+
+        let entries: Vec<(i32, String)> = cache
+            .scan(&query)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(entries.len(), ENTRY_COUNT as usize);
+        assert_eq!(entries[0], (0, "value-0".to_owned()));
+        assert_eq!(entries[6], (6, "value-6".to_owned()));
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.filter_type_id, None);
+        assert_eq!(log.partition, -1);
+        assert!(!log.local);
+        assert_eq!(log.pages, 2);
+        assert_eq!(log.closed, Vec::<i64>::new());
+    });
+}
+
+#[test]
+fn scan_query_partition_and_filter() {
+    run_async(async {
+        let (node, log) = start_scan_node().await;
+        let cache = start_cache(&node).await;
+
+        let mut query = ScanQuery::new();
+        query.set_partition(Some(1));
+        query.set_local(true);
+
+        let keys: Vec<i32> = cache
+            .scan(&query)
+            .await
+            .unwrap()
+            .map_ok(|(key, _)| key)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(keys, vec![1, 3, 5]);
+        assert_eq!(log.lock().unwrap().partition, 1);
+        assert!(log.lock().unwrap().local);
+
+        let mut query = ScanQuery::new();
+        query.set_filter(Some(BinaryObject::new(EVEN_FILTER)));
+
+        let keys: Vec<i32> = cache
+            .scan(&query)
+            .await
+            .unwrap()
+            .map_ok(|(key, _)| key)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(keys, vec![0, 2, 4, 6]);
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(
+            log.filter_type_id,
+            Some(BinaryObject::type_id_of(EVEN_FILTER))
+        );
+        assert_eq!(log.filter_platform, 1);
+    });
+}
+
+#[test]
+fn scan_query_close_on_drop() {
+    run_async(async {
+        let (node, log) = start_scan_node().await;
+        let cache = start_cache(&node).await;
+
+        let mut query = ScanQuery::new();
+        query.set_page_size(2);
+
+        let mut cursor = cache.scan(&query).await.unwrap();
+        cursor.next().await.unwrap().unwrap();
+        drop(cursor);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert_eq!(log.lock().unwrap().closed, vec![CURSOR_ID]);
+    });
+}
+
+#[test]
+fn scan_query_unexpected_type() {
+    run_async(async {
+        let (node, _) = start_scan_node().await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cache: IgniteCache<i32, i64> =
+            client.get_or_create_cache("scan".to_owned()).await.unwrap();
+
+        // Values of the fake cache are strings.
+        let err = cache.scan(&ScanQuery::new()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value of type i64 is expected, got value with type header 9"
+        );
+    });
+}
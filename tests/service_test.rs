@@ -0,0 +1,335 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ignite_rust::*;
+use uuid::Uuid;
+
+/// Op code of the request getting IDs of the cluster nodes.
+const OP_CLUSTER_GROUP_GET_NODE_IDS: i16 = 5100;
+
+/// Op code of the request invoking method of the service.
+const OP_SERVICE_INVOKE: i16 = 7000;
+
+/// Op code of the request getting descriptors of all the services.
+const OP_SERVICE_GET_DESCRIPTORS: i16 = 7001;
+
+/// Op code of the request getting descriptor of the service.
+const OP_SERVICE_GET_DESCRIPTOR: i16 = 7002;
+
+/// Feature mask with service invocation, descriptors and cluster groups supported.
+const FEATURES_SERVICES: [u8; 2] = [0x30, 0x02];
+
+/// Name of the service deployed on the fake node.
+const SERVICE_NAME: &str = "calculator";
+
+/// Status of the failed request.
+const STATUS_FAILED: i32 = 1;
+
+/// Service invocation received by the fake node.
+#[derive(Debug, Clone, PartialEq)]
+struct Invocation {
+    service: String,
+    flags: u8,
+    timeout: i64,
+    node_ids: Vec<u128>,
+    method: String,
+    args: Vec<Option<i64>>,
+    param_types: Vec<i32>,
+}
+
+/// Read integer argument, returning the position after it.
+fn read_arg(payload: &[u8], pos: usize) -> (Option<i64>, usize) {
+    match payload[pos] {
+        3 => (Some(i64::from(read_i32(payload, pos + 1))), pos + 5),
+        4 => (Some(read_i64(payload, pos + 1)), pos + 9),
+        101 => (None, pos + 1),
+        hdr => panic!("Unexpected argument type: {}", hdr),
+    }
+}
+
+/// Read the service invocation request.
+fn read_invocation(payload: &[u8]) -> Invocation {
+    let (service, pos) = read_str(payload, 0);
+    let flags = payload[pos];
+    let timeout = read_i64(payload, pos + 1);
+
+    let count = read_i32(payload, pos + 9) as usize;
+    let node_ids = (0..count)
+        .map(|i| read_i64(payload, pos + 13 + i * 16 + 8) as u128)
+        .collect();
+
+    let (method, pos) = read_str(payload, pos + 13 + count * 16);
+
+    let count = read_i32(payload, pos);
+    let mut pos = pos + 4;
+
+    let mut args = Vec::new();
+    let mut param_types = Vec::new();
+    for _ in 0..count {
+        if flags & 2 != 0 {
+            param_types.push(read_i32(payload, pos));
+            pos += 4;
+        }
+
+        let (arg, next) = read_arg(payload, pos);
+        args.push(arg);
+        pos = next;
+    }
+
+    Invocation {
+        service: service.unwrap(),
+        flags,
+        timeout,
+        node_ids,
+        method: method.unwrap(),
+        args,
+        param_types,
+    }
+}
+
+/// Write descriptor of the calculator service.
+fn put_descriptor(buf: &mut Vec<u8>) {
+    put_str(buf, SERVICE_NAME);
+    put_str(buf, "org.apache.ignite.test.CalculatorService");
+    put_i32(buf, 0);
+    put_i32(buf, 1);
+    buf.push(101);
+    put_uuid(buf, 5);
+    buf.push(0);
+}
+
+/// Start node serving the calculator service, logging the invocations.
+async fn start_node() -> (FakeNode, Arc<Mutex<Vec<Invocation>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let log0 = log.clone();
+
+    let options = FakeNodeOptions {
+        version: FAKE_NODE_VERSION_1_7,
+        features: FEATURES_SERVICES.to_vec(),
+        ..Default::default()
+    };
+
+    let node = FakeNode::start_with(options, move |req| {
+        let mut payload = Vec::new();
+
+        match req.op_code {
+            OP_SERVICE_INVOKE => {
+                let invocation = read_invocation(&req.payload);
+                log0.lock().unwrap().push(invocation.clone());
+
+                let args: Vec<i64> = invocation.args.iter().flatten().copied().collect();
+                match invocation.method.as_str() {
+                    "calculate" => {
+                        payload.push(4);
+                        put_i64(&mut payload, args[0] * 2);
+                    }
+                    "sum" => {
+                        payload.push(4);
+                        put_i64(&mut payload, args.iter().sum());
+                    }
+                    "getTotal" => payload.push(101),
+                    _ => return FakeReply::Err(STATUS_FAILED, "Method not found".to_owned()),
+                }
+            }
+            OP_SERVICE_GET_DESCRIPTORS => {
+                put_i32(&mut payload, 1);
+                put_descriptor(&mut payload);
+            }
+            OP_SERVICE_GET_DESCRIPTOR => match read_str(&req.payload, 0).0.as_deref() {
+                Some(SERVICE_NAME) => put_descriptor(&mut payload),
+                _ => return FakeReply::Err(STATUS_FAILED, "Service not found".to_owned()),
+            },
+            OP_CLUSTER_GROUP_GET_NODE_IDS => {
+                payload.push(1);
+                put_i64(&mut payload, 1);
+                put_i32(&mut payload, 2);
+                for id in 1..=2 {
+                    put_i64(&mut payload, 0);
+                    put_i64(&mut payload, id);
+                }
+            }
+            _ => {}
+        }
+
+        FakeReply::Ok(payload)
+    })
+    .await;
+
+    (node, log)
+}
+
+/// Proxy of the calculator service.
+#[service_proxy]
+trait Calculator {
+    /// Double the value.
+    async fn calculate(&self, x: i32) -> IgniteResult<i64>;
+
+    /// Add two values, invoking the overloaded method.
+    #[ignite(rename = "sum", param_types("long", "java.lang.Long"))]
+    async fn add(&self, a: i64, b: Option<i64>) -> IgniteResult<i64>;
+
+    /// Get total of all the calculations, which is not known.
+    fn get_total(&self) -> IgniteResult<Option<i64>>;
+}
+
+#[test]
+fn services_invoke() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+        let services = client.services();
+
+        let res: i64 = services
+            .invoke(
+                SERVICE_NAME,
+                "calculate",
+                &[21i32.into()],
+                Some(Duration::from_secs(5)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res, 42);
+
+        let res: i64 = services
+            .invoke_with_param_types(
+                SERVICE_NAME,
+                "sum",
+                &[1i64.into(), 2i32.into()],
+                &["long", "int"],
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res, 3);
+
+        let err = services
+            .invoke::<i64>(SERVICE_NAME, "unknown", &[], None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(STATUS_FAILED));
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(
+            log[0],
+            Invocation {
+                service: SERVICE_NAME.to_owned(),
+                flags: 1,
+                timeout: 5000,
+                node_ids: Vec::new(),
+                method: "calculate".to_owned(),
+                args: vec![Some(21)],
+                param_types: Vec::new(),
+            }
+        );
+        assert_eq!(log[1].flags, 3);
+        assert_eq!(log[1].param_types, vec![4, 3]);
+        assert_eq!(log[1].args, vec![Some(1), Some(2)]);
+    });
+}
+
+#[test]
+fn services_proxy() {
+    run_async(async {
+        let (node, log) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+
+        let group = client
+            .cluster()
+            .for_node_ids(vec![Uuid::from_u128(1), Uuid::from_u128(9)]);
+
+        let calculator = client
+            .services()
+            .with_cluster_group(&group)
+            .service_proxy(SERVICE_NAME)
+            .with_timeout(Duration::from_millis(300));
+
+        assert_eq!(calculator.name(), SERVICE_NAME);
+
+        assert_eq!(calculator.calculate(5).await.unwrap(), 10);
+        assert_eq!(calculator.add(5, Some(6)).await.unwrap(), 11);
+        assert_eq!(calculator.get_total().await.unwrap(), None);
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.len(), 3);
+        assert!(log
+            .iter()
+            .all(|inv| inv.node_ids == vec![1] && inv.timeout == 300));
+
+        assert_eq!(log[1].method, "sum");
+        assert_eq!(log[1].param_types, vec![4, 4]);
+        assert_eq!(log[2].method, "getTotal");
+        assert!(log[2].args.is_empty());
+
+        // Wrong result type.
+        let res = client
+            .services()
+            .invoke::<String>(SERVICE_NAME, "calculate", &[1i32.into()], None)
+            .await;
+
+        assert!(res.is_err());
+    });
+}
+
+#[test]
+fn services_descriptors() {
+    run_async(async {
+        let (node, _) = start_node().await;
+        let client = start_client_without_pa(&node).await;
+        let services = client.services();
+
+        let descriptors = services.service_descriptors().await.unwrap();
+        assert_eq!(descriptors.len(), 1);
+
+        let descriptor = &descriptors[0];
+        assert_eq!(descriptor.name(), SERVICE_NAME);
+        assert_eq!(
+            descriptor.service_class(),
+            "org.apache.ignite.test.CalculatorService"
+        );
+        assert_eq!(descriptor.total_count(), 0);
+        assert_eq!(descriptor.max_per_node_count(), 1);
+        assert_eq!(descriptor.cache_name(), None);
+        assert_eq!(descriptor.origin_node_id(), Some(Uuid::from_u128(5)));
+        assert_eq!(descriptor.platform(), ServicePlatform::Java);
+
+        assert_eq!(
+            services.service_descriptor(SERVICE_NAME).await.unwrap(),
+            *descriptor
+        );
+        assert!(services.service_descriptor("unknown").await.is_err());
+    });
+}
+
+#[test]
+fn services_unsupported() {
+    run_async(async {
+        let node = FakeNode::start_with(
+            FakeNodeOptions {
+                version: FAKE_NODE_VERSION_1_7,
+                ..Default::default()
+            },
+            |_| FakeReply::Ok(Vec::new()),
+        )
+        .await;
+        let client = start_client_without_pa(&node).await;
+
+        let err = client
+            .services()
+            .invoke::<i64>(SERVICE_NAME, "calculate", &[1i32.into()], None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = client.services().service_descriptors().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    });
+}
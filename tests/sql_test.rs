@@ -0,0 +1,352 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{StreamExt, TryStreamExt};
+use ignite_rust::*;
+
+/// Op code of the request executing SQL fields query.
+const OP_QUERY_SQL_FIELDS: i16 = 2004;
+
+/// Op code of the request getting the next page of SQL fields query.
+const OP_QUERY_SQL_FIELDS_CURSOR_GET_PAGE: i16 = 2005;
+
+/// ID of the cursor returned by the fake node.
+const CURSOR_ID: i64 = 42;
+
+/// Number of rows in the fake table.
+const ROW_COUNT: usize = 5;
+
+/// Query received by the fake node.
+#[derive(Debug, Default, Clone)]
+struct QueryLog {
+    schema: Option<String>,
+    sql: Option<String>,
+    args: Vec<i32>,
+    statement_type: i8,
+    pages: usize,
+    closed: Vec<i64>,
+}
+
+/// Write page of the rows of the fake table, starting with the given row.
+/// Every row has ID and name, which is null for the odd IDs.
+fn put_page(buf: &mut Vec<u8>, from: usize, page_size: usize) -> usize {
+    let to = ROW_COUNT.min(from + page_size);
+
+    put_i32(buf, (to - from) as i32);
+    for id in from..to {
+        buf.push(3);
+        put_i32(buf, id as i32);
+
+        if id % 2 == 0 {
+            put_str(buf, &format!("name-{}", id));
+        } else {
+            buf.push(101);
+        }
+    }
+    buf.push((to < ROW_COUNT) as u8);
+
+    to
+}
+
+/// Start node serving the fake table, logging the queries.
+async fn start_sql_node() -> (FakeNode, Arc<Mutex<QueryLog>>) {
+    let log = Arc::new(Mutex::new(QueryLog::default()));
+    let log0 = log.clone();
+
+    // Page size and the next row of the open cursor.
+    let cursor = Mutex::new((0, 0));
+
+    let node = FakeNode::start(move |req| {
+        let mut log = log0.lock().unwrap();
+        let mut payload = Vec::new();
+
+        match req.op_code {
+            OP_QUERY_SQL_FIELDS => {
+                // Cache ID and flags go first.
+                let (schema, pos) = read_str(&req.payload, 5);
+                let page_size = read_i32(&req.payload, pos) as usize;
+                let (sql, pos) = read_str(&req.payload, pos + 8);
+
+                let sql = sql.unwrap();
+                if sql.ends_with("broken") {
+                    return FakeReply::Err(1, "Failed to parse query".to_owned());
+                }
+
+                let args = read_i32(&req.payload, pos);
+                log.args = (0..args as usize)
+                    .map(|i| read_i32(&req.payload, pos + 4 + i * 5 + 1))
+                    .collect();
+                log.statement_type = req.payload[pos + 4 + args as usize * 5] as i8;
+                log.schema = schema;
+                log.sql = Some(sql.clone());
+
+                // Update count is the first argument.
+                if !sql.starts_with("SELECT") {
+                    put_i64(&mut payload, CURSOR_ID);
+                    put_i32(&mut payload, 1);
+                    put_str(&mut payload, "UPDATED");
+                    put_i32(&mut payload, 1);
+                    payload.push(4);
+                    put_i64(&mut payload, log.args.first().cloned().unwrap_or(0).into());
+                    payload.push(0);
+
+                    return FakeReply::Ok(payload);
+                }
+
+                put_i64(&mut payload, CURSOR_ID);
+                put_i32(&mut payload, 2);
+                put_str(&mut payload, "ID");
+                put_str(&mut payload, "NAME");
+
+                let next = put_page(&mut payload, 0, page_size);
+                *cursor.lock().unwrap() = (page_size, next);
+            }
+            OP_QUERY_SQL_FIELDS_CURSOR_GET_PAGE => {
+                assert_eq!(read_i64(&req.payload, 0), CURSOR_ID);
+                log.pages += 1;
+
+                let mut cursor = cursor.lock().unwrap();
+                cursor.1 = put_page(&mut payload, cursor.1, cursor.0);
+            }
+            OP_RESOURCE_CLOSE => log.closed.push(read_i64(&req.payload, 0)),
+            _ => put_i32(&mut payload, 0),
+        }
+
+        FakeReply::Ok(payload)
+    })
+    .await;
+
+    (node, log)
+}
+
+/// Start client connected to the node.
+async fn start_client(node: &FakeNode) -> IgniteClient {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&node.endpoint()).unwrap();
+
+    IgniteClient::start(cfg).await.unwrap()
+}
+
+#[test]
+fn sql_fields_query_pages() {
+    run_async(async {
+        let (node, log) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let mut query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+        query.set_page_size(2);
+
+        let cursor = client.query_sql_fields(&query).await.unwrap();
+
+        assert_eq!(cursor.column_names(), &["ID", "NAME"]);
+        assert_eq!(cursor.column_count(), 2);
+        assert_eq!(cursor.column_types(), &[Some("Int"), Some("String")]);
+
+        let rows: Vec<Vec<IgniteValue>> = cursor.try_collect().await.unwrap();
+
+        assert_eq!(rows.len(), ROW_COUNT);
+        assert_eq!(rows[0], vec![0.into(), "name-0".into()]);
+        assert_eq!(rows[3], vec![3.into(), IgniteValue::Null]);
+        assert_eq!(rows[4][0].as_i64(), Some(4));
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.pages, 2);
+        assert_eq!(log.closed, Vec::<i64>::new());
+    });
+}
+
+#[test]
+fn sql_fields_query_args() {
+    run_async(async {
+        let (node, log) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let mut query = SqlFieldsQuery::new("SELECT id FROM Person WHERE id BETWEEN ? AND ?");
+        query.set_schema("TEST");
+        query.set_args(vec![1.into(), 3.into()]);
+
+        let rows: Vec<_> = client
+            .query_sql_fields(&query)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(rows.len(), ROW_COUNT);
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.schema.as_deref(), Some("TEST"));
+        assert_eq!(log.sql.as_deref(), Some(query.get_sql()));
+        assert_eq!(log.args, vec![1, 3]);
+        assert_eq!(log.pages, 0);
+    });
+}
+
+#[test]
+fn sql_fields_query_close_on_drop() {
+    run_async(async {
+        let (node, log) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let mut query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+        query.set_page_size(2);
+
+        let mut cursor = client.query_sql_fields(&query).await.unwrap();
+        cursor.next().await.unwrap().unwrap();
+        drop(cursor);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert_eq!(log.lock().unwrap().closed, vec![CURSOR_ID]);
+    });
+}
+
+#[test]
+fn sql_fields_query_error() {
+    run_async(async {
+        let (node, _) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let err = client
+            .query_sql_fields(&SqlFieldsQuery::new("SELECT broken"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(1));
+    });
+}
+
+/// Row of the fake table.
+#[derive(Debug, PartialEq, FromRow)]
+struct Person {
+    id: i64,
+    #[ignite(rename = "NAME")]
+    full_name: Option<String>,
+}
+
+/// Row of the fake table, which does not allow null names.
+#[derive(Debug, FromRow)]
+struct NamedPerson {
+    #[allow(dead_code)]
+    name: String,
+}
+
+/// Row with the column missing in the fake table.
+#[derive(Debug, FromRow)]
+struct PersonAge {
+    #[allow(dead_code)]
+    age: i32,
+}
+
+#[test]
+fn sql_query_as() {
+    run_async(async {
+        let (node, _) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let mut query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+        query.set_page_size(2);
+        query.set_include_field_names(false);
+
+        let people: Vec<Person> = client
+            .query_as(&query)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(people.len(), ROW_COUNT);
+        assert_eq!(
+            people[2],
+            Person {
+                id: 2,
+                full_name: Some("name-2".to_owned())
+            }
+        );
+        assert_eq!(people[3].full_name, None);
+    });
+}
+
+#[test]
+fn sql_query_as_mismatch() {
+    run_async(async {
+        let (node, _) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let query = SqlFieldsQuery::new("SELECT id, name FROM Person");
+
+        let rows: Vec<IgniteResult<NamedPerson>> =
+            client.query_as(&query).await.unwrap().collect().await;
+
+        assert!(rows[0].is_ok());
+        let err = rows[1].as_ref().unwrap_err();
+        assert!(err.to_string().contains("column NAME"), "{}", err);
+
+        let mut cursor = client.query_as::<PersonAge>(&query).await.unwrap();
+        let err = cursor.try_next().await.unwrap_err();
+        assert_eq!(err.to_string(), "Column age is not found");
+    });
+}
+
+#[test]
+fn sql_execute() {
+    run_async(async {
+        let (node, log) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let count = client
+            .execute("CREATE TABLE Person (id INT PRIMARY KEY)", vec![])
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(log.lock().unwrap().statement_type, 2);
+
+        let count = client
+            .execute("INSERT INTO Person (id) VALUES (?)", vec![3.into()])
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let mut query = SqlFieldsQuery::new("UPDATE Person SET id = ?");
+        query.set_args(vec![7.into()]);
+        assert_eq!(client.execute_query(&query).await.unwrap(), 7);
+        assert_eq!(log.lock().unwrap().statement_type, 0);
+    });
+}
+
+#[test]
+fn sql_execute_batch() {
+    run_async(async {
+        let (node, log) = start_sql_node().await;
+        let client = start_client(&node).await;
+
+        let sql = "INSERT INTO Person (id) VALUES (?)";
+        let counts = client
+            .execute_batch(sql, vec![vec![1.into()], vec![2.into()], vec![3.into()]])
+            .await
+            .unwrap();
+
+        assert_eq!(counts, vec![1, 2, 3]);
+        assert_eq!(log.lock().unwrap().args, vec![3]);
+
+        let err = client
+            .execute_batch("INSERT broken", vec![vec![1.into()]])
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(1));
+        assert_eq!(err.to_string(), "Failed to execute argument set 0");
+
+        let source = err.source().unwrap().downcast_ref::<IgniteError>().unwrap();
+        assert_eq!(source.kind(), ErrorKind::Server(1));
+    });
+}
@@ -0,0 +1,109 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ignite_rust::*;
+use tokio::net::TcpListener;
+
+/// Start node, delaying responses to the get requests for a second.
+async fn start_slow_node() -> FakeNode {
+    FakeNode::start(|req| match req.op_code {
+        OP_CACHE_GET => FakeReply::Delayed(Duration::from_secs(1), vec![101]),
+        _ => FakeReply::Ok(Vec::new()),
+    })
+    .await
+}
+
+#[test]
+fn timeout_handshake() {
+    run_async(async {
+        // Accepts connections, but never responds.
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = listener.local_addr().unwrap().to_string();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let accepted0 = accepted.clone();
+
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                accepted0.fetch_add(1, Ordering::SeqCst);
+                conns.push(conn);
+            }
+        });
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&endpoint).unwrap();
+        cfg.set_handshake_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let res = IgniteClient::start(cfg).await;
+
+        assert!(res.is_err());
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn timeout_request() {
+    run_async(async {
+        let node = start_slow_node().await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+        cfg.set_request_timeout(Some(Duration::from_millis(100)));
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        let err = cache.get(&1).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+        assert!(started.elapsed() < Duration::from_millis(500));
+
+        // Timed out request does not break the connection, the late response is dropped.
+        let value = cache
+            .with_timeout(Duration::from_secs(5))
+            .get(&1)
+            .await
+            .unwrap();
+
+        assert_eq!(value, None);
+        assert_eq!(node.connections(), 1);
+    });
+}
+
+#[test]
+fn timeout_per_cache() {
+    run_async(async {
+        let node = start_slow_node().await;
+
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint()).unwrap();
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+        let cache = client
+            .get_or_create_cache::<i32, i32>("cache1".to_owned())
+            .await
+            .unwrap();
+
+        let fast = cache.with_timeout(Duration::from_millis(100));
+        assert_eq!(fast.timeout(), Some(Duration::from_millis(100)));
+        assert_eq!(cache.timeout(), None);
+
+        let err = fast.get(&1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+
+        // Without timeout the operation waits for the response.
+        assert_eq!(cache.get(&1).await.unwrap(), None);
+    });
+}
@@ -0,0 +1,216 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ignite_rust::*;
+use tokio_rustls::rustls::internal::pemfile;
+use tokio_rustls::rustls::{AllowAnyAuthenticatedClient, NoClientAuth};
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+
+/// Get path to the file with the test certificate or key.
+fn resource(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("resources")
+        .join("tls")
+        .join(name)
+}
+
+/// Make TLS configuration of the fake node, requiring client certificate if asked.
+fn server_tls(client_auth: bool) -> Arc<ServerConfig> {
+    let verifier = if client_auth {
+        let mut roots = RootCertStore::empty();
+        roots
+            .add_pem_file(&mut BufReader::new(File::open(resource("ca.pem")).unwrap()))
+            .unwrap();
+
+        AllowAnyAuthenticatedClient::new(roots)
+    } else {
+        NoClientAuth::new()
+    };
+
+    let certs = pemfile::certs(&mut BufReader::new(
+        File::open(resource("server.pem")).unwrap(),
+    ))
+    .unwrap();
+    let mut keys = pemfile::pkcs8_private_keys(&mut BufReader::new(
+        File::open(resource("server.key")).unwrap(),
+    ))
+    .unwrap();
+
+    let mut config = ServerConfig::new(verifier);
+    config.set_single_cert(certs, keys.remove(0)).unwrap();
+
+    Arc::new(config)
+}
+
+/// Start TLS node, replying to every request with an empty list.
+async fn start_node(client_auth: bool) -> FakeNode {
+    let options = FakeNodeOptions {
+        tls: Some(server_tls(client_auth)),
+        ..FakeNodeOptions::default()
+    };
+
+    FakeNode::start_with(options, |_| FakeReply::Ok(empty_names_payload())).await
+}
+
+/// Start client connecting to the node with the given TLS configuration.
+async fn start_client(node: &FakeNode, tls: TlsConfiguration) -> IgniteResult<IgniteClient> {
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&node.endpoint()).unwrap();
+    cfg.set_retry_limit(0);
+    cfg.set_tls(tls);
+
+    IgniteClient::start(cfg).await
+}
+
+/// Make TLS configuration trusting the test CA.
+fn trusting_tls() -> TlsConfiguration {
+    let mut tls = TlsConfiguration::new();
+    tls.set_ca_file(resource("ca.pem"));
+    tls
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_connects_to_trusted_node() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        // Certificate is issued for localhost, not for the IP address.
+        let mut cfg = ClientConfiguration::new();
+        cfg.set_endpoints(&node.endpoint().replace("127.0.0.1", "localhost"))
+            .unwrap();
+        cfg.set_tls(trusting_tls());
+
+        let client = IgniteClient::start(cfg).await.unwrap();
+
+        assert_eq!(client.cache_names().await.unwrap(), Vec::<String>::new());
+        assert_eq!(node.requests(), 1);
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_uses_server_name() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let mut tls = trusting_tls();
+        tls.set_server_name("localhost");
+
+        let client = start_client(&node, tls).await.unwrap();
+
+        client.cache_names().await.unwrap();
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_rejects_untrusted_node() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let mut tls = TlsConfiguration::new();
+        tls.set_server_name("localhost");
+
+        let err = start_client(&node, tls).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Connection);
+        assert_eq!(node.requests(), 0);
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_verify_mode_ca_only() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let mut tls = trusting_tls();
+        tls.set_server_name("example.com");
+
+        assert!(start_client(&node, tls.clone()).await.is_err());
+
+        tls.set_verify_mode(TlsVerifyMode::CaOnly);
+
+        let client = start_client(&node, tls).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        // Chain is still verified.
+        let mut tls = TlsConfiguration::new();
+        tls.set_verify_mode(TlsVerifyMode::CaOnly);
+
+        assert!(start_client(&node, tls).await.is_err());
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_verify_mode_none() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let mut tls = TlsConfiguration::new();
+        tls.set_verify_mode(TlsVerifyMode::None);
+
+        let client = start_client(&node, tls).await.unwrap();
+        client.cache_names().await.unwrap();
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_client_certificate() {
+    run_async(async {
+        let node = start_node(true).await;
+
+        let mut tls = trusting_tls();
+        tls.set_server_name("localhost");
+
+        assert!(start_client(&node, tls.clone()).await.is_err());
+        assert_eq!(node.requests(), 0);
+
+        tls.set_client_certificate(resource("client.pem"), resource("client.key"));
+
+        let client = start_client(&node, tls).await.unwrap();
+        client.cache_names().await.unwrap();
+
+        assert_eq!(node.requests(), 1);
+    });
+}
+
+#[cfg(any(feature = "tls-rustls", feature = "tls-native"))]
+#[test]
+fn tls_missing_files() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let mut tls = TlsConfiguration::new();
+        tls.set_ca_file(resource("missing.pem"));
+
+        let err = start_client(&node, tls).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::General);
+        assert_eq!(node.connections(), 0);
+    });
+}
+
+#[cfg(not(any(feature = "tls-rustls", feature = "tls-native")))]
+#[test]
+fn tls_not_supported() {
+    run_async(async {
+        let node = start_node(false).await;
+
+        let err = start_client(&node, trusting_tls()).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::General);
+        assert_eq!(node.connections(), 0);
+    });
+}
@@ -0,0 +1,374 @@
+extern crate ignite_rust;
+
+mod utils;
+use utils::*;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::FutureExt;
+use ignite_rust::*;
+
+/// Op code of the request putting value by key.
+const OP_CACHE_PUT: i16 = 1001;
+
+/// Op code of the request checking if the key is in cache.
+const OP_CACHE_CONTAINS_KEY: i16 = 1011;
+
+/// Op code of the request removing the key.
+const OP_CACHE_REMOVE_KEY: i16 = 1016;
+
+/// Op code of the request starting the transaction.
+const OP_TX_START: i16 = 4000;
+
+/// Op code of the request finishing the transaction.
+const OP_TX_END: i16 = 4001;
+
+/// Flag of the cache operation made within the transaction.
+const FLAG_TRANSACTIONAL: u8 = 2;
+
+/// Status of the failed request.
+const STATUS_FAILED: i32 = 1;
+
+/// Error reported by the server when the optimistic transaction can not be committed.
+const CONFLICT_ERROR: &str =
+    "class org.apache.ignite.transactions.TransactionOptimisticException: \
+    Failed to prepare transaction, read/write conflict [key=1, cache=accounts]";
+
+/// Transaction started on the fake node.
+#[derive(Debug, Clone, PartialEq)]
+struct TxStart {
+    node: usize,
+    concurrency: u8,
+    isolation: u8,
+    timeout: i64,
+    label: Option<String>,
+}
+
+/// Requests received by the fake nodes.
+#[derive(Debug, Default, Clone)]
+struct TxLog {
+    started: Vec<TxStart>,
+    // Node, transaction ID and whether it is committed.
+    ended: Vec<(usize, i32, bool)>,
+    // Node, op code and transaction ID of the cache operations.
+    ops: Vec<(usize, i16, Option<i32>)>,
+    // Number of the following commits failing because of the conflict.
+    conflicts: u32,
+}
+
+/// Start node with the given index, logging the transactions and cache operations.
+async fn start_node(idx: usize, version: (i16, i16, i16), log: Arc<Mutex<TxLog>>) -> FakeNode {
+    let options = FakeNodeOptions {
+        version,
+        ..Default::default()
+    };
+
+    FakeNode::start_with(options, move |req| {
+        let mut log = log.lock().unwrap();
+
+        let payload = match req.op_code {
+            OP_TX_START => {
+                let (label, _) = read_str(&req.payload, 10);
+                log.started.push(TxStart {
+                    node: idx,
+                    concurrency: req.payload[0],
+                    isolation: req.payload[1],
+                    timeout: read_i64(&req.payload, 2),
+                    label,
+                });
+
+                let mut payload = Vec::new();
+                put_i32(&mut payload, log.started.len() as i32);
+                payload
+            }
+            OP_TX_END => {
+                let committed = req.payload[4] != 0;
+                log.ended.push((idx, read_i32(&req.payload, 0), committed));
+
+                if committed && log.conflicts > 0 {
+                    log.conflicts -= 1;
+                    return FakeReply::Err(STATUS_FAILED, CONFLICT_ERROR.to_owned());
+                }
+                Vec::new()
+            }
+            OP_CACHE_GET | OP_CACHE_PUT | OP_CACHE_CONTAINS_KEY | OP_CACHE_REMOVE_KEY => {
+                // Transaction ID follows the cache ID and flags.
+                let tx_id = if req.payload[4] & FLAG_TRANSACTIONAL != 0 {
+                    Some(read_i32(&req.payload, 5))
+                } else {
+                    None
+                };
+                log.ops.push((idx, req.op_code, tx_id));
+
+                match req.op_code {
+                    OP_CACHE_GET => vec![101],
+                    OP_CACHE_PUT => Vec::new(),
+                    _ => vec![1],
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        FakeReply::Ok(payload)
+    })
+    .await
+}
+
+/// Start client connected to the nodes, using them one after another.
+async fn start_client(nodes: &[&FakeNode]) -> IgniteClient {
+    let mut cfg = client_config_without_pa(nodes);
+    cfg.set_load_balancing(LoadBalancing::RoundRobin);
+
+    IgniteClient::start(cfg).await.unwrap()
+}
+
+#[test]
+fn tx_commit_pins_operations() {
+    run_async(async {
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node0 = start_node(0, FAKE_NODE_VERSION_1_7, log.clone()).await;
+        let node1 = start_node(1, FAKE_NODE_VERSION_1_7, log.clone()).await;
+
+        let client = start_client(&[&node0, &node1]).await;
+        let cache = client
+            .get_or_create_cache::<i32, i32>("accounts".to_owned())
+            .await
+            .unwrap();
+
+        let tx = client
+            .transactions()
+            .tx_start(
+                TransactionConcurrency::Pessimistic,
+                TransactionIsolation::RepeatableRead,
+                Some(Duration::from_secs(5)),
+                Some("transfer"),
+            )
+            .await
+            .unwrap();
+
+        let accounts = cache.with_transaction(&tx);
+        assert_eq!(accounts.transaction_id(), Some(tx.id()));
+        assert_eq!(cache.transaction_id(), None);
+
+        for key in 0..4 {
+            accounts.put(&key, &key).await.unwrap();
+        }
+        assert_eq!(accounts.get(&1).await.unwrap(), None);
+        assert!(accounts.contains_key(&1).await.unwrap());
+        assert!(accounts.remove(&1).await.unwrap());
+
+        // Operations out of the transaction are balanced as usual.
+        cache.put(&10, &10).await.unwrap();
+        cache.put(&11, &11).await.unwrap();
+
+        let tx_id = tx.id();
+        tx.commit().await.unwrap();
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.started.len(), 1);
+
+        let start = &log.started[0];
+        assert_eq!(start.concurrency, 1);
+        assert_eq!(start.isolation, 1);
+        assert_eq!(start.timeout, 5000);
+        assert_eq!(start.label.as_deref(), Some("transfer"));
+
+        let tx_ops: Vec<_> = log.ops.iter().filter(|op| op.2.is_some()).collect();
+        assert_eq!(tx_ops.len(), 7);
+        assert!(tx_ops
+            .iter()
+            .all(|op| op.0 == start.node && op.2 == Some(tx_id)));
+
+        let other_nodes: Vec<_> = log
+            .ops
+            .iter()
+            .filter(|op| op.2.is_none())
+            .map(|op| op.0)
+            .collect();
+        assert_eq!(other_nodes.len(), 2);
+        assert_ne!(other_nodes[0], other_nodes[1]);
+
+        assert_eq!(log.ended, vec![(start.node, tx_id, true)]);
+    });
+}
+
+#[test]
+fn tx_rollback_and_drop() {
+    run_async(async {
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node = start_node(0, FAKE_NODE_VERSION_1_7, log.clone()).await;
+
+        let client = start_client(&[&node]).await;
+        let transactions = client.transactions();
+
+        let tx = transactions
+            .tx_start(
+                TransactionConcurrency::Optimistic,
+                TransactionIsolation::Serializable,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let rolled_back = tx.id();
+        tx.rollback().await.unwrap();
+
+        let tx = transactions
+            .tx_start(
+                TransactionConcurrency::Optimistic,
+                TransactionIsolation::ReadCommitted,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let dropped = tx.id();
+        drop(tx);
+
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.started[0].concurrency, 0);
+        assert_eq!(log.started[0].isolation, 2);
+        assert_eq!(log.started[0].timeout, 0);
+        assert_eq!(log.started[0].label, None);
+
+        assert_eq!(
+            log.ended,
+            vec![(0, rolled_back, false), (0, dropped, false)]
+        );
+    });
+}
+
+#[test]
+fn tx_unsupported() {
+    run_async(async {
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node = start_node(0, FAKE_NODE_VERSION_1_4, log.clone()).await;
+
+        let client = start_client_without_pa(&node).await;
+
+        let err = client
+            .transactions()
+            .tx_start(
+                TransactionConcurrency::Pessimistic,
+                TransactionIsolation::ReadCommitted,
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(log.lock().unwrap().started.is_empty());
+    });
+}
+
+/// Make options of the optimistic serializable transaction, retried without delays.
+fn optimistic_options(max_attempts: u32) -> TransactionOptions {
+    let mut opts = TransactionOptions::new();
+    opts.set_concurrency(TransactionConcurrency::Optimistic);
+    opts.set_isolation(TransactionIsolation::Serializable);
+    opts.set_max_attempts(max_attempts);
+    opts.set_retry_backoff(Duration::from_millis(1), Duration::from_millis(5));
+    opts
+}
+
+#[test]
+fn transactional_retries_conflicts() {
+    run_async(async {
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node = start_node(0, FAKE_NODE_VERSION_1_7, log.clone()).await;
+
+        let client = start_client(&[&node]).await;
+        let cache = client
+            .get_or_create_cache::<i32, i32>("accounts".to_owned())
+            .await
+            .unwrap();
+
+        log.lock().unwrap().conflicts = 2;
+
+        let mut opts = optimistic_options(3);
+        opts.set_label(Some("transfer"));
+
+        let mut runs = 0;
+        let res = client
+            .transactional(&opts, |tx| {
+                runs += 1;
+                let accounts = cache.with_transaction(tx);
+                async move {
+                    accounts.put(&1, &10).await?;
+                    Ok(accounts.transaction_id().unwrap())
+                }
+                .boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(runs, 3);
+        assert_eq!(res, 3);
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.started.len(), 3);
+        assert!(log
+            .started
+            .iter()
+            .all(|start| start.label.as_deref() == Some("transfer")));
+        assert_eq!(log.ended, vec![(0, 1, true), (0, 2, true), (0, 3, true)]);
+        assert_eq!(
+            log.ops.iter().map(|op| op.2).collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(3)]
+        );
+
+        // Conflicts keep happening after the last attempt.
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node = start_node(0, FAKE_NODE_VERSION_1_7, log.clone()).await;
+        let client = start_client(&[&node]).await;
+
+        log.lock().unwrap().conflicts = 5;
+
+        let err = client
+            .transactional(&optimistic_options(2), |_| async { Ok(()) }.boxed())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(STATUS_FAILED));
+        assert_eq!(log.lock().unwrap().started.len(), 2);
+    });
+}
+
+#[test]
+fn transactional_rolls_back_on_error() {
+    run_async(async {
+        let log = Arc::new(Mutex::new(TxLog::default()));
+        let node = start_node(0, FAKE_NODE_VERSION_1_7, log.clone()).await;
+
+        let client = start_client(&[&node]).await;
+
+        let err = client
+            .transactional(&optimistic_options(3), |_| {
+                async { Err::<(), _>(IgniteError::new("Insufficient funds")) }.boxed()
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "Insufficient funds");
+        assert_eq!(log.lock().unwrap().ended, vec![(0, 1, false)]);
+
+        // Only optimistic serializable transactions are retried.
+        log.lock().unwrap().conflicts = 1;
+
+        let err = client
+            .transactional(&TransactionOptions::new(), |_| async { Ok(()) }.boxed())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Server(STATUS_FAILED));
+
+        let log = log.lock().unwrap().clone();
+        assert_eq!(log.started.len(), 2);
+        assert_eq!(log.started[1].concurrency, 1);
+        assert_eq!(log.ended, vec![(0, 1, false), (0, 2, true)]);
+    });
+}
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::{AbortHandle, Abortable};
+use ignite_rust::{ClientConfiguration, IgniteClient};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// Protocol version the fake node accepts by default.
+pub const FAKE_NODE_VERSION: (i16, i16, i16) = (1, 2, 0);
+
+/// Protocol version with partition awareness support.
+pub const FAKE_NODE_VERSION_1_4: (i16, i16, i16) = (1, 4, 0);
+
+/// Protocol version with feature negotiation and user attributes support.
+pub const FAKE_NODE_VERSION_1_7: (i16, i16, i16) = (1, 7, 0);
+
+/// Op code of the request closing the resource.
+pub const OP_RESOURCE_CLOSE: i16 = 0;
+
+/// Op code of the request getting value by key.
+pub const OP_CACHE_GET: i16 = 1000;
+
+/// Options of the fake node.
+#[derive(Clone)]
+pub struct FakeNodeOptions {
+    /// Protocol version the node accepts.
+    pub version: (i16, i16, i16),
+    /// ID of the node, sent in the handshake response since version 1.4.
+    pub node_id: u128,
+    /// TLS configuration. Connections are plain if not set.
+    pub tls: Option<Arc<ServerConfig>>,
+    /// User and password the node accepts. Any credentials are accepted if not set.
+    pub credentials: Option<(String, String)>,
+    /// Bitmask of the features, the node supports since version 1.7.
+    pub features: Vec<u8>,
+    /// Address to listen on. Random local port is used if not set.
+    pub addr: Option<SocketAddr>,
+}
+
+impl Default for FakeNodeOptions {
+    fn default() -> Self {
+        FakeNodeOptions {
+            version: FAKE_NODE_VERSION,
+            node_id: 0,
+            tls: None,
+            credentials: None,
+            features: Vec::new(),
+            addr: None,
+        }
+    }
+}
+
+/// Request received by the fake node.
+#[derive(Debug)]
+pub struct FakeRequest {
+    pub op_code: i16,
+    pub req_id: i64,
+    pub payload: Vec<u8>,
+}
+
+/// Reply of the fake node to a request.
+#[derive(Debug)]
+pub enum FakeReply {
+    /// Respond with success and the given payload.
+    Ok(Vec<u8>),
+    /// Respond with the error status and message.
+    Err(i32, String),
+    /// Respond with success and the given payload after a delay.
+    /// Other requests on the connection are not processed meanwhile.
+    Delayed(Duration, Vec<u8>),
+    /// Close the connection without responding.
+    Drop,
+    /// Respond with success and the given payload, then push the notifications.
+    /// Notifications are only supported since version 1.4.
+    Notify(Vec<u8>, Vec<FakeNotification>),
+}
+
+/// Notification pushed by the fake node for the resource.
+#[derive(Debug)]
+pub struct FakeNotification {
+    pub resource_id: i64,
+    pub op_code: i16,
+    pub payload: Vec<u8>,
+    /// Status and message of the error, sent instead of the payload.
+    pub error: Option<(i32, String)>,
+}
+
+/// Handler, producing replies for the requests.
+pub type FakeHandler = Arc<dyn Fn(&FakeRequest) -> FakeReply + Send + Sync>;
+
+/// State of the node, shared with the tasks serving connections.
+struct NodeState {
+    options: FakeNodeOptions,
+    handler: FakeHandler,
+    connections: AtomicUsize,
+    requests: AtomicUsize,
+    topology: AtomicI64,
+    user_attributes: Mutex<Vec<HashMap<String, String>>>,
+}
+
+/// Simple in-process server speaking the thin client protocol.
+/// Used to test the client without running a real Ignite node.
+pub struct FakeNode {
+    addr: SocketAddr,
+    state: Arc<NodeState>,
+    handle: AbortHandle,
+}
+
+impl FakeNode {
+    /// Start a new node on a random local port.
+    pub async fn start<F>(handler: F) -> FakeNode
+    where
+        F: Fn(&FakeRequest) -> FakeReply + Send + Sync + 'static,
+    {
+        FakeNode::start_with(FakeNodeOptions::default(), handler).await
+    }
+
+    /// Start a new node with the given options, on a random local port by default.
+    pub async fn start_with<F>(options: FakeNodeOptions, handler: F) -> FakeNode
+    where
+        F: Fn(&FakeRequest) -> FakeReply + Send + Sync + 'static,
+    {
+        let bind_addr = options
+            .addr
+            .unwrap_or_else(|| "127.0.0.1:0".parse().unwrap());
+        let mut listener = TcpListener::bind(bind_addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let acceptor = options.tls.clone().map(TlsAcceptor::from);
+        let state = Arc::new(NodeState {
+            options,
+            handler: Arc::new(handler),
+            connections: AtomicUsize::new(0),
+            requests: AtomicUsize::new(0),
+            topology: AtomicI64::new(1),
+            user_attributes: Mutex::new(Vec::new()),
+        });
+
+        let state0 = state.clone();
+        let accept = async move {
+            loop {
+                let (conn, _) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(_) => return,
+                };
+
+                state0.connections.fetch_add(1, Ordering::SeqCst);
+
+                let state = state0.clone();
+                let acceptor = acceptor.clone();
+
+                tokio::spawn(async move {
+                    match acceptor {
+                        // Client is not served if TLS handshake fails.
+                        Some(acceptor) => {
+                            if let Ok(conn) = acceptor.accept(conn).await {
+                                serve(conn, state).await;
+                            }
+                        }
+                        None => serve(conn, state).await,
+                    }
+                });
+            }
+        };
+
+        let (handle, reg) = AbortHandle::new_pair();
+        tokio::spawn(Abortable::new(accept, reg));
+
+        FakeNode {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    /// Get endpoint of the node in the format accepted by the client configuration.
+    pub fn endpoint(&self) -> String {
+        format!("{}", self.addr)
+    }
+
+    /// Get number of the accepted connections.
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    /// Get number of the received requests, excluding handshakes.
+    pub fn requests(&self) -> usize {
+        self.state.requests.load(Ordering::SeqCst)
+    }
+
+    /// Get user attributes, received in the accepted handshakes.
+    /// Only sent by the client since version 1.7.
+    pub fn user_attributes(&self) -> Vec<HashMap<String, String>> {
+        self.state.user_attributes.lock().unwrap().clone()
+    }
+
+    /// Set affinity topology version. Since version 1.4 the change is reported
+    /// to the client in the header of the next response on every connection.
+    pub fn set_topology_version(&self, ver: i64) {
+        self.state.topology.store(ver, Ordering::SeqCst);
+    }
+}
+
+impl Drop for FakeNode {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Make configuration of the client connected to the nodes, with partition awareness
+/// disabled. Use it with the nodes, which do not answer the partitions request.
+pub fn client_config_without_pa(nodes: &[&FakeNode]) -> ClientConfiguration {
+    let endpoints: Vec<_> = nodes.iter().map(|node| node.endpoint()).collect();
+
+    let mut cfg = ClientConfiguration::new();
+    cfg.set_endpoints(&endpoints.join(",")).unwrap();
+    cfg.set_partition_awareness(false);
+
+    cfg
+}
+
+/// Start client connected to the node, with partition awareness disabled.
+pub async fn start_client_without_pa(node: &FakeNode) -> IgniteClient {
+    IgniteClient::start(client_config_without_pa(&[node]))
+        .await
+        .unwrap()
+}
+
+/// Serve a single connection, either plain or secure.
+async fn serve<S: AsyncRead + AsyncWrite + Unpin>(mut conn: S, state: Arc<NodeState>) {
+    let options = &state.options;
+    let flags_supported = options.version >= FAKE_NODE_VERSION_1_4;
+    let features_supported = options.version >= FAKE_NODE_VERSION_1_7;
+
+    loop {
+        let msg = match read_message(&mut conn).await {
+            Some(msg) => msg,
+            None => return,
+        };
+
+        let ver = (read_i16(&msg, 1), read_i16(&msg, 3), read_i16(&msg, 5));
+
+        if ver == options.version {
+            // Operation code, version and client type, followed by the features
+            // and user attributes since 1.7, and then by the credentials.
+            let mut pos = 8;
+            if features_supported {
+                pos += 5 + read_i32(&msg, pos + 1) as usize;
+
+                let (attributes, next) = read_str_map(&msg, pos);
+                state.user_attributes.lock().unwrap().push(attributes);
+                pos = next;
+            }
+
+            let (user, pos) = read_str(&msg, pos);
+            let (pass, _) = read_str(&msg, pos);
+
+            if let Some((exp_user, exp_pass)) = &options.credentials {
+                if (user.as_ref(), pass.as_ref()) != (Some(exp_user), Some(exp_pass)) {
+                    let mut rsp = vec![0u8];
+                    put_i16(&mut rsp, ver.0);
+                    put_i16(&mut rsp, ver.1);
+                    put_i16(&mut rsp, ver.2);
+                    put_str(&mut rsp, "Invalid credentials");
+                    put_i32(&mut rsp, 2000);
+
+                    write_message(&mut conn, &rsp).await;
+                    return;
+                }
+            }
+
+            let mut rsp = vec![1u8];
+            if features_supported {
+                rsp.push(12);
+                put_i32(&mut rsp, options.features.len() as i32);
+                rsp.extend_from_slice(&options.features);
+            }
+            if flags_supported {
+                put_uuid(&mut rsp, options.node_id);
+            }
+
+            write_message(&mut conn, &rsp).await;
+            break;
+        }
+
+        let mut rsp = vec![0u8];
+        put_i16(&mut rsp, options.version.0);
+        put_i16(&mut rsp, options.version.1);
+        put_i16(&mut rsp, options.version.2);
+        put_str(&mut rsp, "Unsupported version");
+
+        write_message(&mut conn, &rsp).await;
+    }
+
+    let mut reported_topology = 0;
+
+    loop {
+        let msg = match read_message(&mut conn).await {
+            Some(msg) => msg,
+            None => return,
+        };
+
+        state.requests.fetch_add(1, Ordering::SeqCst);
+
+        let req = FakeRequest {
+            op_code: read_i16(&msg, 0),
+            req_id: read_i64(&msg, 2),
+            payload: msg[10..].to_vec(),
+        };
+
+        let mut notifications = Vec::new();
+
+        let (status, payload) = match (state.handler)(&req) {
+            FakeReply::Ok(payload) => (0, payload),
+            FakeReply::Notify(payload, pushed) => {
+                notifications = pushed;
+                (0, payload)
+            }
+            FakeReply::Err(status, err) => {
+                let mut payload = Vec::new();
+                put_str(&mut payload, &err);
+                (status, payload)
+            }
+            FakeReply::Delayed(delay, payload) => {
+                time::delay_for(delay).await;
+                (0, payload)
+            }
+            FakeReply::Drop => return,
+        };
+
+        let mut rsp = Vec::new();
+        put_i64(&mut rsp, req.req_id);
+
+        if flags_supported {
+            let topology = state.topology.load(Ordering::SeqCst);
+
+            let mut flags = 0i16;
+            if status != 0 {
+                flags |= 1;
+            }
+            if topology != reported_topology {
+                flags |= 2;
+            }
+            put_i16(&mut rsp, flags);
+
+            if topology != reported_topology {
+                put_i64(&mut rsp, topology);
+                put_i32(&mut rsp, 0);
+                reported_topology = topology;
+            }
+
+            if status != 0 {
+                put_i32(&mut rsp, status);
+            }
+        } else {
+            put_i32(&mut rsp, status);
+        }
+
+        rsp.extend_from_slice(&payload);
+
+        write_message(&mut conn, &rsp).await;
+
+        for notification in notifications {
+            let mut msg = Vec::new();
+            put_i64(&mut msg, notification.resource_id);
+
+            match notification.error {
+                Some((status, err)) => {
+                    put_i16(&mut msg, 4 | 1);
+                    put_i16(&mut msg, notification.op_code);
+                    put_i32(&mut msg, status);
+                    put_str(&mut msg, &err);
+                }
+                None => {
+                    put_i16(&mut msg, 4);
+                    put_i16(&mut msg, notification.op_code);
+                    msg.extend_from_slice(&notification.payload);
+                }
+            }
+
+            write_message(&mut conn, &msg).await;
+        }
+    }
+}
+
+/// Read length-prefixed message.
+async fn read_message<S: AsyncRead + Unpin>(conn: &mut S) -> Option<Vec<u8>> {
+    let mut len_buf = [0u8; 4];
+    conn.read_exact(&mut len_buf).await.ok()?;
+
+    let mut msg = vec![0u8; i32::from_le_bytes(len_buf) as usize];
+    conn.read_exact(&mut msg).await.ok()?;
+
+    Some(msg)
+}
+
+/// Write length-prefixed message.
+async fn write_message<S: AsyncWrite + Unpin>(conn: &mut S, msg: &[u8]) {
+    let mut data = Vec::with_capacity(4 + msg.len());
+    put_i32(&mut data, msg.len() as i32);
+    data.extend_from_slice(msg);
+
+    let _ = conn.write_all(&data).await;
+}
+
+pub fn read_i16(data: &[u8], pos: usize) -> i16 {
+    let mut buf = [0u8; 2];
+    buf.copy_from_slice(&data[pos..pos + 2]);
+    i16::from_le_bytes(buf)
+}
+
+pub fn read_i32(data: &[u8], pos: usize) -> i32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&data[pos..pos + 4]);
+    i32::from_le_bytes(buf)
+}
+
+pub fn read_i64(data: &[u8], pos: usize) -> i64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&data[pos..pos + 8]);
+    i64::from_le_bytes(buf)
+}
+
+pub fn put_i16(buf: &mut Vec<u8>, val: i16) {
+    buf.extend_from_slice(&val.to_le_bytes());
+}
+
+pub fn put_i32(buf: &mut Vec<u8>, val: i32) {
+    buf.extend_from_slice(&val.to_le_bytes());
+}
+
+pub fn put_i64(buf: &mut Vec<u8>, val: i64) {
+    buf.extend_from_slice(&val.to_le_bytes());
+}
+
+/// Read string with the type header, which can be null.
+/// Returns the position after the string.
+pub fn read_str(data: &[u8], pos: usize) -> (Option<String>, usize) {
+    if data[pos] == 101 {
+        return (None, pos + 1);
+    }
+
+    let len = read_i32(data, pos + 1) as usize;
+    let val = String::from_utf8(data[pos + 5..pos + 5 + len].to_vec()).unwrap();
+
+    (Some(val), pos + 5 + len)
+}
+
+/// Read map of strings with the type header, which can be null.
+/// Returns the position after the map.
+pub fn read_str_map(data: &[u8], pos: usize) -> (HashMap<String, String>, usize) {
+    let mut map = HashMap::new();
+    if data[pos] == 101 {
+        return (map, pos + 1);
+    }
+
+    // Header, size and kind of the map.
+    let len = read_i32(data, pos + 1);
+    let mut pos = pos + 6;
+
+    for _ in 0..len {
+        let (key, next) = read_str(data, pos);
+        let (value, next) = read_str(data, next);
+
+        map.insert(key.unwrap(), value.unwrap());
+        pos = next;
+    }
+
+    (map, pos)
+}
+
+/// Write string with the type header.
+pub fn put_str(buf: &mut Vec<u8>, val: &str) {
+    buf.push(9);
+    put_i32(buf, val.len() as i32);
+    buf.extend_from_slice(val.as_bytes());
+}
+
+/// Write UUID with the type header.
+pub fn put_uuid(buf: &mut Vec<u8>, val: u128) {
+    buf.push(10);
+    put_i64(buf, (val >> 64) as i64);
+    put_i64(buf, val as i64);
+}
+
+/// Make payload of the empty cache names response. Since it is a single zero
+/// integer, it also fits other requests returning an empty list.
+pub fn empty_names_payload() -> Vec<u8> {
+    let mut payload = Vec::new();
+    put_i32(&mut payload, 0);
+    payload
+}
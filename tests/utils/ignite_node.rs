@@ -1,14 +1,14 @@
 use std::env;
+use std::env::current_dir;
 use std::ffi::OsStr;
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
-use std::env::current_dir;
 use std::time::{Duration, Instant};
 
 use tokio::time;
 
-use crate::IgniteResult;
 use crate::IgniteError;
+use crate::IgniteResult;
 
 /// Simple abstraction to allow rust users find and run Ignite node.
 /// Used for test purposes.
@@ -21,17 +21,17 @@ impl IgniteNode {
     /// Try to start a new instance of Ignite node.
     pub fn start<P: AsRef<OsStr>>(cfg_path: P) -> IgniteResult<Self> {
         let process = Some(start_process(cfg_path)?);
-        Ok(Self{process})
+        Ok(Self { process })
     }
 
     /// Try to stop this node.
     pub fn stop(&mut self) -> IgniteResult<()> {
         match &mut self.process {
-            None => {},
+            None => {}
             Some(node) => {
                 kill_process_tree(node.id())?;
                 self.process = None;
-            },
+            }
         };
         Ok(())
     }
@@ -64,7 +64,10 @@ pub fn kill_process_tree(proc_id: u32) -> IgniteResult<()> {
         return Ok(());
     }
 
-    Err(IgniteError::new(format!("Error while killing process: {}", res.to_string())))
+    Err(IgniteError::new(format!(
+        "Error while killing process: {}",
+        res
+    )))
 }
 
 /// Killing process tree with all it's children on Unix-like systems.
@@ -84,7 +87,10 @@ pub fn kill_process_tree(proc_id: u32) -> IgniteResult<()> {
         return Ok(());
     }
 
-    Err(IgniteError::new(format!("Error while killing process: {}", res.to_string())))
+    Err(IgniteError::new(format!(
+        "Error while killing process: {}",
+        res
+    )))
 }
 
 /// Start node for tests.
@@ -102,8 +108,8 @@ pub async fn start_test_node(cfg_name: &str) -> IgniteResult<IgniteNode> {
 
 /// Wait until the node is available for connection by thin client.
 pub async fn wait_till_available(port: u16, timeout: u64) -> IgniteResult<()> {
-    use ignite_rust::IgniteClient;
     use ignite_rust::ClientConfiguration;
+    use ignite_rust::IgniteClient;
 
     let mut cfg = ClientConfiguration::new();
     cfg.set_endpoints(&format!("127.0.0.1:{}", port))?;
@@ -142,8 +148,8 @@ fn start_process<P: AsRef<OsStr>>(path: P) -> IgniteResult<Child> {
     };
 
     Ok(Command::new(script_path)
-                .arg(&path)
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()?)
+        .arg(&path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?)
 }
@@ -1,10 +1,17 @@
+#![allow(dead_code)]
+
 extern crate futures;
 extern crate ignite_rust;
 
+mod fake_node;
 mod ignite_node;
 
-pub use ignite_node::IgniteNode;
+#[allow(unused_imports)]
+pub use fake_node::*;
+#[allow(unused_imports)]
 pub use ignite_node::start_test_node;
+#[allow(unused_imports)]
+pub use ignite_node::IgniteNode;
 
 use std::sync::Once;
 
@@ -27,4 +34,4 @@ pub fn run_async<F: Future>(future: F) {
 #[allow(dead_code)]
 pub fn make_unique_name() -> String {
     thread_rng().sample_iter(&Alphanumeric).take(64).collect()
-}
\ No newline at end of file
+}